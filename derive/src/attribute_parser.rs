@@ -27,9 +27,33 @@ pub struct FieldAttributesInfo {
     pub needs_predicate: bool,
     /// Whether the field should be skipped
     pub skipped: bool,
+    /// The bounds the value of the field must be within
+    pub range: Option<AttributeBounds>,
+    /// The bounds the length of the field must be within
+    pub length: Option<AttributeBounds>,
+    /// Whether the field must not be empty
+    pub non_empty: bool,
+    /// The regular expression the field must match
+    pub pattern: Option<LitStr>,
+    /// Whether the field captures the unknown fields of the container
+    pub extra: bool,
 
     /// Span of the `default` attribute, if any, for compile error reporting purposes
     default_span: Option<Span>,
+    /// Span of the `non_empty` attribute, if any, for compile error reporting purposes
+    non_empty_span: Option<Span>,
+    /// Span of the `extra` attribute, if any, for compile error reporting purposes
+    extra_span: Option<Span>,
+}
+
+/// The value of the `range` and `length` field attributes
+///
+/// e.g. `#[deserr(range(min = 1, max = 1000))]`
+#[derive(Debug, Clone)]
+pub struct AttributeBounds {
+    pub min: Option<Expr>,
+    pub max: Option<Expr>,
+    span: Span,
 }
 
 /// The value of the `default` field attribute
@@ -133,6 +157,53 @@ impl FieldAttributesInfo {
             }
             self.try_from = Some(try_from)
         }
+        if let Some(range) = other.range {
+            if let Some(self_range) = &self.range {
+                return Err(syn::Error::new(
+                    self_range.span,
+                    "The `range` field attribute is defined twice.",
+                ));
+            }
+            self.range = Some(range)
+        }
+        if let Some(length) = other.length {
+            if let Some(self_length) = &self.length {
+                return Err(syn::Error::new(
+                    self_length.span,
+                    "The `length` field attribute is defined twice.",
+                ));
+            }
+            self.length = Some(length)
+        }
+        if other.non_empty {
+            if let Some(self_non_empty_span) = self.non_empty_span {
+                return Err(syn::Error::new(
+                    self_non_empty_span,
+                    "The `non_empty` field attribute is defined twice.",
+                ));
+            }
+            self.non_empty = true;
+            self.non_empty_span = other.non_empty_span;
+        }
+        if let Some(pattern) = other.pattern {
+            if let Some(self_pattern) = &self.pattern {
+                return Err(syn::Error::new_spanned(
+                    self_pattern,
+                    "The `pattern` field attribute is defined twice.",
+                ));
+            }
+            self.pattern = Some(pattern)
+        }
+        if other.extra {
+            if let Some(self_extra_span) = self.extra_span {
+                return Err(syn::Error::new(
+                    self_extra_span,
+                    "The `extra` field attribute is defined twice.",
+                ));
+            }
+            self.extra = true;
+            self.extra_span = other.extra_span;
+        }
         self.needs_predicate |= other.needs_predicate;
         self.skipped |= other.skipped;
 
@@ -203,6 +274,34 @@ impl syn::parse::Parse for FieldAttributesInfo {
                 "skip" => {
                     other.skipped = true;
                 }
+                "range" => {
+                    let range_attr = parse_attribute_bounds(&attr_name, input)?;
+                    // #[deserr( .. range(min = expr, max = expr) )]
+                    other.range = Some(range_attr);
+                }
+                "length" => {
+                    let length_attr = parse_attribute_bounds(&attr_name, input)?;
+                    // #[deserr( .. length(min = expr, max = expr) )]
+                    other.length = Some(length_attr);
+                }
+                "non_empty" => {
+                    other.non_empty = true;
+                    other.non_empty_span = Some(attr_name.span());
+                }
+                "pattern" => {
+                    let _eq = input.parse::<Token![=]>()?;
+                    let pattern = input.parse::<LitStr>()?;
+                    // #[deserr( ... pattern = "regex" )]
+                    if let Err(e) = regex_syntax::Parser::new().parse(&pattern.value()) {
+                        let message = format!("Invalid regex in the `pattern` attribute: {e}");
+                        return Err(syn::Error::new_spanned(pattern, message));
+                    }
+                    other.pattern = Some(pattern);
+                }
+                "extra" => {
+                    other.extra = true;
+                    other.extra_span = Some(attr_name.span());
+                }
                 _ => {
                     let message = format!("Unknown deserr field attribute: {}", attr_name);
                     return Result::Err(syn::Error::new_spanned(attr_name, message));
@@ -243,33 +342,53 @@ pub fn read_deserr_field_attributes(
             continue;
         }
     }
+    if let Some(extra_span) = this.extra_span {
+        // The extra field is never deserialized from a key of its own, so most of the
+        // attributes wouldn't make sense on it
+        let incompatible = [
+            ("rename", this.rename.is_some()),
+            ("default", this.default.is_some()),
+            ("missing_field_error", this.missing_field_error.is_some()),
+            ("from", this.from.is_some()),
+            ("try_from", this.try_from.is_some()),
+            ("skip", this.skipped),
+            ("range", this.range.is_some()),
+            ("length", this.length.is_some()),
+            ("non_empty", this.non_empty),
+            ("pattern", this.pattern.is_some()),
+        ];
+        if let Some((attr, _)) = incompatible.iter().find(|(_, used)| *used) {
+            return Err(syn::Error::new(
+                extra_span,
+                format!("The `extra` and `{attr}` attributes can't be used together."),
+            ));
+        }
+    }
     Ok(this)
 }
 
 /// The value of the `default` field attribute
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum RenameAll {
     /// `#[deserr(rename_all = camelCase)]`
     CamelCase,
     /// `#[deserr(rename_all = lowercase)]`
     LowerCase,
+    /// `#[deserr(rename_all = kebab-case)]`
+    KebabCase,
 }
 
 /// The value of the `tag` field attribute
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum TagType {
     /// `#[deserr(tag = "somestring")]`
     Internal(String),
     /// An external tag is the default value, when there is no `tag` attribute.
+    #[default]
     External,
 }
 
-impl Default for TagType {
-    fn default() -> Self {
-        Self::External
-    }
-}
-
 /// The value of the `deny_unknown_fields` field attribute
 #[derive(Debug, Clone)]
 pub enum DenyUnknownFields {
@@ -306,6 +425,36 @@ pub struct FunctionReturningError {
     pub error_ty: syn::Type,
 }
 
+/// A constraint between the fields of a struct, checked after all the fields have been collected.
+#[derive(Debug, Clone)]
+pub enum FieldsConstraint {
+    /// `#[deserr(requires(field, other1, other2))]`
+    ///
+    /// If `field` is specified, then all the other fields must be specified too.
+    Requires { field: Ident, others: Vec<Ident> },
+    /// `#[deserr(conflicts_with(field, other1, other2))]`
+    ///
+    /// If `field` is specified, then none of the other fields can be specified.
+    ConflictsWith { field: Ident, others: Vec<Ident> },
+    /// `#[deserr(exactly_one_of(field1, field2, field3))]`
+    ///
+    /// Exactly one of the fields must be specified.
+    ExactlyOneOf { fields: Vec<Ident> },
+}
+
+impl FieldsConstraint {
+    /// Return all the fields involved in the constraint.
+    pub fn fields(&self) -> Vec<&Ident> {
+        match self {
+            FieldsConstraint::Requires { field, others }
+            | FieldsConstraint::ConflictsWith { field, others } => {
+                std::iter::once(field).chain(others).collect()
+            }
+            FieldsConstraint::ExactlyOneOf { fields } => fields.iter().collect(),
+        }
+    }
+}
+
 /// Attributes that are applied to containers
 ///
 /// The `tag` attribute can only be applied to enums.
@@ -327,6 +476,10 @@ pub struct ContainerAttributesInfo {
     /// A function to call on the deserialized value to validate it
     pub validate: Option<FunctionReturningError>,
 
+    /// The constraints between the fields, given by the `requires`, `conflicts_with`
+    /// and `exactly_one_of` attributes
+    pub constraints: Vec<FieldsConstraint>,
+
     validate_span: Option<Span>,
     rename_all_span: Option<Span>,
     tag_span: Option<Span>,
@@ -424,6 +577,7 @@ impl ContainerAttributesInfo {
 
         self.generic_params.extend(other.generic_params);
         self.where_predicates.extend(other.where_predicates);
+        self.constraints.extend(other.constraints);
 
         Ok(())
     }
@@ -443,10 +597,22 @@ fn parse_rename_all(input: &ParseBuffer) -> Result<RenameAll, syn::Error> {
     let rename_all = match ident.to_string().as_str() {
         "camelCase" => RenameAll::CamelCase,
         "lowercase" => RenameAll::LowerCase,
+        // `kebab-case` is parsed as the `kebab` ident, a `-` and the `case` ident
+        "kebab" if input.peek(Token![-]) => {
+            let _dash = input.parse::<Token![-]>()?;
+            let case = input.parse::<Ident>()?;
+            if case != "case" {
+                return Result::Err(syn::Error::new_spanned(
+                    case,
+                    "rename_all can either be equal to `camelCase`, `lowercase` or `kebab-case`",
+                ));
+            }
+            RenameAll::KebabCase
+        }
         _ => {
             return Result::Err(syn::Error::new_spanned(
                 ident,
-                "rename_all can either be equal to `camelCase` or `lowercase`",
+                "rename_all can either be equal to `camelCase`, `lowercase` or `kebab-case`",
             ));
         }
     };
@@ -483,6 +649,75 @@ fn parse_attribute_from(span: Span, input: &ParseBuffer) -> Result<AttributeFrom
     })
 }
 
+fn parse_attribute_bounds(
+    attr_name: &Ident,
+    input: &ParseBuffer,
+) -> Result<AttributeBounds, syn::Error> {
+    let content;
+    let _ = parenthesized!(content in input);
+    // #[deserr( .. range(..) ..)]
+    let mut bounds = AttributeBounds {
+        min: None,
+        max: None,
+        span: attr_name.span(),
+    };
+
+    loop {
+        let bound_name = content.parse::<Ident>()?;
+        let _eq = content.parse::<Token![=]>()?;
+        let expr = content.parse::<Expr>()?;
+        // #[deserr( .. range(bound_name = expr) ..)]
+        let bound = match bound_name.to_string().as_str() {
+            "min" => &mut bounds.min,
+            "max" => &mut bounds.max,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    bound_name,
+                    format!("The `{attr_name}` attribute only accepts the `min` and `max` bounds"),
+                ))
+            }
+        };
+        if bound.is_some() {
+            return Err(syn::Error::new_spanned(
+                &bound_name,
+                format!("The `{bound_name}` bound is defined twice."),
+            ));
+        }
+        *bound = Some(expr);
+
+        if content.peek(Token![,]) {
+            let _comma = content.parse::<Token![,]>()?;
+            if content.is_empty() {
+                break;
+            }
+        } else if content.is_empty() {
+            break;
+        } else {
+            return Err(syn::Error::new(content.span(), "Expected end of attribute"));
+        }
+    }
+
+    Ok(bounds)
+}
+
+fn parse_constraint_fields(
+    attr_name: &Ident,
+    input: &ParseBuffer,
+) -> Result<Vec<Ident>, syn::Error> {
+    let content;
+    let _ = parenthesized!(content in input);
+    // #[deserr( .. requires(..) ..)]
+    let fields = content.parse_terminated(<Ident as syn::parse::Parse>::parse, Token![,])?;
+    // #[deserr( .. requires(field1, field2) ..)]
+    if fields.len() < 2 {
+        return Err(syn::Error::new(
+            attr_name.span(),
+            format!("The `{attr_name}` attribute expects at least two fields"),
+        ));
+    }
+    Ok(fields.into_iter().collect())
+}
+
 fn parse_attribute_try_from(
     span: Span,
     input: &ParseBuffer,
@@ -563,6 +798,27 @@ impl syn::parse::Parse for ContainerAttributesInfo {
                     // #[deserr( ... validate = some::func<T> )]
                     this.validate = Some(validate_func);
                 }
+                "requires" => {
+                    let mut fields = parse_constraint_fields(&attr_name, input)?;
+                    let field = fields.remove(0);
+                    this.constraints.push(FieldsConstraint::Requires {
+                        field,
+                        others: fields,
+                    });
+                }
+                "conflicts_with" => {
+                    let mut fields = parse_constraint_fields(&attr_name, input)?;
+                    let field = fields.remove(0);
+                    this.constraints.push(FieldsConstraint::ConflictsWith {
+                        field,
+                        others: fields,
+                    });
+                }
+                "exactly_one_of" => {
+                    let fields = parse_constraint_fields(&attr_name, input)?;
+                    this.constraints
+                        .push(FieldsConstraint::ExactlyOneOf { fields });
+                }
                 "generic_param" => {
                     let _eq = input.parse::<Token![=]>()?;
                     let param = input.parse::<GenericParam>()?;
@@ -622,6 +878,20 @@ pub fn validate_container_attributes(
             ));
         }
     }
+    if let Some(constraint) = attributes.constraints.first() {
+        if !matches!(container.data, syn::Data::Struct(..)) {
+            return Err(syn::Error::new(
+                constraint.fields()[0].span(),
+                "The `requires`, `conflicts_with` and `exactly_one_of` attributes can only be used on structs",
+            ));
+        }
+        if attributes.try_from.is_some() || attributes.from.is_some() {
+            return Err(syn::Error::new(
+                constraint.fields()[0].span(),
+                "Cannot use the `requires`, `conflicts_with` and `exactly_one_of` attributes together with the `from` or `try_from` attribute",
+            ));
+        }
+    }
     if matches!(container.data, syn::Data::Struct(..)) {
         if let Some(tag) = attributes.tag_span {
             return Err(syn::Error::new(
@@ -655,7 +925,7 @@ pub fn read_deserr_container_attributes(
 
 /// Attributes that are applied to enum variants
 ///
-/// There are currently two supported variant attributes: `rename` and `rename_all`.
+/// There are currently three supported variant attributes: `rename`, `rename_all` and `other`.
 /// For example:
 /// ```ignore
 /// enum X {
@@ -669,7 +939,10 @@ pub fn read_deserr_container_attributes(
 pub struct VariantAttributesInfo {
     pub rename_all: Option<RenameAll>,
     pub rename: Option<LitStr>,
+    /// Whether the variant catches all the unknown values
+    pub other: bool,
     rename_all_span: Option<Span>,
+    other_span: Option<Span>,
 }
 impl VariantAttributesInfo {
     /// Merges the other data attributes into `self`.
@@ -704,6 +977,16 @@ impl VariantAttributesInfo {
             }
             self.rename = Some(rename)
         }
+        if other.other {
+            if let Some(self_other_span) = self.other_span {
+                return Err(syn::Error::new(
+                    self_other_span,
+                    "The `other` attribute is defined twice.",
+                ));
+            }
+            self.other = true;
+            self.other_span = other.other_span;
+        }
 
         Ok(())
     }
@@ -725,6 +1008,10 @@ impl syn::parse::Parse for VariantAttributesInfo {
                     this.rename_all = Some(parse_rename_all(input)?);
                     this.rename_all_span = Some(attr_name.span());
                 }
+                "other" => {
+                    this.other = true;
+                    this.other_span = Some(attr_name.span());
+                }
                 _ => {
                     let message = format!("Unknown deserr variant attribute: {}", attr_name);
                     return Result::Err(syn::Error::new_spanned(attr_name, message));
@@ -764,5 +1051,11 @@ pub fn read_deserr_variant_attributes(
             continue;
         }
     }
+    if let (Some(other_span), Some(_)) = (this.other_span, &this.rename) {
+        return Err(syn::Error::new(
+            other_span,
+            "The `other` and `rename` attributes can't be used together.",
+        ));
+    }
     Ok(this)
 }
@@ -2,7 +2,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 use crate::parse_type::{
-    CommonDerivedTypeInfo,
+    CommonDerivedTypeInfo, OtherVariantInfo,
     VariantData::{Named, Unit},
     VariantInfo,
 };
@@ -12,7 +12,12 @@ pub fn generate_derive_tagged_enum_impl(
     info: CommonDerivedTypeInfo,
     tag: String,
     variants: Vec<VariantInfo>,
+    other: Option<OtherVariantInfo>,
 ) -> TokenStream {
+    // all the tag values of the enum as a slice of `&str`
+    let all_tags_as_str = variants.iter().map(|v| &v.key_name);
+    let all_tags_as_str = quote!(&[#(#all_tags_as_str),*]);
+
     // `variant_impls` is the token stream of the code responsible for deserialising
     // all the fields of the enum variants and returning the fully deserialised enum.
     let variants_impls = variants
@@ -26,6 +31,30 @@ pub fn generate_derive_tagged_enum_impl(
         validate,
     } = info;
 
+    // this is the case where the tag exists and is a string, but its value does not
+    // correspond to any valid enum variant name
+    let unknown_tag = match other {
+        // #[deserr(other)] => the rest of the map is ignored
+        Some(other) => {
+            let variant = other.construct(quote! { &tag_value_string });
+            quote! {
+                ::std::result::Result::Ok(#variant)
+            }
+        }
+        None => quote! {
+            ::std::result::Result::Err(
+                ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                    None,
+                    ::deserr::ErrorKind::UnknownValue {
+                        value: &tag_value_string,
+                        accepted: #all_tags_as_str,
+                    },
+                    deserr_location__.push_key(#tag)
+                ))
+            )
+        },
+    };
+
     quote! {
          #impl_trait_tokens {
             fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::std::result::Result<Self, #err_ty> {
@@ -59,19 +88,8 @@ pub fn generate_derive_tagged_enum_impl(
 
                         match tag_value_string.as_str() {
                             #(#variants_impls)*
-                            // this is the case where the tag exists and is a string, but its value does not
-                            // correspond to any valid enum variant name
                             _ => {
-                                ::std::result::Result::Err(
-                                    ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
-                                        None,
-                                        // TODO: expected one of {expected_tags_list}, found {actual_tag} error message
-                                        ::deserr::ErrorKind::Unexpected {
-                                            msg: "Incorrect tag value".to_string(),
-                                        },
-                                        deserr_location__
-                                    ))
-                                )
+                                #unknown_tag
                             }
                         }
                     },
@@ -163,6 +181,7 @@ fn generate_derive_tagged_enum_variant_impl(
 pub fn generate_derive_untagged_enum_impl(
     info: CommonDerivedTypeInfo,
     variants: Vec<VariantInfo>,
+    other: Option<OtherVariantInfo>,
 ) -> TokenStream {
     // all the variant of the enum as a slice of `&str`
     let all_variants_as_str = variants
@@ -187,6 +206,30 @@ pub fn generate_derive_untagged_enum_impl(
         validate,
     } = info;
 
+    // this is the case where the value is a string, but it does not
+    // correspond to any valid enum variant name
+    let unknown_value = match other {
+        // #[deserr(other)] => the value is caught by the `other` variant
+        Some(other) => {
+            let variant = other.construct(quote! { s });
+            quote! {
+                ::std::result::Result::Ok(#variant)
+            }
+        }
+        None => quote! {
+            ::std::result::Result::Err(
+                ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                    None,
+                    ::deserr::ErrorKind::UnknownValue {
+                        value: s,
+                        accepted: #all_variants_as_str,
+                    },
+                    deserr_location__
+                ))
+            )
+        },
+    };
+
     quote! {
          #impl_trait_tokens {
             fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::std::result::Result<Self, #err_ty> {
@@ -195,19 +238,8 @@ pub fn generate_derive_untagged_enum_impl(
                     ::deserr::Value::String(s) => {
                         match s.as_str() {
                             #(#variants_impls)*
-                            // this is the case where the tag exists and is a string, but its value does not
-                            // correspond to any valid enum variant name
                             s => {
-                                ::std::result::Result::Err(
-                                    ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
-                                        None,
-                                        ::deserr::ErrorKind::UnknownValue {
-                                            value: s,
-                                            accepted: #all_variants_as_str,
-                                        },
-                                        deserr_location__
-                                    ))
-                                )
+                                #unknown_value
                             }
                         }
                     },
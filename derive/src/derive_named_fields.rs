@@ -14,11 +14,15 @@ pub fn generate_named_fields_impl(
         field_errs,
         field_from_fns,
         field_from_errors: _,
+        field_validations,
         field_maps,
         missing_field_errors,
         key_names,
         unknown_key,
         needs_predicate: _,
+        present_keys,
+        key_presences,
+        constraints,
     } = fields;
     quote! {
         // Start by declaring all the fields as mutable optionals
@@ -30,6 +34,7 @@ pub fn generate_named_fields_impl(
         #(
             let mut #field_names : ::deserr::FieldState<_> = #field_defaults ;
         )*
+        #present_keys
         // We traverse the entire map instead of looking for specific keys, because we want
         // to handle the case where a key is unknown and the attribute `deny_unknown_fields` was used.
         for (deserr_key__, deserr_value__) in ::deserr::Map::into_iter(deserr_map__) {
@@ -38,11 +43,12 @@ pub fn generate_named_fields_impl(
 
                 #(
                     #key_names => {
+                        #key_presences
                         #field_names = match
                             <#field_tys as ::deserr::Deserr<#field_errs>>::deserialize_from_value(
                                 ::deserr::IntoValue::into_value(deserr_value__),
                                 deserr_location__.push_key(deserr_key__.as_str())
-                            ) {
+                            ) #field_validations {
                                 ::std::result::Result::Ok(x) => {
                                     #field_from_fns
                                 },
@@ -72,6 +78,8 @@ pub fn generate_named_fields_impl(
                 #missing_field_errors
             }
         )*
+        // Then we check the constraints between the fields
+        #constraints
 
         if let Some(deserr_error__) = deserr_error__ {
             ::std::result::Result::Err(deserr_error__)
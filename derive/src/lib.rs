@@ -23,11 +23,16 @@ pub fn derive_deserialize(input: TokenStream) -> TokenStream {
             TraitImplementationInfo::Struct(fields) => {
                 derive_struct::generate_derive_struct_impl(derived_type_info.common, fields).into()
             }
-            TraitImplementationInfo::Enum { tag, variants } => match tag {
+            TraitImplementationInfo::Enum {
+                tag,
+                variants,
+                other,
+            } => match tag {
                 TagType::Internal(tag_key) => derive_enum::generate_derive_tagged_enum_impl(
                     derived_type_info.common,
                     tag_key,
                     variants,
+                    other,
                 )
                 .into(),
                 TagType::External
@@ -38,6 +43,7 @@ pub fn derive_deserialize(input: TokenStream) -> TokenStream {
                     derive_enum::generate_derive_untagged_enum_impl(
                         derived_type_info.common,
                         variants,
+                        other,
                     )
                     .into()
                 }
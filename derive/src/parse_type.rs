@@ -1,13 +1,14 @@
 use crate::attribute_parser::{
     read_deserr_container_attributes, read_deserr_field_attributes, read_deserr_variant_attributes,
-    validate_container_attributes, AttributeFrom, AttributeTryFrom, ContainerAttributesInfo,
-    DefaultFieldAttribute, DenyUnknownFields, FunctionReturningError, RenameAll, TagType,
+    validate_container_attributes, AttributeBounds, AttributeFrom, AttributeTryFrom,
+    ContainerAttributesInfo, DefaultFieldAttribute, DenyUnknownFields, FieldAttributesInfo,
+    FieldsConstraint, FunctionReturningError, RenameAll, TagType,
 };
 
 use convert_case::{Case, Casing};
 use proc_macro2::TokenStream;
 use proc_macro2::{Ident, Span};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::spanned::Spanned;
 use syn::{parse_quote, Data, DeriveInput, WherePredicate};
 
@@ -45,6 +46,7 @@ pub enum TraitImplementationInfo {
     Enum {
         tag: TagType,
         variants: Vec<VariantInfo>,
+        other: Option<OtherVariantInfo>,
     },
     FallibleUserProvidedFunction {
         try_from_attr: AttributeTryFrom,
@@ -69,6 +71,27 @@ pub struct VariantInfo {
     pub key_name: String,
 }
 
+/// Contains the information about the enum variant with the `other` attribute,
+/// which is used when no other variant matches.
+pub struct OtherVariantInfo {
+    /// The identifier of the enum variant
+    pub ident: Ident,
+    /// Whether the variant holds the unknown value, such as `Other(String)`
+    pub holds_value: bool,
+}
+
+impl OtherVariantInfo {
+    /// Create the token stream building the variant from the unknown value `value`, of type `&str`.
+    pub fn construct(&self, value: TokenStream) -> TokenStream {
+        let ident = &self.ident;
+        if self.holds_value {
+            quote! { Self::#ident(::std::string::ToString::to_string(#value)) }
+        } else {
+            quote! { Self::#ident }
+        }
+    }
+}
+
 /// Contains the information needed to generate the deserialization code
 /// for the content of an enum variant.
 #[derive(Debug)]
@@ -132,9 +155,33 @@ impl DerivedTypeInfo {
                 Data::Enum(e) => {
                     // parse a VariantInfo for each variant in the enum
                     let mut parsed_variants = vec![];
+                    let mut other = None;
                     for variant in e.variants {
                         let variant_attrs = read_deserr_variant_attributes(&variant.attrs)?;
 
+                        if variant_attrs.other {
+                            if other.is_some() {
+                                return Err(syn::Error::new(
+                                    variant.ident.span(),
+                                    "The `other` attribute can only be used on one variant.",
+                                ));
+                            }
+                            // The catch-all variant is never matched by its name
+                            let holds_value = match &variant.fields {
+                                syn::Fields::Unit => false,
+                                syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => true,
+                                fields => return Err(syn::Error::new(
+                                    fields.span(),
+                                    "The `other` attribute can only be used on unit variants or variants holding a single `String`.",
+                                )),
+                            };
+                            other = Some(OtherVariantInfo {
+                                ident: variant.ident,
+                                holds_value,
+                            });
+                            continue;
+                        }
+
                         let renamed = variant_attrs.rename.as_ref().map(|i| i.value());
 
                         // The key in the serialized value representing the variant, which is influenced by the
@@ -168,6 +215,7 @@ impl DerivedTypeInfo {
                     TraitImplementationInfo::Enum {
                         tag: attrs.tag,
                         variants: parsed_variants,
+                        other,
                     }
                 }
                 Data::Union(u) => {
@@ -366,12 +414,23 @@ pub struct NamedFieldsInfo {
 
     pub field_from_fns: Vec<TokenStream>,
     pub field_from_errors: Vec<Option<syn::Type>>,
+    pub field_validations: Vec<TokenStream>,
 
     pub field_maps: Vec<TokenStream>,
     pub missing_field_errors: Vec<TokenStream>,
     pub key_names: Vec<String>,
 
     pub needs_predicate: Vec<bool>,
+    /// A token stream declaring which keys were present in the map.
+    ///
+    /// It is empty unless the `requires`, `conflicts_with` or `exactly_one_of` attributes are used.
+    pub present_keys: TokenStream,
+    /// A token stream per key, recording that the key was present in the map.
+    pub key_presences: Vec<TokenStream>,
+    /// A token stream representing the code to check the constraints between the fields.
+    ///
+    /// It is relevant to the `requires`, `conflicts_with` and `exactly_one_of` attributes.
+    pub constraints: TokenStream,
     /// A token stream representing the code to handle an unknown field key.
     ///
     /// It is relevant to the `deny_unknown_fields` attribute.
@@ -402,6 +461,9 @@ impl NamedFieldsInfo {
         let mut field_from_fns = vec![];
         // The list of error types that can be returned by the `from` clauses
         let mut field_from_errors = vec![];
+        // the token stream which validates the deserialised field value
+        // influenced by the `range`, `length`, `non_empty` and `pattern` attributes
+        let mut field_validations = vec![];
         // the token stream which maps the deserialised field value
         let mut field_maps = vec![];
         // `true` iff the field has the needs_predicate attribute
@@ -419,7 +481,25 @@ impl NamedFieldsInfo {
         // We put all the non-skipped fields at the beginning, so that when we iterate
         // over the non-skipped key names, we can access their corresponding field names
         // using the same index.
-        fields_extra.sort_by_key(|x| x.1.skipped);
+        // The field capturing the unknown keys is treated like a skipped field.
+        fields_extra.sort_by_key(|x| x.1.skipped || x.1.extra);
+
+        // The field with the `extra` attribute, which captures the unknown keys, if any
+        let mut extra_fields = fields_extra.iter().filter(|x| x.1.extra);
+        let extra_field = extra_fields.next().map(|(field, attrs)| {
+            let error = attrs
+                .error
+                .clone()
+                .or_else(|| data_attrs.err_ty.clone())
+                .unwrap_or_else(|| parse_quote!(__Deserr_E));
+            (field.ident.clone().unwrap(), field.ty.clone(), error)
+        });
+        if let Some((field, _)) = extra_fields.next() {
+            return Err(syn::Error::new(
+                field.ident.span(),
+                "The `extra` attribute can only be used on one field.",
+            ));
+        }
 
         for (field, attrs) in fields_extra.iter() {
             let field_name = field.ident.clone().unwrap();
@@ -436,7 +516,7 @@ impl NamedFieldsInfo {
                         quote! { ::deserr::FieldState::Some(#expr) }
                     }
                 }
-            } else if attrs.skipped {
+            } else if attrs.skipped || attrs.extra {
                 quote! { ::deserr::FieldState::Some(::std::default::Default::default()) }
             } else {
                 quote! { ::deserr::FieldState::Missing }
@@ -468,9 +548,16 @@ impl NamedFieldsInfo {
             needs_predicate.push(attrs.needs_predicate);
         }
 
-        for (field, attrs) in fields_extra.into_iter().filter(|x| !x.1.skipped) {
+        // the identifiers of the non-skipped fields, in the same order as `key_names`
+        let mut constraint_fields = vec![];
+
+        for (field, attrs) in fields_extra
+            .into_iter()
+            .filter(|x| !x.1.skipped && !x.1.extra)
+        {
             let field_ty = &field.ty;
             let field_name = field.ident.clone().unwrap();
+            constraint_fields.push(field_name.clone());
 
             let renamed = attrs.rename.as_ref().map(|i| i.value());
             let key_name = key_name_for_ident(
@@ -478,8 +565,8 @@ impl NamedFieldsInfo {
                 data_attrs.rename_all.as_ref(),
                 renamed.as_deref(),
             );
-            let error = match attrs.error {
-                Some(error) => error,
+            let error = match &attrs.error {
+                Some(error) => error.clone(),
                 None => data_attrs
                     .err_ty
                     .clone()
@@ -553,6 +640,8 @@ impl NamedFieldsInfo {
                 },
             };
 
+            let field_validation = field_validation_tokens(&attrs, &error);
+
             let field_from_error = attrs
                 .try_from
                 .as_ref()
@@ -596,6 +685,7 @@ impl NamedFieldsInfo {
             field_errs.push(error);
             field_from_fns.push(field_from_fn);
             field_from_errors.push(field_from_error);
+            field_validations.push(field_validation);
             missing_field_errors.push(missing_field_error);
         }
 
@@ -605,41 +695,77 @@ impl NamedFieldsInfo {
         // If the #[deserr(deny_unknown_fields)] or #[deserr(deny_unknown_fields = func)] attribute exists,
         // we return an error: either the default error, or an error created by the custom function given by
         // the user.
-        let unknown_key = match &data_attrs.deny_unknown_fields {
-            Some(DenyUnknownFields::DefaultError) => {
-                // Here we must give as argument the accepted keys
-                quote! {
-                    deserr_error__ = match <#err_ty as ::deserr::DeserializeError>::error::<V>(
+        //
+        // If a field has the #[deserr(extra)] attribute, the unknown keys are inserted in this field instead.
+        let unknown_key = match (&data_attrs.deny_unknown_fields, extra_field) {
+            (Some(_), Some((extra_field, _, _))) => {
+                return Err(syn::Error::new(
+                    extra_field.span(),
+                    "The `extra` and `deny_unknown_fields` attributes can't be used together.",
+                ))
+            }
+            // #[deserr(extra)] => deserialize the value and insert it in the extra field
+            (None, Some((extra_field, extra_ty, error))) => quote! {
+                match <<#extra_ty as ::deserr::ExtraFields>::Value as ::deserr::Deserr<#error>>::deserialize_from_value(
+                    ::deserr::IntoValue::into_value(deserr_value__),
+                    deserr_location__.push_key(deserr_key__)
+                ) {
+                    ::std::result::Result::Ok(x) => {
+                        if let ::deserr::FieldState::Some(deserr_extra__) = &mut #extra_field {
+                            <#extra_ty as ::deserr::ExtraFields>::insert_extra(deserr_extra__, deserr_key__.to_string(), x);
+                        }
+                    }
+                    ::std::result::Result::Err(e) => {
+                        deserr_error__ = match <#err_ty as ::deserr::MergeWithError<_>>::merge(
+                            deserr_error__,
+                            e,
+                            deserr_location__.push_key(deserr_key__)
+                        ) {
+                            ::std::ops::ControlFlow::Continue(e) => ::std::option::Option::Some(e),
+                            ::std::ops::ControlFlow::Break(e) => return ::std::result::Result::Err(e),
+                        };
+                    }
+                }
+            },
+            (deny_unknown_fields, None) => match deny_unknown_fields {
+                Some(DenyUnknownFields::DefaultError) => {
+                    // Here we must give as argument the accepted keys
+                    quote! {
+                        deserr_error__ = match <#err_ty as ::deserr::DeserializeError>::error::<V>(
+                            deserr_error__,
+                            ::deserr::ErrorKind::UnknownKey {
+                                key: deserr_key__,
+                                accepted: &[#(#key_names),*],
+                            },
+                            deserr_location__
+                        ) {
+                            ::std::ops::ControlFlow::Continue(e) => ::std::option::Option::Some(e),
+                            ::std::ops::ControlFlow::Break(e) => {
+                                return ::std::result::Result::Err(e)
+                            }
+                        };
+                    }
+                }
+                Some(DenyUnknownFields::Function(func)) => quote! {
+                    let deserr_e__ = #func (deserr_key__, &[#(#key_names),*], deserr_location__) ;
+                    deserr_error__ = match <#err_ty as ::deserr::MergeWithError<_>>::merge(
                         deserr_error__,
-                        ::deserr::ErrorKind::UnknownKey {
-                            key: deserr_key__,
-                            accepted: &[#(#key_names),*],
-                        },
-                        deserr_location__
+                        deserr_e__,
+                        deserr_location__,
                     ) {
                         ::std::ops::ControlFlow::Continue(e) => ::std::option::Option::Some(e),
                         ::std::ops::ControlFlow::Break(e) => {
                             return ::std::result::Result::Err(e)
                         }
                     };
-                }
-            }
-            Some(DenyUnknownFields::Function(func)) => quote! {
-                let deserr_e__ = #func (deserr_key__, &[#(#key_names),*], deserr_location__) ;
-                deserr_error__ = match <#err_ty as ::deserr::MergeWithError<_>>::merge(
-                    deserr_error__,
-                    deserr_e__,
-                    deserr_location__,
-                ) {
-                    ::std::ops::ControlFlow::Continue(e) => ::std::option::Option::Some(e),
-                    ::std::ops::ControlFlow::Break(e) => {
-                        return ::std::result::Result::Err(e)
-                    }
-                };
+                },
+                None => quote! {},
             },
-            None => quote! {},
         };
 
+        let (present_keys, key_presences, constraints) =
+            constraints_tokens(&constraint_fields, &key_names, &data_attrs.constraints)?;
+
         Ok(Self {
             field_names,
             field_tys,
@@ -648,14 +774,144 @@ impl NamedFieldsInfo {
             field_errs,
             field_from_fns,
             field_from_errors,
+            field_validations,
             field_maps,
             needs_predicate,
+            present_keys,
+            key_presences,
+            constraints,
             missing_field_errors,
             unknown_key,
         })
     }
 }
 
+/// Create the token streams that check the `requires`, `conflicts_with` and `exactly_one_of`
+/// constraints.
+///
+/// Returns the declaration of the `deserr_present__` array, the code recording the presence of
+/// each key, and the code checking each constraint once all the fields have been collected.
+fn constraints_tokens(
+    fields: &[syn::Ident],
+    key_names: &[String],
+    constraints: &[FieldsConstraint],
+) -> syn::Result<(TokenStream, Vec<TokenStream>, TokenStream)> {
+    if constraints.is_empty() {
+        return Ok((quote! {}, vec![quote! {}; key_names.len()], quote! {}));
+    }
+
+    // Return the token stream `(key_name, deserr_present__[index])` for the given field
+    let presence = |ident: &syn::Ident| -> syn::Result<TokenStream> {
+        match fields.iter().position(|field| field == ident) {
+            Some(index) => {
+                let key_name = &key_names[index];
+                Ok(quote! { (#key_name, deserr_present__[#index]) })
+            }
+            None => Err(syn::Error::new(
+                ident.span(),
+                format!("Unknown field `{ident}`. Note that skipped and extra fields can't be used in constraints"),
+            )),
+        }
+    };
+
+    let mut checks = vec![];
+    for constraint in constraints {
+        let call = match constraint {
+            FieldsConstraint::Requires { field, others } => {
+                let field = presence(field)?;
+                let others = others
+                    .iter()
+                    .map(presence)
+                    .collect::<syn::Result<Vec<_>>>()?;
+                quote! { ::deserr::validators::check_requires(deserr_error__, #field, &[#(#others),*], deserr_location__) }
+            }
+            FieldsConstraint::ConflictsWith { field, others } => {
+                let field = presence(field)?;
+                let others = others
+                    .iter()
+                    .map(presence)
+                    .collect::<syn::Result<Vec<_>>>()?;
+                quote! { ::deserr::validators::check_conflicts_with(deserr_error__, #field, &[#(#others),*], deserr_location__) }
+            }
+            FieldsConstraint::ExactlyOneOf { fields } => {
+                let fields = fields
+                    .iter()
+                    .map(presence)
+                    .collect::<syn::Result<Vec<_>>>()?;
+                quote! { ::deserr::validators::check_exactly_one_of(deserr_error__, &[#(#fields),*], deserr_location__) }
+            }
+        };
+        checks.push(quote! {
+            deserr_error__ = match #call {
+                ::std::ops::ControlFlow::Continue(e) => e,
+                ::std::ops::ControlFlow::Break(e) => return ::std::result::Result::Err(e),
+            };
+        });
+    }
+
+    let len = key_names.len();
+    let present_keys = quote! { let mut deserr_present__: [bool; #len] = [false; #len]; };
+    let key_presences = (0..len)
+        .map(|index| quote! { deserr_present__[#index] = true; })
+        .collect();
+
+    Ok((present_keys, key_presences, quote! { #(#checks)* }))
+}
+
+/// Create the token stream that validates a successfully deserialised field value according to
+/// the `range`, `length`, `non_empty` and `pattern` attributes.
+///
+/// It is appended to the result of the field deserialisation, so it is either empty or of the form
+/// `.and_then(|x| { checks ; Ok(x) })`.
+fn field_validation_tokens(attrs: &FieldAttributesInfo, error: &syn::Type) -> TokenStream {
+    let location = quote! { deserr_location__.push_key(deserr_key__.as_str()) };
+    let mut checks = vec![];
+
+    if let Some(AttributeBounds { min, max, .. }) = &attrs.range {
+        let min = optional_expr(min);
+        let max = optional_expr(max);
+        checks.push(quote! {
+            ::deserr::validators::check_range::<_, #error>(&x, #min, #max, #location)?;
+        });
+    }
+    if let Some(AttributeBounds { min, max, .. }) = &attrs.length {
+        let min = optional_expr(min);
+        let max = optional_expr(max);
+        checks.push(quote! {
+            ::deserr::validators::check_length::<_, #error>(&x, #min, #max, #location)?;
+        });
+    }
+    if attrs.non_empty {
+        checks.push(quote! {
+            ::deserr::validators::check_non_empty::<_, #error>(&x, #location)?;
+        });
+    }
+    if let Some(pattern) = &attrs.pattern {
+        // the macro is a compile error when the `regex` feature of deserr is disabled
+        checks.push(quote_spanned! { pattern.span() =>
+            ::deserr::__check_pattern!(&x, #pattern, #error, #location)?;
+        });
+    }
+
+    if checks.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            .and_then(|x| {
+                #(#checks)*
+                ::std::result::Result::Ok(x)
+            })
+        }
+    }
+}
+
+fn optional_expr(expr: &Option<syn::Expr>) -> TokenStream {
+    match expr {
+        Some(expr) => quote! { ::std::option::Option::Some(#expr) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
 /// Transforms the given `ident` string according to the rules of the `rename` and `rename_all` attributes
 fn key_name_for_ident(
     ident: String,
@@ -667,6 +923,7 @@ fn key_name_for_ident(
         None => match rename_all {
             Some(RenameAll::CamelCase) => ident.to_case(Case::Camel),
             Some(RenameAll::LowerCase) => ident.to_lowercase(),
+            Some(RenameAll::KebabCase) => ident.to_case(Case::Kebab),
             None => ident,
         },
     }
@@ -1,10 +1,12 @@
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use axum::routing::post;
+use axum::routing::{get, post};
+use axum::Extension;
 use axum::Json;
 use axum::Router;
-use deserr::axum::AxumJson;
-use deserr::errors::JsonError;
+use deserr::axum::{AxumJson, AxumQuery};
+use deserr::errors::{JsonError, QueryParamError};
+use deserr::query_string::QueryConfig;
 use deserr::take_cf_content;
 use deserr::DeserializeError;
 use deserr::Deserr;
@@ -103,6 +105,11 @@ async fn deserr(item: AxumJson<Query, JsonError>) -> Json<Query> {
     Json(item.0)
 }
 
+/// This handler uses the `AxumQuery` deserr extractor, the range is sent as `range[min]=2&range[max]=4`
+async fn deserr_query(item: AxumQuery<Query, QueryParamError>) -> Json<Query> {
+    Json(item.0)
+}
+
 #[tokio::main]
 async fn main() {
     tracing_subscriber::registry()
@@ -115,7 +122,9 @@ async fn main() {
 
     let app = Router::new()
         .route("/serde", post(serde))
-        .route("/deserr", post(deserr));
+        .route("/deserr", post(deserr))
+        .route("/deserr-query", get(deserr_query))
+        .layer(Extension(QueryConfig::new().brackets(true)));
 
     let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 8001))
         .await
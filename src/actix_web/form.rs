@@ -0,0 +1,141 @@
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use actix_web::dev::{Payload, UrlEncoded};
+use actix_web::{FromRequest, HttpRequest, ResponseError};
+use deserr::{DeserializeError, Deserr};
+use futures::ready;
+
+use crate::errors::FormError;
+use crate::query_string::{QueryConfig, QueryValue};
+
+/// Extractor for typed data from `application/x-www-form-urlencoded` request payloads
+/// deserialised by deserr.
+///
+/// # Extractor
+/// To extract typed data from a request body, the inner type `T` must implement the
+/// [`deserr::Deserr<E>`] trait. The inner type `E` must implement the
+/// [`DeserializeError`] + `ResponseError` traits.
+///
+/// The request is rejected if its content type is not `application/x-www-form-urlencoded`
+/// or if its payload is larger than the limit, see [`AwebFormConfig`].
+#[derive(Debug)]
+pub struct AwebForm<T, E>(pub T, PhantomData<*const E>);
+
+impl<T, E> AwebForm<T, E> {
+    pub fn new(data: T) -> Self {
+        AwebForm(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Configuration of the [`AwebForm`] extractor.
+///
+/// Register it with [`App::app_data`](actix_web::App::app_data), or on a specific resource
+/// or scope, to change the limits of the forms.
+#[derive(Debug, Clone, Copy)]
+pub struct AwebFormConfig {
+    limit: usize,
+    query_config: QueryConfig,
+}
+
+impl AwebFormConfig {
+    /// Set the maximum accepted payload size. By default this limit is 16kB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set how the fields of the form are parsed, e.g. to support the bracket notation.
+    /// By default every option of the [`QueryConfig`] is disabled.
+    pub fn query_config(mut self, query_config: QueryConfig) -> Self {
+        self.query_config = query_config;
+        self
+    }
+}
+
+impl Default for AwebFormConfig {
+    fn default() -> Self {
+        AwebFormConfig {
+            limit: 16_384,
+            query_config: QueryConfig::default(),
+        }
+    }
+}
+
+impl<T, E> FromRequest for AwebForm<T, E>
+where
+    E: DeserializeError + ResponseError + 'static,
+    T: Deserr<E>,
+{
+    type Error = actix_web::Error;
+    type Future = AwebFormExtractFut<T, E>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = req
+            .app_data::<AwebFormConfig>()
+            .copied()
+            .unwrap_or_default();
+        AwebFormExtractFut {
+            fut: UrlEncoded::new(req, payload).limit(config.limit),
+            query_config: config.query_config,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+pub struct AwebFormExtractFut<T, E> {
+    fut: UrlEncoded<Vec<(String, String)>>,
+    query_config: QueryConfig,
+    _phantom: PhantomData<*const (T, E)>,
+}
+
+impl<T, E> Future for AwebFormExtractFut<T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError + ResponseError + 'static,
+{
+    type Output = Result<AwebForm<T, E>, actix_web::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let AwebFormExtractFut {
+            fut, query_config, ..
+        } = self.get_mut();
+        let fut = Pin::new(fut);
+
+        let res = ready!(fut.poll(cx));
+
+        let res = match res {
+            Err(err) => Err(err.into()),
+            Ok(pairs) => match QueryValue::from_pairs(pairs, *query_config) {
+                Ok(value) => match deserr::deserialize::<_, _, E>(value) {
+                    Ok(data) => Ok(AwebForm::new(data)),
+                    Err(e) => Err(e)?,
+                },
+                Err(e) => Err(actix_web::error::ErrorBadRequest(format!(
+                    "Failed to deserialize form body: {e}"
+                ))),
+            },
+        };
+
+        Poll::Ready(res)
+    }
+}
+
+impl actix_web::ResponseError for FormError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        actix_web::HttpResponseBuilder::new(self.status_code())
+            .content_type("text/plain")
+            .body(self.to_string())
+    }
+}
@@ -0,0 +1,88 @@
+use std::future::{ready, Ready};
+use std::marker::PhantomData;
+use std::{fmt, ops};
+
+use actix_http::Payload;
+use actix_web::{FromRequest, HttpRequest, ResponseError};
+
+use crate::errors::HeaderError;
+use crate::headers::HeadersValue;
+use crate::{DeserializeError, Deserr};
+
+/// Extractor for typed data from the headers of a request deserialised by deserr.
+///
+/// # Extractor
+/// The header names are case-insensitive, thus given in lowercase, and a header specified
+/// multiple times is a sequence of values, see the [`headers`](crate::headers) module.
+/// The inner type `T` must implement the [`deserr::Deserr<E>`] trait. The inner type `E` must
+/// implement the [`DeserializeError`] + `ResponseError` traits.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AwebHeaders<T, E>(pub T, PhantomData<*const E>);
+
+impl<T, E> AwebHeaders<T, E> {
+    /// Unwrap into inner `T` value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, E> ops::Deref for AwebHeaders<T, E> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, E> ops::DerefMut for AwebHeaders<T, E> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Display, E> fmt::Display for AwebHeaders<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T, E> FromRequest for AwebHeaders<T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError + ResponseError + 'static,
+{
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, actix_web::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        // actix-web doesn't use the same version of the `http` crate
+        let mut headers = http::HeaderMap::with_capacity(req.headers().len());
+        for (name, value) in req.headers() {
+            let name = http::HeaderName::from_bytes(name.as_str().as_bytes());
+            let value = http::HeaderValue::from_bytes(value.as_bytes());
+            if let (Ok(name), Ok(value)) = (name, value) {
+                headers.append(name, value);
+            }
+        }
+
+        ready(
+            match deserr::deserialize::<_, _, E>(HeadersValue::from(headers)) {
+                Ok(data) => Ok(AwebHeaders(data, PhantomData)),
+                Err(e) => Err(e.into()),
+            },
+        )
+    }
+}
+
+impl actix_web::ResponseError for HeaderError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        actix_web::HttpResponseBuilder::new(self.status_code())
+            .content_type("text/plain")
+            .body(self.to_string())
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt::Debug;
+use std::future::{ready, Ready};
+use std::sync::Arc;
+
+use actix_http::Payload;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::{mime, FromRequest, HttpRequest};
+
+use crate::errors::JsonBodyError;
+use crate::json_array::JsonArrayStream;
+use crate::serde_json::is_json_content_type;
+
+/// Extractor for a stream of typed data from the elements of a JSON array request payload
+/// deserialised by deserr.
+///
+/// # Extractor
+/// The payload isn't read in memory: the inner [`JsonArrayStream`] deserializes the elements
+/// of the array one at a time while it is polled, yielding a `Result<T, E>` per element, see
+/// the [`json_array`](crate::json_array) module.
+/// The inner type `T` must implement the [`deserr::Deserr<E>`] trait. The inner type `E` must
+/// implement the [`DeserializeError`](crate::DeserializeError) trait.
+///
+/// To read an array nested in objects, such as `{ "documents": [...] }`, give the keys
+/// leading to it to [`JsonArrayStream::key_path`] before polling the stream.
+///
+/// The request is rejected if its content type is not accepted, and the stream yields an
+/// error on an element longer than the limit, see [`AwebJsonArrayConfig`].
+pub struct AwebJsonArray<T, E>(pub JsonArrayStream<Payload, T, E>);
+
+impl<T, E> AwebJsonArray<T, E> {
+    pub fn new(stream: JsonArrayStream<Payload, T, E>) -> Self {
+        AwebJsonArray(stream)
+    }
+
+    pub fn into_inner(self) -> JsonArrayStream<Payload, T, E> {
+        self.0
+    }
+}
+
+/// Configuration of the [`AwebJsonArray`] extractor.
+///
+/// Register it with [`App::app_data`](actix_web::App::app_data), or on a specific resource
+/// or scope, to change the maximum length of the elements and the accepted content types.
+#[derive(Clone)]
+pub struct AwebJsonArrayConfig {
+    max_element_length: usize,
+    content_type: Option<Arc<dyn Fn(mime::Mime) -> bool + Send + Sync>>,
+    content_type_required: bool,
+}
+
+impl AwebJsonArrayConfig {
+    /// Set the maximum length of an element in bytes, see
+    /// [`JsonArrayStream::max_element_length`]. By default this limit is 2MB.
+    pub fn max_element_length(mut self, max_element_length: usize) -> Self {
+        self.max_element_length = max_element_length;
+        self
+    }
+
+    /// Set a predicate accepting more content types than `application/json` and the
+    /// `+json` suffixes.
+    pub fn content_type<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(mime::Mime) -> bool + Send + Sync + 'static,
+    {
+        self.content_type = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Set whether the content type of the requests is checked. By default it is.
+    pub fn content_type_required(mut self, content_type_required: bool) -> Self {
+        self.content_type_required = content_type_required;
+        self
+    }
+
+    fn accepts(&self, content_type: &str) -> bool {
+        is_json_content_type(content_type)
+            || self.content_type.as_ref().is_some_and(|predicate| {
+                content_type
+                    .parse::<mime::Mime>()
+                    .is_ok_and(|mime| predicate(mime))
+            })
+    }
+}
+
+impl Default for AwebJsonArrayConfig {
+    fn default() -> Self {
+        AwebJsonArrayConfig {
+            max_element_length: 2_097_152,
+            content_type: None,
+            content_type_required: true,
+        }
+    }
+}
+
+impl Debug for AwebJsonArrayConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwebJsonArrayConfig")
+            .field("max_element_length", &self.max_element_length)
+            .field("content_type", &self.content_type.as_ref().map(|_| ".."))
+            .field("content_type_required", &self.content_type_required)
+            .finish()
+    }
+}
+
+impl<T, E> FromRequest for AwebJsonArray<T, E> {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, actix_web::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = req
+            .app_data::<AwebJsonArrayConfig>()
+            .cloned()
+            .unwrap_or_default();
+        if config.content_type_required {
+            let content_type = req
+                .headers()
+                .get(CONTENT_TYPE)
+                .map(|content_type| String::from_utf8_lossy(content_type.as_bytes()));
+            let error = match content_type {
+                Some(content_type) if config.accepts(&content_type) => None,
+                Some(content_type) => {
+                    Some(JsonBodyError::InvalidContentType(content_type.into_owned()))
+                }
+                None => Some(JsonBodyError::MissingContentType),
+            };
+            if let Some(error) = error {
+                let message = error.to_string();
+                return ready(Err(actix_web::error::ErrorUnsupportedMediaType(message)));
+            }
+        }
+        let stream =
+            JsonArrayStream::new(payload.take()).max_element_length(config.max_element_length);
+        ready(Ok(AwebJsonArray(stream)))
+    }
+}
@@ -1,9 +1,33 @@
 #[cfg(feature = "serde-json")]
+mod form;
+#[cfg(feature = "serde-json")]
+mod headers;
+#[cfg(feature = "serde-json")]
+mod json_array;
+#[cfg(all(feature = "serde-json", feature = "multipart"))]
+mod multipart;
+#[cfg(feature = "serde-json")]
+mod ndjson;
+#[cfg(feature = "serde-json")]
+mod path;
+#[cfg(feature = "serde-json")]
 mod query_parameters;
 #[cfg(feature = "serde-json")]
 mod serde_json;
 
+#[cfg(feature = "serde-json")]
+pub use self::form::{AwebForm, AwebFormConfig, AwebFormExtractFut};
+#[cfg(feature = "serde-json")]
+pub use self::headers::AwebHeaders;
+#[cfg(feature = "serde-json")]
+pub use self::json_array::{AwebJsonArray, AwebJsonArrayConfig};
+#[cfg(all(feature = "serde-json", feature = "multipart"))]
+pub use self::multipart::{AwebMultipart, AwebMultipartConfig};
+#[cfg(feature = "serde-json")]
+pub use self::ndjson::{AwebNdJson, AwebNdJsonConfig};
+#[cfg(feature = "serde-json")]
+pub use self::path::AwebPath;
 #[cfg(feature = "serde-json")]
 pub use self::query_parameters::AwebQueryParameter;
 #[cfg(feature = "serde-json")]
-pub use self::serde_json::{AwebJson, AwebJsonExtractFut};
+pub use self::serde_json::{AwebJson, AwebJsonConfig, AwebJsonExtractFut};
@@ -0,0 +1,124 @@
+use std::convert::Infallible;
+use std::marker::PhantomData;
+
+use actix_http::error::PayloadError;
+use actix_web::dev::Payload;
+use actix_web::{FromRequest, HttpRequest, ResponseError};
+use futures::future::LocalBoxFuture;
+use futures::StreamExt;
+
+use crate::errors::MultipartError;
+use crate::multipart::MultipartValue;
+use crate::{DeserializeError, Deserr};
+
+/// Extractor for typed data from `multipart/form-data` request payloads
+/// deserialised by deserr.
+///
+/// # Extractor
+/// The text parts and the files are given to deserr as a map from the name of the part to its
+/// value, see the [`multipart`](crate::multipart) module.
+/// The inner type `T` must implement the [`deserr::Deserr<E>`] trait. The inner type `E` must
+/// implement the [`DeserializeError`] + `ResponseError` traits.
+///
+/// The whole payload is read in memory, and is limited by the [`AwebMultipartConfig`].
+#[derive(Debug)]
+pub struct AwebMultipart<T, E>(pub T, PhantomData<*const E>);
+
+impl<T, E> AwebMultipart<T, E> {
+    pub fn new(data: T) -> Self {
+        AwebMultipart(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Configuration of the [`AwebMultipart`] extractor.
+///
+/// Register it with [`App::app_data`](actix_web::App::app_data), or on a specific resource
+/// or scope, to change the limit of the multipart requests.
+#[derive(Debug, Clone, Copy)]
+pub struct AwebMultipartConfig {
+    limit: usize,
+}
+
+impl AwebMultipartConfig {
+    /// Set the maximum accepted payload size. By default this limit is 2MB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+impl Default for AwebMultipartConfig {
+    fn default() -> Self {
+        AwebMultipartConfig { limit: 2_097_152 }
+    }
+}
+
+impl<T, E> FromRequest for AwebMultipart<T, E>
+where
+    E: DeserializeError + ResponseError + 'static,
+    T: Deserr<E>,
+{
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, actix_web::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = req
+            .app_data::<AwebMultipartConfig>()
+            .copied()
+            .unwrap_or_default();
+        let content_type = req
+            .headers()
+            .get(actix_web::http::header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .unwrap_or_default()
+            .to_owned();
+        let mut payload = payload.take();
+
+        Box::pin(async move {
+            let boundary = multer::parse_boundary(content_type).map_err(|e| match e {
+                multer::Error::NoMultipart => actix_web::error::ErrorUnsupportedMediaType(e),
+                e => actix_web::error::ErrorBadRequest(e),
+            })?;
+
+            let mut body = Vec::new();
+            while let Some(chunk) = payload.next().await {
+                let chunk = chunk?;
+                if body.len() + chunk.len() > config.limit {
+                    return Err(PayloadError::Overflow.into());
+                }
+                body.extend_from_slice(&chunk);
+            }
+
+            let stream = futures::stream::once(async move { Ok::<_, Infallible>(body) });
+            let multipart = multer::Multipart::new(stream, boundary);
+            let value = MultipartValue::from_multipart(multipart)
+                .await
+                .map_err(|e| {
+                    actix_web::error::ErrorBadRequest(format!(
+                        "Failed to read the multipart request: {e}"
+                    ))
+                })?;
+            match deserr::deserialize::<_, _, E>(value) {
+                Ok(data) => Ok(AwebMultipart::new(data)),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+}
+
+impl actix_web::ResponseError for MultipartError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        actix_web::HttpResponseBuilder::new(self.status_code())
+            .content_type("text/plain")
+            .body(self.to_string())
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::Debug;
+use std::future::{ready, Ready};
+use std::sync::Arc;
+
+use actix_http::Payload;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::{mime, FromRequest, HttpRequest};
+
+use crate::ndjson::{content_type_error, is_ndjson_content_type, NdJsonStream};
+
+/// Extractor for a stream of typed data from newline-delimited JSON request payloads
+/// deserialised by deserr.
+///
+/// # Extractor
+/// The payload isn't read in memory: the inner [`NdJsonStream`] deserializes the lines of
+/// the payload one at a time while it is polled, yielding a `Result<T, E>` per line, see the
+/// [`ndjson`](crate::ndjson) module.
+/// The inner type `T` must implement the [`deserr::Deserr<E>`] trait. The inner type `E` must
+/// implement the [`DeserializeError`](crate::DeserializeError) trait.
+///
+/// The request is rejected if its content type is not accepted, and the stream yields an
+/// error on a line longer than the limit, see [`AwebNdJsonConfig`].
+pub struct AwebNdJson<T, E>(pub NdJsonStream<Payload, T, E>);
+
+impl<T, E> AwebNdJson<T, E> {
+    pub fn new(stream: NdJsonStream<Payload, T, E>) -> Self {
+        AwebNdJson(stream)
+    }
+
+    pub fn into_inner(self) -> NdJsonStream<Payload, T, E> {
+        self.0
+    }
+}
+
+/// Configuration of the [`AwebNdJson`] extractor.
+///
+/// Register it with [`App::app_data`](actix_web::App::app_data), or on a specific resource
+/// or scope, to change the maximum length of the lines and the accepted content types.
+#[derive(Clone)]
+pub struct AwebNdJsonConfig {
+    max_line_length: usize,
+    content_type: Option<Arc<dyn Fn(mime::Mime) -> bool + Send + Sync>>,
+    content_type_required: bool,
+}
+
+impl AwebNdJsonConfig {
+    /// Set the maximum length of a line in bytes, see [`NdJsonStream::max_line_length`].
+    /// By default this limit is 2MB.
+    pub fn max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = max_line_length;
+        self
+    }
+
+    /// Set a predicate accepting more content types than `application/x-ndjson`.
+    pub fn content_type<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(mime::Mime) -> bool + Send + Sync + 'static,
+    {
+        self.content_type = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Set whether the content type of the requests is checked. By default it is.
+    pub fn content_type_required(mut self, content_type_required: bool) -> Self {
+        self.content_type_required = content_type_required;
+        self
+    }
+
+    fn accepts(&self, content_type: &str) -> bool {
+        is_ndjson_content_type(content_type)
+            || self.content_type.as_ref().is_some_and(|predicate| {
+                content_type
+                    .parse::<mime::Mime>()
+                    .is_ok_and(|mime| predicate(mime))
+            })
+    }
+}
+
+impl Default for AwebNdJsonConfig {
+    fn default() -> Self {
+        AwebNdJsonConfig {
+            max_line_length: 2_097_152,
+            content_type: None,
+            content_type_required: true,
+        }
+    }
+}
+
+impl Debug for AwebNdJsonConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwebNdJsonConfig")
+            .field("max_line_length", &self.max_line_length)
+            .field("content_type", &self.content_type.as_ref().map(|_| ".."))
+            .field("content_type_required", &self.content_type_required)
+            .finish()
+    }
+}
+
+impl<T, E> FromRequest for AwebNdJson<T, E> {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, actix_web::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = req
+            .app_data::<AwebNdJsonConfig>()
+            .cloned()
+            .unwrap_or_default();
+        if config.content_type_required {
+            let content_type = req
+                .headers()
+                .get(CONTENT_TYPE)
+                .map(|content_type| String::from_utf8_lossy(content_type.as_bytes()));
+            match content_type.as_deref() {
+                Some(content_type) if config.accepts(content_type) => (),
+                content_type => {
+                    let message = content_type_error(content_type);
+                    return ready(Err(actix_web::error::ErrorUnsupportedMediaType(message)));
+                }
+            }
+        }
+        let stream = NdJsonStream::new(payload.take()).max_line_length(config.max_line_length);
+        ready(Ok(AwebNdJson(stream)))
+    }
+}
@@ -0,0 +1,85 @@
+use std::future::{ready, Ready};
+use std::marker::PhantomData;
+use std::{fmt, ops};
+
+use actix_http::Payload;
+use actix_web::{FromRequest, HttpRequest, ResponseError};
+
+use crate::errors::PathError;
+use crate::query_string::QueryValue;
+use crate::{DeserializeError, Deserr};
+
+/// Extractor for typed data from the matched segments of the path of a request
+/// deserialised by deserr.
+///
+/// # Extractor
+/// The segments are given to deserr as a map from the name of the segment to its value, e.g.
+/// `/indexes/{uid}/documents/{id}` becomes `{ "uid": ..., "id": ... }`.
+/// The inner type `T` must implement the [`deserr::Deserr<E>`] trait. The inner type `E` must
+/// implement the [`DeserializeError`] + `ResponseError` traits.
+///
+/// The numbers and booleans are parsed from their string representation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AwebPath<T, E>(pub T, PhantomData<*const E>);
+
+impl<T, E> AwebPath<T, E> {
+    /// Unwrap into inner `T` value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, E> ops::Deref for AwebPath<T, E> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, E> ops::DerefMut for AwebPath<T, E> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Display, E> fmt::Display for AwebPath<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T, E> FromRequest for AwebPath<T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError + ResponseError + 'static,
+{
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, actix_web::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let segments = req
+            .match_info()
+            .iter()
+            .map(|(key, value)| (key.to_owned(), QueryValue::String(value.to_owned())));
+        let value = QueryValue::Map(segments.collect());
+
+        ready(match deserr::deserialize::<_, _, E>(value) {
+            Ok(data) => Ok(AwebPath(data, PhantomData)),
+            Err(e) => Err(e.into()),
+        })
+    }
+}
+
+impl actix_web::ResponseError for PathError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        actix_web::HttpResponseBuilder::new(self.status_code())
+            .content_type("text/plain")
+            .body(self.to_string())
+    }
+}
@@ -1,12 +1,16 @@
-//! A module to parse query parameter as String with deserr
+//! A module to parse query parameters with deserr
+//!
+//! The way the query string is parsed can be configured by registering a [`QueryConfig`]
+//! with [`App::app_data`](actix_web::App::app_data), or on a specific resource or scope.
 
+use std::future::{ready, Ready};
 use std::marker::PhantomData;
 use std::{fmt, ops};
 
+use crate::query_string::{QueryConfig, QueryValue};
 use crate::{DeserializeError, Deserr};
 use actix_http::Payload;
-use actix_utils::future::{err, ok, Ready};
-use actix_web::web::Query;
+use actix_web::error::QueryPayloadError;
 use actix_web::{FromRequest, HttpRequest, ResponseError};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -24,10 +28,22 @@ where
     T: Deserr<E>,
     E: DeserializeError + ResponseError + 'static,
 {
+    /// Deserialize `T` from a query string.
+    ///
+    /// The numbers and booleans are parsed from their string representation.
     pub fn from_query(query_str: &str) -> Result<Self, actix_web::Error> {
-        let value = Query::<serde_json::Value>::from_query(query_str)?;
+        Self::from_query_with_config(query_str, QueryConfig::default())
+    }
+
+    /// Deserialize `T` from a query string parsed following the given [`QueryConfig`].
+    pub fn from_query_with_config(
+        query_str: &str,
+        config: QueryConfig,
+    ) -> Result<Self, actix_web::Error> {
+        let value = QueryValue::from_query_with_config(query_str, config)
+            .map_err(QueryPayloadError::Deserialize)?;
 
-        match deserr::deserialize::<_, _, E>(value.0) {
+        match deserr::deserialize::<_, _, E>(value) {
             Ok(data) => Ok(AwebQueryParameter(data, PhantomData)),
             Err(e) => Err(e)?,
         }
@@ -64,8 +80,10 @@ where
 
     #[inline]
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        AwebQueryParameter::from_query(req.query_string())
-            .map(ok)
-            .unwrap_or_else(err)
+        let config = req.app_data::<QueryConfig>().copied().unwrap_or_default();
+        ready(AwebQueryParameter::from_query_with_config(
+            req.query_string(),
+            config,
+        ))
     }
 }
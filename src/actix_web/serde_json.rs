@@ -2,15 +2,18 @@ use std::fmt::Debug;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
-use actix_web::dev::Payload;
-use actix_web::web::Json;
-use actix_web::{FromRequest, HttpRequest, ResponseError};
-use deserr::{DeserializeError, Deserr};
+use actix_web::dev::{JsonBody, Payload};
+use actix_web::error::{InternalError, JsonPayloadError};
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::http::StatusCode;
+use actix_web::{mime, FromRequest, HttpRequest, ResponseError};
+use deserr::{take_cf_content, DeserializeError, Deserr, MergeWithError, ValuePointerRef};
 use futures::ready;
 
-use crate::errors::JsonError;
+use crate::errors::{JsonBodyError, JsonError};
 
 /// Extractor for typed data from Json request payloads
 /// deserialised by deserr.
@@ -18,7 +21,11 @@ use crate::errors::JsonError;
 /// # Extractor
 /// To extract typed data from a request body, the inner type `T` must implement the
 /// [`deserr::Deserr<E>`] trait. The inner type `E` must implement the
-/// [`DeserializeError`] + `ResponseError` traits.
+/// [`DeserializeError`] + [`MergeWithError<JsonBodyError>`] + `ResponseError` traits.
+///
+/// The request is rejected with an `E` if its payload is larger than the limit or if its
+/// content type is not accepted, see [`AwebJsonConfig`]. The response of the error is then
+/// sent with the status code of the [`JsonBodyError`], 413 or 415.
 #[derive(Debug)]
 pub struct AwebJson<T, E>(pub T, PhantomData<*const E>);
 
@@ -32,9 +39,65 @@ impl<T, E> AwebJson<T, E> {
     }
 }
 
+/// Configuration of the [`AwebJson`] extractor.
+///
+/// Register it with [`App::app_data`](actix_web::App::app_data), or on a specific resource
+/// or scope, to change the limit and the accepted content types of the Json payloads.
+/// The [`JsonConfig`](actix_web::web::JsonConfig) of actix-web is not used by [`AwebJson`].
+#[derive(Clone)]
+pub struct AwebJsonConfig {
+    limit: usize,
+    content_type: Option<Arc<dyn Fn(mime::Mime) -> bool + Send + Sync>>,
+    content_type_required: bool,
+}
+
+impl AwebJsonConfig {
+    /// Set the maximum accepted payload size. By default this limit is 2MB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set a predicate accepting more content types than `application/json` and the
+    /// `+json` suffixes.
+    pub fn content_type<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(mime::Mime) -> bool + Send + Sync + 'static,
+    {
+        self.content_type = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Set whether the content type of the requests is checked. By default it is.
+    pub fn content_type_required(mut self, content_type_required: bool) -> Self {
+        self.content_type_required = content_type_required;
+        self
+    }
+}
+
+impl Default for AwebJsonConfig {
+    fn default() -> Self {
+        AwebJsonConfig {
+            limit: 2_097_152,
+            content_type: None,
+            content_type_required: true,
+        }
+    }
+}
+
+impl Debug for AwebJsonConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwebJsonConfig")
+            .field("limit", &self.limit)
+            .field("content_type", &self.content_type.as_ref().map(|_| ".."))
+            .field("content_type_required", &self.content_type_required)
+            .finish()
+    }
+}
+
 impl<T, E> FromRequest for AwebJson<T, E>
 where
-    E: DeserializeError + ResponseError + 'static,
+    E: DeserializeError + MergeWithError<JsonBodyError> + ResponseError + 'static,
     T: Deserr<E>,
 {
     type Error = actix_web::Error;
@@ -42,34 +105,69 @@ where
 
     #[inline]
     fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = req
+            .app_data::<AwebJsonConfig>()
+            .cloned()
+            .unwrap_or_default();
+        let content_type = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .map(|content_type| String::from_utf8_lossy(content_type.as_bytes()).into_owned());
         AwebJsonExtractFut {
-            fut: Json::<serde_json::Value>::from_request(req, payload),
+            fut: JsonBody::new(
+                req,
+                payload,
+                config.content_type.as_deref(),
+                config.content_type_required,
+            )
+            .limit(config.limit),
+            content_type,
             _phantom: PhantomData,
         }
     }
 }
 
 pub struct AwebJsonExtractFut<T, E> {
-    fut: <Json<serde_json::Value> as FromRequest>::Future,
+    fut: JsonBody<serde_json::Value>,
+    content_type: Option<String>,
     _phantom: PhantomData<*const (T, E)>,
 }
 
 impl<T, E> Future for AwebJsonExtractFut<T, E>
 where
     T: Deserr<E>,
-    E: DeserializeError + ResponseError + 'static,
+    E: DeserializeError + MergeWithError<JsonBodyError> + ResponseError + 'static,
 {
     type Output = Result<AwebJson<T, E>, actix_web::Error>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let AwebJsonExtractFut { fut, .. } = self.get_mut();
+        let AwebJsonExtractFut {
+            fut, content_type, ..
+        } = self.get_mut();
         let fut = Pin::new(fut);
 
         let res = ready!(fut.poll(cx));
 
         let res = match res {
-            Err(err) => Err(err),
-            Ok(data) => match deserr::deserialize::<_, _, E>(data.into_inner()) {
+            Err(err) => {
+                let body_error = match err {
+                    JsonPayloadError::OverflowKnownLength { limit, .. }
+                    | JsonPayloadError::Overflow { limit } => {
+                        JsonBodyError::PayloadTooLarge { limit: Some(limit) }
+                    }
+                    JsonPayloadError::ContentType => match content_type.take() {
+                        Some(content_type) => JsonBodyError::InvalidContentType(content_type),
+                        None => JsonBodyError::MissingContentType,
+                    },
+                    err => return Poll::Ready(Err(err.into())),
+                };
+                let status = StatusCode::from_u16(body_error.status_code()).unwrap();
+                let error: E = take_cf_content(E::merge(None, body_error, ValuePointerRef::Origin));
+                let mut response = error.error_response();
+                *response.status_mut() = status;
+                Err(InternalError::from_response(error, response).into())
+            }
+            Ok(data) => match deserr::deserialize::<_, _, E>(data) {
                 Ok(data) => Ok(AwebJson::new(data)),
                 Err(e) => Err(e)?,
             },
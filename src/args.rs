@@ -0,0 +1,198 @@
+//! A command-line arguments backend for deserr.
+//!
+//! The arguments are a map from the names of the options to their values:
+//! - `--key value` and `--key=value` give the string `value` to the key `key`.
+//! - `--flag`, when it isn't followed by a value, gives the string `true` to the key `flag`.
+//! - `--db.port 5432` gives `5432` to the key `port` of the map `db`.
+//! - An option given multiple times, e.g. `--tag a --tag b`, is a sequence of values.
+//!
+//! Every value is a string, thus [`ArgsValue`] coerces the strings into numbers and
+//! booleans when needed, see [`IntoValue::COERCE_STRINGS`].
+//!
+//! ```
+//! use deserr::Deserr;
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! #[deserr(deny_unknown_fields)]
+//! struct Args {
+//!     db: Db,
+//!     #[deserr(default)]
+//!     verbose: bool,
+//!     #[deserr(default)]
+//!     tag: Vec<String>,
+//! }
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! #[deserr(deny_unknown_fields)]
+//! struct Db {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! let args = ["--db.host", "localhost", "--db.port=5432", "--verbose", "--tag", "a", "--tag", "b"];
+//! let parsed = deserr::args::deserialize_args::<Args>(args.map(String::from)).unwrap();
+//! assert_eq!(parsed.db, Db { host: String::from("localhost"), port: 5432 });
+//! assert!(parsed.verbose);
+//! assert_eq!(parsed.tag, ["a", "b"]);
+//!
+//! let args = ["--db.host", "localhost", "--db.prot", "5432"];
+//! let error = deserr::args::deserialize_args::<Args>(args.map(String::from)).unwrap_err();
+//! assert_eq!(
+//!     error.to_string(),
+//!     "Unknown option `--db.prot`: did you mean `--db.port`? expected one of `--db.host`, `--db.port`"
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::errors::ArgsError;
+use crate::{Deserr, IntoValue, Map, Value, ValueKind};
+
+/// Deserialize the arguments given to the process, without the name of the program.
+pub fn deserialize<T: Deserr<ArgsError>>() -> Result<T, ArgsError> {
+    let args = std::env::args_os()
+        .skip(1)
+        .map(|arg| {
+            arg.into_string().map_err(|arg| {
+                ArgsError::invalid_argument(
+                    &arg.to_string_lossy(),
+                    "the argument is not valid unicode",
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    deserialize_args(args)
+}
+
+/// Deserialize the given arguments, which must not include the name of the program.
+pub fn deserialize_args<T: Deserr<ArgsError>>(
+    args: impl IntoIterator<Item = String>,
+) -> Result<T, ArgsError> {
+    let value = ArgsValue::from_args(args)?;
+    crate::deserialize::<T, _, ArgsError>(value)
+}
+
+/// A value parsed from command-line arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgsValue {
+    String(String),
+    Sequence(Vec<ArgsValue>),
+    Map(ArgsMap),
+}
+
+impl ArgsValue {
+    /// Build a map of options from the given arguments.
+    ///
+    /// If an option is both a value and a map, e.g. `--db` and `--db.port`, the map is kept.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, ArgsError> {
+        let mut map = ArgsMap::default();
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            let Some(option) = arg.strip_prefix("--") else {
+                return Err(ArgsError::invalid_argument(
+                    &arg,
+                    "expected an option starting with `--`",
+                ));
+            };
+            let (name, value) = match option.split_once('=') {
+                Some((name, value)) => (name, value.to_owned()),
+                None => match args.next_if(|next| !next.starts_with("--")) {
+                    Some(value) => (option, value),
+                    None => (option, String::from("true")),
+                },
+            };
+            let keys = name.split('.').map(str::to_owned).collect::<Vec<_>>();
+            if keys.iter().any(String::is_empty) {
+                return Err(ArgsError::invalid_argument(
+                    &arg,
+                    "expected the name of an option after `--`",
+                ));
+            }
+            map.insert_at(keys, value);
+        }
+        Ok(ArgsValue::Map(map))
+    }
+}
+
+/// The options given in the arguments, sorted by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArgsMap(BTreeMap<String, ArgsValue>);
+
+impl ArgsMap {
+    /// Insert the value at the path described by the keys. If there is already a value, the
+    /// values become a sequence.
+    fn insert_at(&mut self, mut keys: Vec<String>, value: String) {
+        let last = keys.pop().expect("an option has at least one key");
+        let mut map = self;
+        for key in keys {
+            let entry = map
+                .0
+                .entry(key)
+                .or_insert_with(|| ArgsValue::Map(ArgsMap::default()));
+            if !matches!(entry, ArgsValue::Map(_)) {
+                *entry = ArgsValue::Map(ArgsMap::default());
+            }
+            let ArgsValue::Map(inner) = entry else {
+                unreachable!()
+            };
+            map = inner;
+        }
+        let value = ArgsValue::String(value);
+        match map.0.get_mut(&last) {
+            None => {
+                map.0.insert(last, value);
+            }
+            Some(ArgsValue::Map(_)) => (),
+            Some(ArgsValue::Sequence(values)) => values.push(value),
+            Some(entry @ ArgsValue::String(_)) => {
+                let previous = std::mem::replace(entry, ArgsValue::Sequence(Vec::new()));
+                *entry = ArgsValue::Sequence(vec![previous, value]);
+            }
+        }
+    }
+}
+
+impl Map for ArgsMap {
+    type Value = ArgsValue;
+    type Iter = std::collections::btree_map::IntoIter<String, ArgsValue>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        self.0.remove(key)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        self.0.into_iter()
+    }
+}
+
+impl IntoValue for ArgsValue {
+    type Sequence = Vec<ArgsValue>;
+    type Map = ArgsMap;
+
+    const COERCE_STRINGS: bool = true;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            ArgsValue::String(_) => ValueKind::String,
+            ArgsValue::Sequence(_) => ValueKind::Sequence,
+            ArgsValue::Map(_) => ValueKind::Map,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            ArgsValue::String(s) => Value::String(s),
+            ArgsValue::Sequence(seq) => Value::Sequence(seq),
+            ArgsValue::Map(map) => Value::Map(map),
+        }
+    }
+
+    /// An option given once is a sequence of one value.
+    fn string_into_sequence(s: String) -> Result<Vec<ArgsValue>, String> {
+        Ok(vec![ArgsValue::String(s)])
+    }
+}
@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+
+use crate::errors::FormError;
+use crate::query_string::{QueryConfig, QueryValue};
+use crate::{DeserializeError, Deserr};
+use axum::body::Bytes;
+use axum::extract::rejection::{BytesRejection, InvalidFormContentType};
+use axum::extract::FromRequest;
+use axum::response::IntoResponse;
+use http::{header, StatusCode};
+
+/// Extractor for typed data from `application/x-www-form-urlencoded` request payloads
+/// deserialised by deserr.
+///
+/// ## Extractor
+/// To extract typed data from a request body, the inner type `T` must implement the
+/// [`deserr::Deserr<E>`] trait. The inner type `E` must implement the
+/// [`DeserializeError`] trait.
+///
+/// The request is rejected if its content type is not `application/x-www-form-urlencoded`
+/// or if its payload is larger than the limit set by the
+/// [`DefaultBodyLimit`](axum::extract::DefaultBodyLimit) layer, 2MB by default.
+/// The way the fields are parsed can be configured by adding a [`QueryConfig`] to the
+/// request extensions, for example with the [`Extension`](axum::Extension) layer.
+#[derive(Debug)]
+pub struct AxumForm<T, E>(pub T, PhantomData<E>);
+
+impl<T, E> AxumForm<T, E> {
+    pub fn new(data: T) -> Self {
+        AxumForm(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+pub enum AxumFormRejection<E: DeserializeError> {
+    DeserrError(E),
+    InvalidFormContentType(InvalidFormContentType),
+    BytesRejection(BytesRejection),
+    InvalidFormBody(serde_urlencoded::de::Error),
+}
+
+impl<E: DeserializeError + std::fmt::Display> std::fmt::Display for AxumFormRejection<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AxumFormRejection::DeserrError(e) => e.fmt(f),
+            AxumFormRejection::InvalidFormContentType(e) => e.fmt(f),
+            AxumFormRejection::BytesRejection(e) => e.fmt(f),
+            AxumFormRejection::InvalidFormBody(e) => {
+                write!(f, "Failed to deserialize form body: {e}")
+            }
+        }
+    }
+}
+
+impl<T, E, S> FromRequest<S> for AxumForm<T, E>
+where
+    E: DeserializeError + IntoResponse + 'static,
+    T: Deserr<E>,
+    S: Send + Sync,
+{
+    type Rejection = AxumFormRejection<E>;
+
+    async fn from_request(req: axum::extract::Request, state: &S) -> Result<Self, Self::Rejection> {
+        let is_form = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| {
+                content_type.starts_with("application/x-www-form-urlencoded")
+            });
+        if !is_form {
+            return Err(AxumFormRejection::InvalidFormContentType(
+                InvalidFormContentType::default(),
+            ));
+        }
+        let config = req
+            .extensions()
+            .get::<QueryConfig>()
+            .copied()
+            .unwrap_or_default();
+        let bytes = Bytes::from_request(req, state).await?;
+        let pairs = serde_urlencoded::from_bytes::<Vec<(String, String)>>(&bytes)
+            .map_err(AxumFormRejection::InvalidFormBody)?;
+        let value =
+            QueryValue::from_pairs(pairs, config).map_err(AxumFormRejection::InvalidFormBody)?;
+        let data = deserr::deserialize::<_, _, _>(value)?;
+        Ok(AxumForm(data, PhantomData))
+    }
+}
+
+impl<E: DeserializeError> From<E> for AxumFormRejection<E> {
+    fn from(value: E) -> Self {
+        AxumFormRejection::DeserrError(value)
+    }
+}
+
+impl<E: DeserializeError> From<BytesRejection> for AxumFormRejection<E> {
+    fn from(value: BytesRejection) -> Self {
+        AxumFormRejection::BytesRejection(value)
+    }
+}
+
+impl<E: DeserializeError + IntoResponse> IntoResponse for AxumFormRejection<E> {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            AxumFormRejection::DeserrError(e) => e.into_response(),
+            AxumFormRejection::InvalidFormContentType(e) => e.into_response(),
+            AxumFormRejection::BytesRejection(e) => e.into_response(),
+            AxumFormRejection::InvalidFormBody(e) => (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to deserialize form body: {e}"),
+            )
+                .into_response(),
+        }
+    }
+}
+
+impl IntoResponse for FormError {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
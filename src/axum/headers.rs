@@ -0,0 +1,50 @@
+use std::marker::PhantomData;
+
+use crate::errors::HeaderError;
+use crate::headers::HeadersValue;
+use crate::{DeserializeError, Deserr};
+use axum::extract::FromRequestParts;
+use axum::response::IntoResponse;
+use http::request::Parts;
+use http::StatusCode;
+
+/// Extractor for typed data from the headers of a request deserialised by deserr.
+///
+/// ## Extractor
+/// The header names are case-insensitive, thus given in lowercase, and a header specified
+/// multiple times is a sequence of values, see the [`headers`](crate::headers) module.
+/// The inner type `T` must implement the [`deserr::Deserr<E>`] trait. The inner type `E` must
+/// implement the [`DeserializeError`] trait, and is directly used as the rejection.
+#[derive(Debug)]
+pub struct AxumHeaders<T, E>(pub T, PhantomData<E>);
+
+impl<T, E> AxumHeaders<T, E> {
+    pub fn new(data: T) -> Self {
+        AxumHeaders(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, E, S> FromRequestParts<S> for AxumHeaders<T, E>
+where
+    E: DeserializeError + IntoResponse + 'static,
+    T: Deserr<E>,
+    S: Send + Sync,
+{
+    type Rejection = E;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = HeadersValue::from(parts.headers.clone());
+        let data = deserr::deserialize::<_, _, _>(value)?;
+        Ok(AxumHeaders(data, PhantomData))
+    }
+}
+
+impl IntoResponse for HeaderError {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
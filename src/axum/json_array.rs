@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use crate::errors::JsonBodyError;
+use crate::json_array::JsonArrayStream;
+use crate::serde_json::is_json_content_type;
+use axum::body::BodyDataStream;
+use axum::extract::FromRequest;
+use axum::response::IntoResponse;
+use http::{header, StatusCode};
+
+/// Extractor for a stream of typed data from the elements of a JSON array request payload
+/// deserialised by deserr.
+///
+/// ## Extractor
+/// The payload isn't read in memory: the inner [`JsonArrayStream`] deserializes the elements
+/// of the array one at a time while it is polled, yielding a `Result<T, E>` per element, see
+/// the [`json_array`](crate::json_array) module.
+/// The inner type `T` must implement the [`deserr::Deserr<E>`] trait. The inner type `E` must
+/// implement the [`DeserializeError`](crate::DeserializeError) trait.
+///
+/// To read an array nested in objects, such as `{ "documents": [...] }`, give the keys
+/// leading to it to [`JsonArrayStream::key_path`] before polling the stream.
+///
+/// The request is rejected if its content type is not accepted, and the stream yields an
+/// error on an element longer than the limit. These can be configured by adding an
+/// [`AxumJsonArrayConfig`] to the request extensions, e.g. with the
+/// [`Extension`](axum::Extension) layer.
+pub struct AxumJsonArray<T, E>(pub JsonArrayStream<BodyDataStream, T, E>);
+
+impl<T, E> AxumJsonArray<T, E> {
+    pub fn new(stream: JsonArrayStream<BodyDataStream, T, E>) -> Self {
+        AxumJsonArray(stream)
+    }
+
+    pub fn into_inner(self) -> JsonArrayStream<BodyDataStream, T, E> {
+        self.0
+    }
+}
+
+type ContentTypePredicate = dyn Fn(&str) -> bool + Send + Sync;
+
+/// Configuration of the [`AxumJsonArray`] extractor.
+#[derive(Clone)]
+pub struct AxumJsonArrayConfig {
+    max_element_length: usize,
+    content_type: Option<Arc<ContentTypePredicate>>,
+    content_type_required: bool,
+}
+
+impl AxumJsonArrayConfig {
+    /// Set the maximum length of an element in bytes, see
+    /// [`JsonArrayStream::max_element_length`]. By default this limit is 2MB.
+    pub fn max_element_length(mut self, max_element_length: usize) -> Self {
+        self.max_element_length = max_element_length;
+        self
+    }
+
+    /// Set a predicate accepting more content types than `application/json` and the
+    /// `+json` suffixes. It is given the value of the `Content-Type` header.
+    pub fn content_type<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.content_type = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Set whether the content type of the requests is checked. By default it is.
+    pub fn content_type_required(mut self, content_type_required: bool) -> Self {
+        self.content_type_required = content_type_required;
+        self
+    }
+
+    fn accepts(&self, content_type: &str) -> bool {
+        is_json_content_type(content_type)
+            || self
+                .content_type
+                .as_ref()
+                .is_some_and(|predicate| predicate(content_type))
+    }
+}
+
+impl Default for AxumJsonArrayConfig {
+    fn default() -> Self {
+        AxumJsonArrayConfig {
+            max_element_length: 2_097_152,
+            content_type: None,
+            content_type_required: true,
+        }
+    }
+}
+
+impl std::fmt::Debug for AxumJsonArrayConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AxumJsonArrayConfig")
+            .field("max_element_length", &self.max_element_length)
+            .field("content_type", &self.content_type.as_ref().map(|_| ".."))
+            .field("content_type_required", &self.content_type_required)
+            .finish()
+    }
+}
+
+/// The rejection of the [`AxumJsonArray`] extractor, when the content type of the request is
+/// missing or not accepted.
+#[derive(Debug)]
+pub enum AxumJsonArrayRejection {
+    MissingContentType,
+    InvalidContentType(String),
+}
+
+impl std::fmt::Display for AxumJsonArrayRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AxumJsonArrayRejection::MissingContentType => JsonBodyError::MissingContentType.fmt(f),
+            AxumJsonArrayRejection::InvalidContentType(content_type) => {
+                JsonBodyError::InvalidContentType(content_type.clone()).fmt(f)
+            }
+        }
+    }
+}
+
+impl<T, E, S> FromRequest<S> for AxumJsonArray<T, E>
+where
+    S: Send + Sync,
+{
+    type Rejection = AxumJsonArrayRejection;
+
+    async fn from_request(
+        req: axum::extract::Request,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let config = req
+            .extensions()
+            .get::<AxumJsonArrayConfig>()
+            .cloned()
+            .unwrap_or_default();
+
+        if config.content_type_required {
+            match req.headers().get(header::CONTENT_TYPE) {
+                None => return Err(AxumJsonArrayRejection::MissingContentType),
+                Some(content_type) => {
+                    let content_type = String::from_utf8_lossy(content_type.as_bytes());
+                    if !config.accepts(&content_type) {
+                        let content_type = content_type.into_owned();
+                        return Err(AxumJsonArrayRejection::InvalidContentType(content_type));
+                    }
+                }
+            }
+        }
+
+        let stream = req.into_body().into_data_stream();
+        let stream = JsonArrayStream::new(stream).max_element_length(config.max_element_length);
+        Ok(AxumJsonArray(stream))
+    }
+}
+
+impl IntoResponse for AxumJsonArrayRejection {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string()).into_response()
+    }
+}
@@ -1,5 +1,45 @@
 #[cfg(feature = "serde-json")]
+mod form;
+#[cfg(feature = "serde-json")]
+mod headers;
+#[cfg(feature = "serde-json")]
+mod json_array;
+#[cfg(all(feature = "serde-json", feature = "multipart"))]
+mod multipart;
+#[cfg(feature = "serde-json")]
+mod ndjson;
+#[cfg(feature = "serde-json")]
+mod path;
+#[cfg(feature = "serde-json")]
+mod query_parameters;
+#[cfg(feature = "serde-json")]
 mod serde_json;
 
 #[cfg(feature = "serde-json")]
-pub use self::serde_json::{AxumJson, AxumJsonRejection};
+pub use self::form::{AxumForm, AxumFormRejection};
+#[cfg(feature = "serde-json")]
+pub use self::headers::AxumHeaders;
+#[cfg(feature = "serde-json")]
+pub use self::json_array::{AxumJsonArray, AxumJsonArrayConfig, AxumJsonArrayRejection};
+#[cfg(all(feature = "serde-json", feature = "multipart"))]
+pub use self::multipart::{AxumMultipart, AxumMultipartRejection};
+#[cfg(feature = "serde-json")]
+pub use self::ndjson::{AxumNdJson, AxumNdJsonConfig, AxumNdJsonRejection};
+#[cfg(feature = "serde-json")]
+pub use self::path::{AxumPath, AxumPathRejection};
+#[cfg(feature = "serde-json")]
+pub use self::query_parameters::{AxumQuery, AxumQueryRejection};
+#[cfg(feature = "serde-json")]
+pub use self::serde_json::{AxumJson, AxumJsonConfig, AxumJsonRejection};
+
+/// Whether the error, or one of its sources, comes from the body limit of the request.
+fn is_length_limit_error(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if error.is::<http_body_util::LengthLimitError>() {
+            return true;
+        }
+        source = error.source();
+    }
+    false
+}
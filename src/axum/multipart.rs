@@ -0,0 +1,125 @@
+use std::marker::PhantomData;
+
+use super::is_length_limit_error;
+use crate::errors::MultipartError;
+use crate::multipart::MultipartValue;
+use crate::{DeserializeError, Deserr};
+use axum::extract::FromRequest;
+use axum::response::IntoResponse;
+use axum::RequestExt;
+use http::{header, StatusCode};
+
+/// Extractor for typed data from `multipart/form-data` request payloads
+/// deserialised by deserr.
+///
+/// ## Extractor
+/// The text parts and the files are given to deserr as a map from the name of the part to its
+/// value, see the [`multipart`](crate::multipart) module.
+/// The inner type `T` must implement the [`deserr::Deserr<E>`] trait. The inner type `E` must
+/// implement the [`DeserializeError`] trait.
+///
+/// The whole payload is read in memory, and is limited by the
+/// [`DefaultBodyLimit`](axum::extract::DefaultBodyLimit) layer, 2MB by default.
+#[derive(Debug)]
+pub struct AxumMultipart<T, E>(pub T, PhantomData<E>);
+
+impl<T, E> AxumMultipart<T, E> {
+    pub fn new(data: T) -> Self {
+        AxumMultipart(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+pub enum AxumMultipartRejection<E: DeserializeError> {
+    DeserrError(E),
+    InvalidBoundary(multer::Error),
+    MultipartError(multer::Error),
+}
+
+impl<E: DeserializeError + std::fmt::Display> std::fmt::Display for AxumMultipartRejection<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AxumMultipartRejection::DeserrError(e) => e.fmt(f),
+            AxumMultipartRejection::InvalidBoundary(e) => {
+                write!(f, "Invalid `multipart/form-data` request: {e}")
+            }
+            AxumMultipartRejection::MultipartError(e) => {
+                write!(f, "Failed to read the multipart request: {e}")
+            }
+        }
+    }
+}
+
+impl<T, E, S> FromRequest<S> for AxumMultipart<T, E>
+where
+    E: DeserializeError + IntoResponse + 'static,
+    T: Deserr<E>,
+    S: Send + Sync,
+{
+    type Rejection = AxumMultipartRejection<E>;
+
+    async fn from_request(
+        req: axum::extract::Request,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .unwrap_or_default();
+        let boundary = multer::parse_boundary(content_type)
+            .map_err(AxumMultipartRejection::InvalidBoundary)?;
+        let stream = req.with_limited_body().into_body().into_data_stream();
+        let multipart = multer::Multipart::new(stream, boundary);
+        let value = MultipartValue::from_multipart(multipart)
+            .await
+            .map_err(AxumMultipartRejection::MultipartError)?;
+        let data = deserr::deserialize::<_, _, _>(value)?;
+        Ok(AxumMultipart(data, PhantomData))
+    }
+}
+
+impl<E: DeserializeError> From<E> for AxumMultipartRejection<E> {
+    fn from(value: E) -> Self {
+        AxumMultipartRejection::DeserrError(value)
+    }
+}
+
+impl<E: DeserializeError + IntoResponse> IntoResponse for AxumMultipartRejection<E> {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            AxumMultipartRejection::DeserrError(e) => e.into_response(),
+            AxumMultipartRejection::InvalidBoundary(e) => (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid `multipart/form-data` request: {e}"),
+            )
+                .into_response(),
+            AxumMultipartRejection::MultipartError(e) => {
+                let status = if is_payload_too_large(&e) {
+                    StatusCode::PAYLOAD_TOO_LARGE
+                } else {
+                    StatusCode::BAD_REQUEST
+                };
+                (status, format!("Failed to read the multipart request: {e}")).into_response()
+            }
+        }
+    }
+}
+
+/// Whether the error comes from the body limit of the request.
+fn is_payload_too_large(error: &multer::Error) -> bool {
+    match error {
+        multer::Error::StreamReadFailed(source) => is_length_limit_error(&**source),
+        _ => false,
+    }
+}
+
+impl IntoResponse for MultipartError {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::ndjson::{content_type_error, is_ndjson_content_type, NdJsonStream};
+use axum::body::BodyDataStream;
+use axum::extract::FromRequest;
+use axum::response::IntoResponse;
+use http::{header, StatusCode};
+
+/// Extractor for a stream of typed data from newline-delimited JSON request payloads
+/// deserialised by deserr.
+///
+/// ## Extractor
+/// The payload isn't read in memory: the inner [`NdJsonStream`] deserializes the lines of
+/// the body one at a time while it is polled, yielding a `Result<T, E>` per line, see the
+/// [`ndjson`](crate::ndjson) module.
+/// The inner type `T` must implement the [`deserr::Deserr<E>`] trait. The inner type `E` must
+/// implement the [`DeserializeError`](crate::DeserializeError) trait.
+///
+/// The request is rejected if its content type is not accepted, and the stream yields an
+/// error on a line longer than the limit. These can be configured by adding an
+/// [`AxumNdJsonConfig`] to the request extensions, e.g. with the
+/// [`Extension`](axum::Extension) layer.
+pub struct AxumNdJson<T, E>(pub NdJsonStream<BodyDataStream, T, E>);
+
+impl<T, E> AxumNdJson<T, E> {
+    pub fn new(stream: NdJsonStream<BodyDataStream, T, E>) -> Self {
+        AxumNdJson(stream)
+    }
+
+    pub fn into_inner(self) -> NdJsonStream<BodyDataStream, T, E> {
+        self.0
+    }
+}
+
+type ContentTypePredicate = dyn Fn(&str) -> bool + Send + Sync;
+
+/// Configuration of the [`AxumNdJson`] extractor.
+#[derive(Clone)]
+pub struct AxumNdJsonConfig {
+    max_line_length: usize,
+    content_type: Option<Arc<ContentTypePredicate>>,
+    content_type_required: bool,
+}
+
+impl AxumNdJsonConfig {
+    /// Set the maximum length of a line in bytes, see [`NdJsonStream::max_line_length`].
+    /// By default this limit is 2MB.
+    pub fn max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = max_line_length;
+        self
+    }
+
+    /// Set a predicate accepting more content types than `application/x-ndjson`. It is
+    /// given the value of the `Content-Type` header.
+    pub fn content_type<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.content_type = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Set whether the content type of the requests is checked. By default it is.
+    pub fn content_type_required(mut self, content_type_required: bool) -> Self {
+        self.content_type_required = content_type_required;
+        self
+    }
+
+    fn accepts(&self, content_type: &str) -> bool {
+        is_ndjson_content_type(content_type)
+            || self
+                .content_type
+                .as_ref()
+                .is_some_and(|predicate| predicate(content_type))
+    }
+}
+
+impl Default for AxumNdJsonConfig {
+    fn default() -> Self {
+        AxumNdJsonConfig {
+            max_line_length: 2_097_152,
+            content_type: None,
+            content_type_required: true,
+        }
+    }
+}
+
+impl std::fmt::Debug for AxumNdJsonConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AxumNdJsonConfig")
+            .field("max_line_length", &self.max_line_length)
+            .field("content_type", &self.content_type.as_ref().map(|_| ".."))
+            .field("content_type_required", &self.content_type_required)
+            .finish()
+    }
+}
+
+/// The rejection of the [`AxumNdJson`] extractor, when the content type of the request is
+/// missing or not accepted.
+#[derive(Debug)]
+pub enum AxumNdJsonRejection {
+    MissingContentType,
+    InvalidContentType(String),
+}
+
+impl std::fmt::Display for AxumNdJsonRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            AxumNdJsonRejection::MissingContentType => content_type_error(None),
+            AxumNdJsonRejection::InvalidContentType(content_type) => {
+                content_type_error(Some(content_type))
+            }
+        };
+        f.write_str(&message)
+    }
+}
+
+impl<T, E, S> FromRequest<S> for AxumNdJson<T, E>
+where
+    S: Send + Sync,
+{
+    type Rejection = AxumNdJsonRejection;
+
+    async fn from_request(
+        req: axum::extract::Request,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let config = req
+            .extensions()
+            .get::<AxumNdJsonConfig>()
+            .cloned()
+            .unwrap_or_default();
+
+        if config.content_type_required {
+            match req.headers().get(header::CONTENT_TYPE) {
+                None => return Err(AxumNdJsonRejection::MissingContentType),
+                Some(content_type) => {
+                    let content_type = String::from_utf8_lossy(content_type.as_bytes());
+                    if !config.accepts(&content_type) {
+                        let content_type = content_type.into_owned();
+                        return Err(AxumNdJsonRejection::InvalidContentType(content_type));
+                    }
+                }
+            }
+        }
+
+        let stream = req.into_body().into_data_stream();
+        let stream = NdJsonStream::new(stream).max_line_length(config.max_line_length);
+        Ok(AxumNdJson(stream))
+    }
+}
+
+impl IntoResponse for AxumNdJsonRejection {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string()).into_response()
+    }
+}
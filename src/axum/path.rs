@@ -0,0 +1,94 @@
+use std::marker::PhantomData;
+
+use crate::errors::PathError;
+use crate::query_string::QueryValue;
+use crate::{DeserializeError, Deserr};
+use axum::extract::rejection::RawPathParamsRejection;
+use axum::extract::{FromRequestParts, RawPathParams};
+use axum::response::IntoResponse;
+use http::request::Parts;
+use http::StatusCode;
+
+/// Extractor for typed data from the matched segments of the path of a request
+/// deserialised by deserr.
+///
+/// ## Extractor
+/// The segments are given to deserr as a map from the name of the segment to its value, e.g.
+/// `/indexes/{uid}/documents/{id}` becomes `{ "uid": ..., "id": ... }`.
+/// The inner type `T` must implement the [`deserr::Deserr<E>`] trait. The inner type `E` must
+/// implement the [`DeserializeError`] trait.
+///
+/// The numbers and booleans are parsed from their string representation.
+#[derive(Debug)]
+pub struct AxumPath<T, E>(pub T, PhantomData<E>);
+
+impl<T, E> AxumPath<T, E> {
+    pub fn new(data: T) -> Self {
+        AxumPath(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+pub enum AxumPathRejection<E: DeserializeError> {
+    DeserrError(E),
+    RawPathParamsRejection(RawPathParamsRejection),
+}
+
+impl<E: DeserializeError + std::fmt::Display> std::fmt::Display for AxumPathRejection<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AxumPathRejection::DeserrError(e) => e.fmt(f),
+            AxumPathRejection::RawPathParamsRejection(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<T, E, S> FromRequestParts<S> for AxumPath<T, E>
+where
+    E: DeserializeError + IntoResponse + 'static,
+    T: Deserr<E>,
+    S: Send + Sync,
+{
+    type Rejection = AxumPathRejection<E>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let params = RawPathParams::from_request_parts(parts, state).await?;
+        let segments = params
+            .iter()
+            .map(|(key, value)| (key.to_owned(), QueryValue::String(value.to_owned())));
+        let value = QueryValue::Map(segments.collect());
+        let data = deserr::deserialize::<_, _, _>(value)?;
+        Ok(AxumPath(data, PhantomData))
+    }
+}
+
+impl<E: DeserializeError> From<E> for AxumPathRejection<E> {
+    fn from(value: E) -> Self {
+        AxumPathRejection::DeserrError(value)
+    }
+}
+
+impl<E: DeserializeError> From<RawPathParamsRejection> for AxumPathRejection<E> {
+    fn from(value: RawPathParamsRejection) -> Self {
+        AxumPathRejection::RawPathParamsRejection(value)
+    }
+}
+
+impl<E: DeserializeError + IntoResponse> IntoResponse for AxumPathRejection<E> {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            AxumPathRejection::DeserrError(e) => e.into_response(),
+            AxumPathRejection::RawPathParamsRejection(e) => e.into_response(),
+        }
+    }
+}
+
+impl IntoResponse for PathError {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
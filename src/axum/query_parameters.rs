@@ -0,0 +1,97 @@
+use std::marker::PhantomData;
+
+use crate::errors::QueryParamError;
+use crate::query_string::{QueryConfig, QueryValue};
+use crate::{DeserializeError, Deserr};
+use axum::extract::FromRequestParts;
+use axum::response::IntoResponse;
+use http::request::Parts;
+use http::StatusCode;
+
+/// Extractor for typed data from the query parameters of a request
+/// deserialised by deserr.
+///
+/// ## Extractor
+/// To extract typed data from the query string, the inner type `T` must implement the
+/// [`deserr::Deserr<E>`] trait. The inner type `E` must implement the
+/// [`DeserializeError`] trait.
+///
+/// The numbers and booleans are parsed from their string representation.
+/// The way the query string is parsed can be configured by adding a [`QueryConfig`] to the
+/// request extensions, for example with the [`Extension`](axum::Extension) layer.
+#[derive(Debug)]
+pub struct AxumQuery<T, E>(pub T, PhantomData<E>);
+
+impl<T, E> AxumQuery<T, E> {
+    pub fn new(data: T) -> Self {
+        AxumQuery(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+pub enum AxumQueryRejection<E: DeserializeError> {
+    DeserrError(E),
+    InvalidQueryString(serde_urlencoded::de::Error),
+}
+
+impl<E: DeserializeError + std::fmt::Display> std::fmt::Display for AxumQueryRejection<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AxumQueryRejection::DeserrError(e) => e.fmt(f),
+            AxumQueryRejection::InvalidQueryString(e) => {
+                write!(f, "Failed to deserialize query string: {e}")
+            }
+        }
+    }
+}
+
+impl<T, E, S> FromRequestParts<S> for AxumQuery<T, E>
+where
+    E: DeserializeError + IntoResponse + 'static,
+    T: Deserr<E>,
+    S: Send + Sync,
+{
+    type Rejection = AxumQueryRejection<E>;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let config = parts
+            .extensions
+            .get::<QueryConfig>()
+            .copied()
+            .unwrap_or_default();
+        let query = parts.uri.query().unwrap_or_default();
+        let value = QueryValue::from_query_with_config(query, config)
+            .map_err(AxumQueryRejection::InvalidQueryString)?;
+        let data = deserr::deserialize::<_, _, _>(value)?;
+        Ok(AxumQuery(data, PhantomData))
+    }
+}
+
+impl<E: DeserializeError> From<E> for AxumQueryRejection<E> {
+    fn from(value: E) -> Self {
+        AxumQueryRejection::DeserrError(value)
+    }
+}
+
+impl<E: DeserializeError + IntoResponse> IntoResponse for AxumQueryRejection<E> {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            AxumQueryRejection::DeserrError(e) => e.into_response(),
+            AxumQueryRejection::InvalidQueryString(e) => (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to deserialize query string: {e}"),
+            )
+                .into_response(),
+        }
+    }
+}
+
+impl IntoResponse for QueryParamError {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
@@ -1,12 +1,16 @@
 use std::marker::PhantomData;
+use std::sync::Arc;
 
-use crate::errors::JsonError;
-use crate::{DeserializeError, Deserr};
-use axum::extract::rejection::JsonRejection;
+use super::is_length_limit_error;
+use crate::errors::{JsonBodyError, JsonError};
+use crate::serde_json::{is_json_content_type, syntax_error};
+use crate::{take_cf_content, DeserializeError, Deserr, MergeWithError, ValuePointerRef};
+use axum::body::Body;
 use axum::extract::FromRequest;
 use axum::response::IntoResponse;
-use axum::Json;
-use http::StatusCode;
+use axum::RequestExt;
+use http::{header, StatusCode};
+use http_body_util::{BodyExt, Limited};
 
 /// Extractor for typed data from Json request payloads
 /// deserialised by deserr.
@@ -14,7 +18,13 @@ use http::StatusCode;
 /// ## Extractor
 /// To extract typed data from a request body, the inner type `T` must implement the
 /// [`deserr::Deserr<E>`] trait. The inner type `E` must implement the
-/// [`DeserializeError`] trait.
+/// [`DeserializeError`] + [`MergeWithError<JsonBodyError>`] traits.
+///
+/// The request is rejected with an `E` if its payload is larger than the limit, if its
+/// content type is not accepted, or if it is not a valid Json, see
+/// [`ErrorKind::Syntax`](crate::ErrorKind::Syntax). The limit and the accepted content
+/// types can be configured by adding an [`AxumJsonConfig`] to the request extensions, e.g.
+/// with the [`Extension`](axum::Extension) layer.
 #[derive(Debug)]
 pub struct AxumJson<T, E>(pub T, PhantomData<E>);
 
@@ -28,31 +38,146 @@ impl<T, E> AxumJson<T, E> {
     }
 }
 
+type ContentTypePredicate = dyn Fn(&str) -> bool + Send + Sync;
+
+/// Configuration of the [`AxumJson`] extractor.
+#[derive(Clone)]
+pub struct AxumJsonConfig {
+    limit: Option<usize>,
+    content_type: Option<Arc<ContentTypePredicate>>,
+    content_type_required: bool,
+}
+
+impl AxumJsonConfig {
+    /// Set the maximum accepted payload size. By default the limit is the one of the
+    /// [`DefaultBodyLimit`](axum::extract::DefaultBodyLimit) layer, 2MB by default.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set a predicate accepting more content types than `application/json` and the
+    /// `+json` suffixes. It is given the value of the `Content-Type` header.
+    pub fn content_type<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.content_type = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Set whether the content type of the requests is checked. By default it is.
+    pub fn content_type_required(mut self, content_type_required: bool) -> Self {
+        self.content_type_required = content_type_required;
+        self
+    }
+
+    fn accepts(&self, content_type: &str) -> bool {
+        is_json_content_type(content_type)
+            || self
+                .content_type
+                .as_ref()
+                .is_some_and(|predicate| predicate(content_type))
+    }
+}
+
+impl Default for AxumJsonConfig {
+    fn default() -> Self {
+        AxumJsonConfig {
+            limit: None,
+            content_type: None,
+            content_type_required: true,
+        }
+    }
+}
+
+impl std::fmt::Debug for AxumJsonConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AxumJsonConfig")
+            .field("limit", &self.limit)
+            .field("content_type", &self.content_type.as_ref().map(|_| ".."))
+            .field("content_type_required", &self.content_type_required)
+            .finish()
+    }
+}
+
+/// The rejection of the [`AxumJson`] extractor.
+///
+/// Everything related to the payload itself is reported as a `DeserrError`, or as a
+/// `PayloadError` along with the status code of its response when the payload is too large
+/// or its content type is not accepted. A `BodyError` only happens when the body couldn't
+/// be read.
 #[derive(Debug)]
 pub enum AxumJsonRejection<E: DeserializeError> {
     DeserrError(E),
-    JsonRejection(JsonRejection),
+    PayloadError { error: E, status: StatusCode },
+    BodyError(axum::Error),
 }
 
 impl<E: DeserializeError + std::fmt::Display> std::fmt::Display for AxumJsonRejection<E> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             AxumJsonRejection::DeserrError(e) => e.fmt(f),
-            AxumJsonRejection::JsonRejection(e) => e.fmt(f),
+            AxumJsonRejection::PayloadError { error, .. } => error.fmt(f),
+            AxumJsonRejection::BodyError(e) => {
+                write!(f, "Failed to buffer the request body: {e}")
+            }
         }
     }
 }
 
 impl<T, E, S> FromRequest<S> for AxumJson<T, E>
 where
-    E: DeserializeError + IntoResponse + 'static,
+    E: DeserializeError + MergeWithError<JsonBodyError> + IntoResponse + 'static,
     T: Deserr<E>,
     S: Send + Sync,
 {
     type Rejection = AxumJsonRejection<E>;
 
-    async fn from_request(req: axum::extract::Request, state: &S) -> Result<Self, Self::Rejection> {
-        let Json(value) = Json::<serde_json::Value>::from_request(req, state).await?;
+    async fn from_request(
+        req: axum::extract::Request,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let config = req
+            .extensions()
+            .get::<AxumJsonConfig>()
+            .cloned()
+            .unwrap_or_default();
+        let body_error = |error: JsonBodyError| AxumJsonRejection::PayloadError {
+            status: StatusCode::from_u16(error.status_code()).unwrap(),
+            error: take_cf_content(E::merge(None, error, ValuePointerRef::Origin)),
+        };
+
+        if config.content_type_required {
+            match req.headers().get(header::CONTENT_TYPE) {
+                None => return Err(body_error(JsonBodyError::MissingContentType)),
+                Some(content_type) => {
+                    let content_type = String::from_utf8_lossy(content_type.as_bytes());
+                    if !config.accepts(&content_type) {
+                        let error = JsonBodyError::InvalidContentType(content_type.into_owned());
+                        return Err(body_error(error));
+                    }
+                }
+            }
+        }
+
+        let body = match config.limit {
+            Some(limit) => Body::new(Limited::new(req.into_body(), limit)),
+            None => req.with_limited_body().into_body(),
+        };
+        let bytes = match body.collect().await {
+            Ok(collected) => collected.to_bytes(),
+            Err(e) if is_length_limit_error(&e) => {
+                let error = JsonBodyError::PayloadTooLarge {
+                    limit: config.limit,
+                };
+                return Err(body_error(error));
+            }
+            Err(e) => return Err(AxumJsonRejection::BodyError(e)),
+        };
+
+        let value =
+            serde_json::from_slice::<serde_json::Value>(&bytes).map_err(syntax_error::<E>)?;
         let data = deserr::deserialize::<_, _, _>(value)?;
         Ok(AxumJson(data, PhantomData))
     }
@@ -64,17 +189,20 @@ impl<E: DeserializeError> From<E> for AxumJsonRejection<E> {
     }
 }
 
-impl<E: DeserializeError> From<JsonRejection> for AxumJsonRejection<E> {
-    fn from(value: JsonRejection) -> Self {
-        AxumJsonRejection::JsonRejection(value)
-    }
-}
-
 impl<E: DeserializeError + IntoResponse> IntoResponse for AxumJsonRejection<E> {
     fn into_response(self) -> axum::response::Response {
         match self {
             AxumJsonRejection::DeserrError(e) => e.into_response(),
-            AxumJsonRejection::JsonRejection(e) => e.into_response(),
+            AxumJsonRejection::PayloadError { error, status } => {
+                let mut response = error.into_response();
+                *response.status_mut() = status;
+                response
+            }
+            AxumJsonRejection::BodyError(e) => (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to buffer the request body: {e}"),
+            )
+                .into_response(),
         }
     }
 }
@@ -0,0 +1,162 @@
+//! A CBOR backend for deserr, deserializing the [`ciborium::Value`]s.
+//!
+//! The tags are ignored, and the tagged values are deserialized as if they weren't tagged,
+//! except for the bignums (tags 2 and 3) which are integers. The integers which don't fit in
+//! 64 bits are deserialized as a [`Value::Number`].
+//! The keys of the maps are their text or, for integer keys, their decimal representation.
+//! The entries of any other key are ignored.
+//!
+//! ```
+//! use ciborium::Value;
+//! use deserr::{errors::JsonError, Deserr};
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Request {
+//!     id: u64,
+//!     payload: Vec<u8>,
+//! }
+//!
+//! let value = Value::Map(vec![
+//!     (Value::Text("id".into()), Value::Integer(42.into())),
+//!     (Value::Text("payload".into()), Value::Bytes(vec![1, 2, 3])),
+//! ]);
+//! let request = deserr::deserialize::<Request, _, JsonError>(value).unwrap();
+//! assert_eq!(request, Request { id: 42, payload: vec![1, 2, 3] });
+//! ```
+
+use ciborium::Value as CValue;
+
+use crate::value::decimal_text;
+use crate::{IntoValue, Map, Value, ValueKind};
+
+type Entries = Vec<(CValue, CValue)>;
+
+/// The CBOR tags of the positive and negative bignums.
+const POSITIVE_BIGNUM: u64 = 2;
+const NEGATIVE_BIGNUM: u64 = 3;
+
+fn integer_value<V: IntoValue>(n: i128) -> Value<V> {
+    if let Ok(n) = u64::try_from(n) {
+        Value::Integer(n)
+    } else if let Ok(n) = i64::try_from(n) {
+        Value::NegativeInteger(n)
+    } else {
+        // CBOR integers go down to -2^64, below the range of an `i64`
+        Value::Number(n.to_string())
+    }
+}
+
+/// Return the integer encoded by a bignum, i.e. its big-endian bytes, or `-1 - n` for the
+/// negative bignums.
+fn bignum_value<V: IntoValue>(bytes: &[u8], negative: bool) -> Value<V> {
+    let bytes = &bytes[bytes.iter().take_while(|b| **b == 0).count()..];
+    if bytes.len() <= 16 {
+        let n = bytes.iter().fold(0u128, |n, b| n << 8 | u128::from(*b));
+        return match (negative, i128::try_from(n)) {
+            (false, Ok(n)) => integer_value(n),
+            (true, Ok(n)) => integer_value(-1 - n),
+            (false, Err(_)) => Value::Number(n.to_string()),
+            (true, Err(_)) => Value::Number(format!("-{}", decimal_text(&add_one(bytes)))),
+        };
+    }
+    if negative {
+        Value::Number(format!("-{}", decimal_text(&add_one(bytes))))
+    } else {
+        Value::Number(decimal_text(bytes))
+    }
+}
+
+/// Return the given big-endian integer plus one.
+fn add_one(bytes: &[u8]) -> Vec<u8> {
+    let mut bytes = bytes.to_vec();
+    for byte in bytes.iter_mut().rev() {
+        let (sum, overflow) = byte.overflowing_add(1);
+        *byte = sum;
+        if !overflow {
+            return bytes;
+        }
+    }
+    bytes.insert(0, 1);
+    bytes
+}
+
+/// Return the key of a map entry, or `None` if it can't be used as a key.
+fn map_key(key: &CValue) -> Option<String> {
+    match key {
+        CValue::Text(key) => Some(key.clone()),
+        CValue::Integer(key) => Some(i128::from(*key).to_string()),
+        CValue::Tag(_, key) => map_key(key),
+        _ => None,
+    }
+}
+
+impl Map for Entries {
+    type Value = CValue;
+    type Iter = std::iter::FilterMap<
+        std::vec::IntoIter<(CValue, CValue)>,
+        fn((CValue, CValue)) -> Option<(String, CValue)>,
+    >;
+
+    fn len(&self) -> usize {
+        self.iter()
+            .filter(|(key, _)| map_key(key).is_some())
+            .count()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let index = self
+            .iter()
+            .position(|(k, _)| map_key(k).as_deref() == Some(key))?;
+        Some(Vec::remove(self, index).1)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        <Self as IntoIterator>::into_iter(self)
+            .filter_map(|(key, value)| Some((map_key(&key)?, value)))
+    }
+}
+
+impl IntoValue for CValue {
+    type Sequence = Vec<CValue>;
+    type Map = Entries;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            CValue::Null => ValueKind::Null,
+            CValue::Bool(_) => ValueKind::Boolean,
+            CValue::Integer(n) => integer_value::<CValue>(i128::from(*n)).kind(),
+            CValue::Float(_) => ValueKind::Float,
+            CValue::Text(_) => ValueKind::String,
+            CValue::Bytes(_) => ValueKind::Bytes,
+            CValue::Tag(tag @ (POSITIVE_BIGNUM | NEGATIVE_BIGNUM), value) => match &**value {
+                CValue::Bytes(bytes) => {
+                    bignum_value::<CValue>(bytes, *tag == NEGATIVE_BIGNUM).kind()
+                }
+                value => value.kind(),
+            },
+            CValue::Tag(_, value) => value.kind(),
+            CValue::Array(_) => ValueKind::Sequence,
+            CValue::Map(_) => ValueKind::Map,
+            _ => unreachable!("unknown CBOR value"),
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            CValue::Null => Value::Null,
+            CValue::Bool(b) => Value::Boolean(b),
+            CValue::Integer(n) => integer_value(i128::from(n)),
+            CValue::Float(x) => Value::Float(x),
+            CValue::Text(s) => Value::String(s),
+            CValue::Bytes(bytes) => Value::Bytes(bytes),
+            CValue::Tag(tag @ (POSITIVE_BIGNUM | NEGATIVE_BIGNUM), value) => match *value {
+                CValue::Bytes(bytes) => bignum_value(&bytes, tag == NEGATIVE_BIGNUM),
+                value => value.into_value(),
+            },
+            CValue::Tag(_, value) => value.into_value(),
+            CValue::Array(seq) => Value::Sequence(seq),
+            CValue::Map(map) => Value::Map(map),
+            _ => unreachable!("unknown CBOR value"),
+        }
+    }
+}
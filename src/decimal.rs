@@ -0,0 +1,88 @@
+use std::convert::Infallible;
+use std::fmt::Display;
+
+use crate::value::is_decimal_text;
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Value, ValueKind,
+    ValuePointerRef,
+};
+
+/// A decimal number keeping its exact text, e.g. `12.50` or `340282366920938463463374607431768211456`.
+///
+/// It can be deserialized from any number, and from the strings containing a number for the
+/// formats where every value is a string. The exact text of the decimal numbers is only kept
+/// by the formats providing it, see [`Value::Number`], the floats being written in their
+/// shortest representation otherwise. It can then be parsed into the decimal or big integer
+/// type of another crate.
+///
+/// ```
+/// use deserr::{errors::QueryParamError, query_string::QueryValue, Decimal, Deserr};
+///
+/// #[derive(Deserr, Debug)]
+/// struct Payment {
+///     amount: Decimal,
+/// }
+///
+/// let value = QueryValue::from_query("amount=12.50").unwrap();
+/// let payment = deserr::deserialize::<Payment, _, QueryParamError>(value).unwrap();
+/// assert_eq!(payment.amount.as_str(), "12.50");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Decimal(String);
+
+impl Decimal {
+    /// Return the given text as a decimal if it is a number as written in JSON, e.g. `-1.5e3`.
+    pub fn new(text: impl Into<String>) -> Option<Self> {
+        let text = text.into();
+        is_decimal_text(&text).then_some(Decimal(text))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for Decimal {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            // the strings are not coerced into a float, which would lose their exact text
+            Value::String(s) if V::COERCE_STRINGS && is_decimal_text(&s) => Ok(Decimal(s)),
+            Value::Integer(x) => Ok(Decimal(x.to_string())),
+            Value::NegativeInteger(x) => Ok(Decimal(x.to_string())),
+            Value::Float(x) if x.is_finite() => Ok(Decimal(x.to_string())),
+            Value::Float(x) => Err(take_cf_content(E::error::<Infallible>(
+                None,
+                ErrorKind::Unexpected {
+                    msg: format!("value: `{x}` is not a decimal number"),
+                },
+                location,
+            ))),
+            Value::Number(x) => Ok(Decimal(x)),
+            v => Err(take_cf_content(E::error(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: v,
+                    accepted: &[
+                        ValueKind::Float,
+                        ValueKind::Integer,
+                        ValueKind::NegativeInteger,
+                    ],
+                },
+                location,
+            ))),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! An environment variables backend for deserr.
+//!
+//! The variables starting with a prefix are a map from their name, without the prefix, to
+//! their value. The names are split on a nesting separator and lowercased, so that
+//! `APP__DB__PORT` is the key `port` of the map `db` with the `APP` prefix and the default
+//! `__` separator.
+//!
+//! Since the keys are lowercased, the names of the fields must be lowercase to be set from
+//! an environment variable: a field renamed with uppercase letters, e.g. by
+//! `#[deserr(rename_all = camelCase)]`, is never found and its missing field error says so.
+//! Use the default snake case names, or `#[deserr(rename_all = lowercase)]`, instead.
+//!
+//! Every value is a string, thus [`EnvValue`] coerces the strings into numbers and
+//! booleans when needed, see [`IntoValue::COERCE_STRINGS`]. A sequence is given as a
+//! comma-separated list of values.
+//!
+//! ```
+//! use deserr::{env::EnvConfig, Deserr};
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Config {
+//!     db: Db,
+//!     #[deserr(default)]
+//!     features: Vec<String>,
+//! }
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Db {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! let config = EnvConfig::new("APP");
+//! let vars = [
+//!     ("APP__DB__HOST", "localhost"),
+//!     ("APP__DB__PORT", "5432"),
+//!     ("APP__FEATURES", "search,vectors"),
+//!     ("HOME", "/home/doggo"),
+//! ];
+//! let vars = vars.map(|(key, value)| (key.to_owned(), value.to_owned()));
+//!
+//! let parsed = deserr::env::deserialize_vars::<Config>(vars.clone(), &config).unwrap();
+//! assert_eq!(parsed.db, Db { host: String::from("localhost"), port: 5432 });
+//! assert_eq!(parsed.features, ["search", "vectors"]);
+//!
+//! let mut vars = vars.to_vec();
+//! vars[1].1 = String::from("five");
+//! let error = deserr::env::deserialize_vars::<Config>(vars, &config).unwrap_err();
+//! assert_eq!(
+//!     error.to_string(),
+//!     "Invalid value type for environment variable `APP__DB__PORT`: expected a positive integer, but found `five`"
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::errors::EnvError;
+use crate::{Deserr, IntoValue, Map, Value, ValueKind};
+
+/// Deserialize the environment variables of the process starting with the prefix of the
+/// given [`EnvConfig`].
+///
+/// The variables whose name or value isn't valid unicode are ignored.
+pub fn deserialize<T: Deserr<EnvError>>(config: &EnvConfig) -> Result<T, EnvError> {
+    let vars = std::env::vars_os()
+        .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)));
+    deserialize_vars(vars, config)
+}
+
+/// Deserialize the given environment variables starting with the prefix of the given
+/// [`EnvConfig`]. The errors name the variables with this prefix.
+pub fn deserialize_vars<T: Deserr<EnvError>>(
+    vars: impl IntoIterator<Item = (String, String)>,
+    config: &EnvConfig,
+) -> Result<T, EnvError> {
+    let value = EnvValue::from_vars(vars, config);
+    crate::deserialize::<T, _, EnvError>(value).map_err(|error| error.with_config(config))
+}
+
+/// How the names of the environment variables are mapped to the keys given to deserr.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvConfig {
+    pub(crate) prefix: String,
+    pub(crate) separator: String,
+}
+
+impl EnvConfig {
+    /// Only consider the variables starting with the given prefix, followed by the separator.
+    /// With an empty prefix, every variable is considered.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        EnvConfig {
+            prefix: prefix.into(),
+            separator: String::from("__"),
+        }
+    }
+
+    /// Set the separator between the prefix and the keys of the nested maps.
+    /// By default it is `__`.
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Return the name of the environment variable corresponding to the given keys.
+    pub fn variable_name<'a>(&self, keys: impl IntoIterator<Item = &'a str>) -> String {
+        let mut name = self.prefix.clone();
+        for key in keys {
+            if !name.is_empty() {
+                name.push_str(&self.separator);
+            }
+            name.push_str(&key.to_uppercase());
+        }
+        name
+    }
+
+    /// Return the lowercase keys of the given variable, or `None` if it doesn't start with
+    /// the prefix.
+    fn keys(&self, name: &str) -> Option<Vec<String>> {
+        let name = if self.prefix.is_empty() {
+            name
+        } else {
+            name.strip_prefix(self.prefix.as_str())?
+                .strip_prefix(self.separator.as_str())?
+        };
+        if name.is_empty() || self.separator.is_empty() {
+            return None;
+        }
+        Some(
+            name.split(self.separator.as_str())
+                .map(str::to_lowercase)
+                .collect(),
+        )
+    }
+}
+
+impl Default for EnvConfig {
+    fn default() -> Self {
+        EnvConfig::new("")
+    }
+}
+
+/// A value parsed from environment variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvValue {
+    String(String),
+    Sequence(Vec<EnvValue>),
+    Map(EnvMap),
+}
+
+impl EnvValue {
+    /// Build a map of keys from the given variables, following the given [`EnvConfig`].
+    ///
+    /// If a variable is both a value and a map, e.g. `APP__DB` and `APP__DB__PORT`, the map
+    /// is kept.
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>, config: &EnvConfig) -> Self {
+        let mut map = EnvMap::default();
+        for (name, value) in vars {
+            let Some(keys) = config.keys(&name) else {
+                continue;
+            };
+            map.insert_at(keys, EnvValue::String(value));
+        }
+        EnvValue::Map(map)
+    }
+}
+
+/// The keys of environment variables, sorted by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnvMap(BTreeMap<String, EnvValue>);
+
+impl EnvMap {
+    /// Insert the value at the path described by the keys.
+    fn insert_at(&mut self, mut keys: Vec<String>, value: EnvValue) {
+        let last = keys.pop().expect("a variable has at least one key");
+        let mut map = self;
+        for key in keys {
+            let entry = map
+                .0
+                .entry(key)
+                .or_insert_with(|| EnvValue::Map(EnvMap::default()));
+            if !matches!(entry, EnvValue::Map(_)) {
+                *entry = EnvValue::Map(EnvMap::default());
+            }
+            let EnvValue::Map(inner) = entry else {
+                unreachable!()
+            };
+            map = inner;
+        }
+        match map.0.get(&last) {
+            Some(EnvValue::Map(_)) => (),
+            _ => {
+                map.0.insert(last, value);
+            }
+        }
+    }
+}
+
+impl Map for EnvMap {
+    type Value = EnvValue;
+    type Iter = std::collections::btree_map::IntoIter<String, EnvValue>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        self.0.remove(key)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        self.0.into_iter()
+    }
+}
+
+impl IntoValue for EnvValue {
+    type Sequence = Vec<EnvValue>;
+    type Map = EnvMap;
+
+    const COERCE_STRINGS: bool = true;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            EnvValue::String(_) => ValueKind::String,
+            EnvValue::Sequence(_) => ValueKind::Sequence,
+            EnvValue::Map(_) => ValueKind::Map,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            EnvValue::String(s) => Value::String(s),
+            EnvValue::Sequence(seq) => Value::Sequence(seq),
+            EnvValue::Map(map) => Value::Map(map),
+        }
+    }
+
+    fn string_into_sequence(s: String) -> Result<Vec<EnvValue>, String> {
+        if s.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(s.split(',')
+            .map(|s| EnvValue::String(s.to_owned()))
+            .collect())
+    }
+}
@@ -0,0 +1,212 @@
+//! This module implements the error messages of command-line arguments deserialization
+//! errors, see the [`args`](crate::args) module.
+//!
+//! The locations are rendered as the options to give on the command line, e.g.
+//! "Missing option `--db.port`".
+
+use std::{convert::Infallible, fmt::Display, ops::ControlFlow};
+
+use crate::{DeserializeError, MergeWithError, Sequence};
+use deserr::{ErrorKind, IntoValue, ValuePointerRef};
+
+use super::helpers::{bounds_description, did_you_mean};
+use super::json::value_kinds_description_json;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgsError(String);
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ArgsError {
+    /// An argument that can't be parsed as an option, before deserializing anything.
+    pub(crate) fn invalid_argument(arg: &str, msg: &str) -> Self {
+        ArgsError(format!("Invalid argument `{arg}`: {msg}"))
+    }
+}
+
+/// Return the option corresponding to the given location, e.g. `--db.port` or `--tag[1]`.
+fn option_name(location: ValuePointerRef) -> String {
+    fn rec(location: ValuePointerRef) -> String {
+        match location {
+            ValuePointerRef::Origin => String::new(),
+            ValuePointerRef::Key { key, prev } => {
+                if matches!(prev, ValuePointerRef::Origin) {
+                    key.to_owned()
+                } else {
+                    rec(*prev) + "." + key
+                }
+            }
+            ValuePointerRef::Index { index, prev } => format!("{}[{index}]", rec(*prev)),
+        }
+    }
+    format!("--{}", rec(location))
+}
+
+/// Return the options of the given fields, inside the given location.
+fn option_names(location: ValuePointerRef, fields: &[&str]) -> Vec<String> {
+    fields
+        .iter()
+        .map(|field| option_name(location.push_key(field)))
+        .collect()
+}
+
+/// Compute a description of a list of options.
+/// e.g. `` `--a`, `--b` and `--c` `` when `last_separator` is `and`.
+fn options_description(options: &[String], last_separator: &str) -> String {
+    let mut options = options
+        .iter()
+        .map(|option| format!("`{option}`"))
+        .collect::<Vec<_>>();
+    match options.pop() {
+        None => String::new(),
+        Some(last) if options.is_empty() => last,
+        Some(last) => format!("{} {last_separator} {last}", options.join(", ")),
+    }
+}
+
+impl DeserializeError for ArgsError {
+    fn error<V: IntoValue>(
+        _self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let option = option_name(location);
+        // the errors without location are about the arguments as a whole
+        let invalid = |message: String| {
+            if matches!(location, ValuePointerRef::Origin) {
+                format!("Invalid arguments: {message}")
+            } else {
+                format!("Invalid value for option `{option}`: {message}")
+            }
+        };
+
+        let message = match error {
+            ErrorKind::IncorrectValueKind { actual, accepted } => {
+                let expected = value_kinds_description_json(accepted)
+                    .replace("an array", "multiple values")
+                    .replace("an object", "multiple options");
+                let received = match actual {
+                    deserr::Value::String(x) => format!("`{x}`"),
+                    deserr::Value::Sequence(_) => "multiple values".to_owned(),
+                    deserr::Value::Map(_) => "multiple options".to_owned(),
+                    _ => actual.kind().to_string(),
+                };
+                format!(
+                    "Invalid value type for option `{option}`: expected {expected}, but found {received}"
+                )
+            }
+            ErrorKind::MissingField { field } => {
+                format!("Missing option `{}`", option_name(location.push_key(field)))
+            }
+            ErrorKind::UnknownKey { key, accepted } => {
+                let options = option_names(location, accepted);
+                let accepted = options.iter().map(String::as_str).collect::<Vec<_>>();
+                let key = option_name(location.push_key(key));
+                format!(
+                    "Unknown option `{key}`: {}expected one of {}",
+                    did_you_mean(&key, &accepted),
+                    accepted
+                        .iter()
+                        .map(|accepted| format!("`{accepted}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+            ErrorKind::UnknownValue { value, accepted } => format!(
+                "Unknown value `{value}` for option `{option}`: {}expected one of {}",
+                did_you_mean(value, accepted),
+                accepted
+                    .iter()
+                    .map(|accepted| format!("`{accepted}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ErrorKind::BadSequenceLen { actual, expected } => invalid(format!(
+                "expected {expected} values, but found {}",
+                actual.len()
+            )),
+            ErrorKind::Unexpected { msg } => invalid(msg),
+            ErrorKind::OutOfRange { actual, min, max } => invalid(format!(
+                "expected a value {}, but found {actual}",
+                bounds_description(min, max)
+            )),
+            ErrorKind::BadLength { actual, min, max } => invalid(format!(
+                "expected a length {}, but found {actual}",
+                bounds_description(
+                    min.as_ref().map(|min| min as &dyn Display),
+                    max.as_ref().map(|max| max as &dyn Display)
+                )
+            )),
+            ErrorKind::EmptyValue => invalid("expected a non-empty value".to_owned()),
+            ErrorKind::PatternMismatch { value, pattern } => invalid(format!(
+                "expected a value matching the pattern `{pattern}`, but found `{value}`"
+            )),
+            ErrorKind::MissingRequiredFields { field, missing } => {
+                let missing_description =
+                    options_description(&option_names(location, missing), "and");
+                let field = option_name(location.push_key(field));
+                if missing.len() == 1 {
+                    format!("Missing option {missing_description}: it is required when `{field}` is specified")
+                } else {
+                    format!("Missing options {missing_description}: they are required when `{field}` is specified")
+                }
+            }
+            ErrorKind::ConflictingFields { field, conflicting } => {
+                let conflicting_description =
+                    options_description(&option_names(location, conflicting), "and");
+                let field = option_name(location.push_key(field));
+                if conflicting.len() == 1 {
+                    format!("Conflicting option {conflicting_description}: it cannot be specified together with `{field}`")
+                } else {
+                    format!("Conflicting options {conflicting_description}: they cannot be specified together with `{field}`")
+                }
+            }
+            ErrorKind::NotExactlyOneOf { fields, present } => {
+                let expected = options_description(&option_names(location, fields), "or");
+                if present.is_empty() {
+                    format!("Missing option: expected exactly one of {expected}")
+                } else {
+                    format!(
+                        "Conflicting options {}: expected exactly one of {expected}",
+                        options_description(&option_names(location, present), "and")
+                    )
+                }
+            }
+            ErrorKind::Syntax { msg, line, column } => invalid(format!(
+                "invalid syntax at line {line}, column {column}: {msg}"
+            )),
+        };
+
+        ControlFlow::Break(ArgsError(message))
+    }
+}
+
+impl MergeWithError<ArgsError> for ArgsError {
+    fn merge(
+        _self_: Option<Self>,
+        other: ArgsError,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(other)
+    }
+}
+
+impl<E: std::error::Error> MergeWithError<E> for ArgsError {
+    fn merge(
+        self_: Option<Self>,
+        other: E,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ArgsError::error::<Infallible>(
+            self_,
+            ErrorKind::Unexpected {
+                msg: other.to_string(),
+            },
+            merge_location,
+        )
+    }
+}
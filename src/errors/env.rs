@@ -0,0 +1,280 @@
+//! This module implements the error messages of environment variables deserialization errors.
+//!
+//! The locations are rendered as the names of the environment variables, e.g.
+//! "Missing environment variable `APP__DB__PORT`". Since the prefix of the variables isn't
+//! known while deserializing, the names are built when the error is displayed, see
+//! [`EnvError::with_config`].
+
+use std::{convert::Infallible, fmt::Display, ops::ControlFlow};
+
+use crate::env::EnvConfig;
+use crate::{DeserializeError, MergeWithError, Sequence};
+use deserr::{ErrorKind, IntoValue, ValuePointerRef};
+
+use super::helpers::{bounds_description, closest_match};
+use super::json::value_kinds_description_json;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvError {
+    parts: Vec<Part>,
+    config: EnvConfig,
+}
+
+/// A part of the message of an [`EnvError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    /// An environment variable, given by its keys and the indexes of its values.
+    Variable(Vec<String>, Vec<usize>),
+    /// A list of environment variables, given by the keys of their parent and their own key.
+    Variables(Vec<String>, Vec<String>, &'static str),
+}
+
+impl EnvError {
+    /// Set the prefix and separator used to name the environment variables.
+    pub fn with_config(mut self, config: &EnvConfig) -> Self {
+        self.config = config.clone();
+        self
+    }
+
+    fn variable_name(&self, keys: &[String], indexes: &[usize]) -> String {
+        let mut name = self.config.variable_name(keys.iter().map(String::as_str));
+        for index in indexes {
+            name.push_str(&format!("[{index}]"));
+        }
+        name
+    }
+}
+
+impl Display for EnvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => write!(f, "{text}")?,
+                Part::Variable(keys, indexes) => {
+                    write!(f, "`{}`", self.variable_name(keys, indexes))?
+                }
+                Part::Variables(parent, keys, last_separator) => {
+                    let mut names = keys
+                        .iter()
+                        .map(|key| {
+                            let mut keys = parent.clone();
+                            keys.push(key.clone());
+                            format!("`{}`", self.variable_name(&keys, &[]))
+                        })
+                        .collect::<Vec<_>>();
+                    if let Some(last) = names.pop() {
+                        if names.is_empty() {
+                            write!(f, "{last}")?;
+                        } else {
+                            write!(f, "{} {last_separator} {last}", names.join(", "))?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Split a location into the keys and the indexes leading to it.
+fn location_keys(location: ValuePointerRef) -> (Vec<String>, Vec<usize>) {
+    fn rec(location: ValuePointerRef, keys: &mut Vec<String>, indexes: &mut Vec<usize>) {
+        match location {
+            ValuePointerRef::Origin => (),
+            ValuePointerRef::Key { key, prev } => {
+                rec(*prev, keys, indexes);
+                keys.push(key.to_owned());
+            }
+            ValuePointerRef::Index { index, prev } => {
+                rec(*prev, keys, indexes);
+                indexes.push(index);
+            }
+        }
+    }
+    let (mut keys, mut indexes) = (Vec::new(), Vec::new());
+    rec(location, &mut keys, &mut indexes);
+    (keys, indexes)
+}
+
+impl DeserializeError for EnvError {
+    fn error<V: IntoValue>(
+        _self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let (keys, indexes) = location_keys(location);
+        let text = |text: &str| Part::Text(text.to_owned());
+        let variable = Part::Variable(keys.clone(), indexes);
+        let child = |key: &str| {
+            let mut keys = keys.clone();
+            keys.push(key.to_owned());
+            Part::Variable(keys, Vec::new())
+        };
+        let children = |fields: &[&str], last_separator| {
+            let fields = fields.iter().map(|field| field.to_string()).collect();
+            Part::Variables(keys.clone(), fields, last_separator)
+        };
+        // the errors without location are about the environment variables as a whole
+        let invalid = |message: String| {
+            if keys.is_empty() {
+                vec![Part::Text(format!(
+                    "Invalid environment variables: {message}"
+                ))]
+            } else {
+                vec![
+                    text("Invalid value for environment variable "),
+                    variable.clone(),
+                    Part::Text(format!(": {message}")),
+                ]
+            }
+        };
+
+        let parts = match error {
+            ErrorKind::IncorrectValueKind { actual, accepted } => {
+                let expected = value_kinds_description_json(accepted)
+                    .replace("an array", "multiple values")
+                    .replace("an object", "multiple environment variables");
+                let received = match actual {
+                    deserr::Value::String(x) => format!("`{x}`"),
+                    deserr::Value::Sequence(_) => "multiple values".to_owned(),
+                    deserr::Value::Map(_) => "multiple environment variables".to_owned(),
+                    _ => actual.kind().to_string(),
+                };
+                vec![
+                    text("Invalid value type for environment variable "),
+                    variable,
+                    Part::Text(format!(": expected {expected}, but found {received}")),
+                ]
+            }
+            ErrorKind::MissingField { field } => {
+                let mut parts = vec![text("Missing environment variable "), child(field)];
+                // the keys are lowercased, so such a field can never be found
+                if field.chars().any(char::is_uppercase) {
+                    parts.push(Part::Text(format!(
+                        ": the field `{field}` can't be set from an environment variable since its name isn't lowercase"
+                    )));
+                }
+                parts
+            }
+            ErrorKind::UnknownKey { key, accepted } => {
+                let mut parts = vec![text("Unknown environment variable "), child(key)];
+                if let Some(suggestion) = closest_match(key, accepted) {
+                    parts.extend([text(": did you mean "), child(suggestion), text("?")]);
+                }
+                parts
+            }
+            ErrorKind::UnknownValue { value, accepted } => {
+                let mut parts = vec![
+                    Part::Text(format!("Unknown value `{value}` for environment variable ")),
+                    variable,
+                    text(": "),
+                ];
+                if let Some(suggestion) = closest_match(value, accepted) {
+                    parts.push(Part::Text(format!("did you mean `{suggestion}`? ")));
+                }
+                let accepted = accepted
+                    .iter()
+                    .map(|accepted| format!("`{accepted}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                parts.push(Part::Text(format!("expected one of {accepted}")));
+                parts
+            }
+            ErrorKind::BadSequenceLen { actual, expected } => invalid(format!(
+                "expected {expected} comma-separated values, but found {}",
+                actual.len()
+            )),
+            ErrorKind::Unexpected { msg } => invalid(msg),
+            ErrorKind::OutOfRange { actual, min, max } => invalid(format!(
+                "expected a value {}, but found {actual}",
+                bounds_description(min, max)
+            )),
+            ErrorKind::BadLength { actual, min, max } => invalid(format!(
+                "expected a length {}, but found {actual}",
+                bounds_description(
+                    min.as_ref().map(|min| min as &dyn Display),
+                    max.as_ref().map(|max| max as &dyn Display)
+                )
+            )),
+            ErrorKind::EmptyValue => invalid("expected a non-empty value".to_owned()),
+            ErrorKind::PatternMismatch { value, pattern } => invalid(format!(
+                "expected a value matching the pattern `{pattern}`, but found `{value}`"
+            )),
+            ErrorKind::MissingRequiredFields { field, missing } => {
+                let noun = if missing.len() == 1 {
+                    "Missing environment variable "
+                } else {
+                    "Missing environment variables "
+                };
+                vec![
+                    text(noun),
+                    children(missing, "and"),
+                    text(": required when "),
+                    child(field),
+                    text(" is specified"),
+                ]
+            }
+            ErrorKind::ConflictingFields { field, conflicting } => {
+                let noun = if conflicting.len() == 1 {
+                    "Conflicting environment variable "
+                } else {
+                    "Conflicting environment variables "
+                };
+                vec![
+                    text(noun),
+                    children(conflicting, "and"),
+                    text(": cannot be specified together with "),
+                    child(field),
+                ]
+            }
+            ErrorKind::NotExactlyOneOf { fields, present } => {
+                let mut parts = if present.is_empty() {
+                    vec![text("Missing environment variable: ")]
+                } else {
+                    vec![
+                        text("Conflicting environment variables "),
+                        children(present, "and"),
+                        text(": "),
+                    ]
+                };
+                parts.extend([text("expected exactly one of "), children(fields, "or")]);
+                parts
+            }
+            ErrorKind::Syntax { msg, line, column } => invalid(format!(
+                "invalid syntax at line {line}, column {column}: {msg}"
+            )),
+        };
+
+        ControlFlow::Break(EnvError {
+            parts,
+            config: EnvConfig::default(),
+        })
+    }
+}
+
+impl MergeWithError<EnvError> for EnvError {
+    fn merge(
+        _self_: Option<Self>,
+        other: EnvError,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(other)
+    }
+}
+
+impl<E: std::error::Error> MergeWithError<E> for EnvError {
+    fn merge(
+        self_: Option<Self>,
+        other: E,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        EnvError::error::<Infallible>(
+            self_,
+            ErrorKind::Unexpected {
+                msg: other.to_string(),
+            },
+            merge_location,
+        )
+    }
+}
@@ -0,0 +1,111 @@
+//! This module implements the error messages of `application/x-www-form-urlencoded` form
+//! deserialization errors.
+//!
+//! The messages are the same as the ones of the query parameters, but they talk about
+//! fields instead of parameters.
+
+use crate::{DeserializeError, MergeWithError};
+use deserr::{ErrorKind, IntoValue, ValuePointerRef};
+use std::{convert::Infallible, fmt::Display, ops::ControlFlow};
+
+use super::query_params::error_message;
+
+#[derive(Debug, Clone)]
+pub struct FormError(String);
+
+impl Display for FormError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FormError {
+    fn new(msg: String) -> Self {
+        FormError(msg)
+    }
+}
+
+impl DeserializeError for FormError {
+    fn error<V: IntoValue>(
+        _self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let message = error_message(error, location, "field");
+
+        ControlFlow::Break(FormError::new(message))
+    }
+}
+
+impl MergeWithError<FormError> for FormError {
+    fn merge(
+        _self_: Option<Self>,
+        other: FormError,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(other)
+    }
+}
+
+impl<E: std::error::Error> MergeWithError<E> for FormError {
+    fn merge(
+        self_: Option<Self>,
+        other: E,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        FormError::error::<Infallible>(
+            self_,
+            ErrorKind::Unexpected {
+                msg: other.to_string(),
+            },
+            merge_location,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_string::{QueryConfig, QueryValue};
+
+    #[allow(dead_code)]
+    #[derive(deserr::Deserr, Debug)]
+    #[deserr(deny_unknown_fields)]
+    struct Signup {
+        name: String,
+        age: u8,
+        #[deserr(default)]
+        address: Option<Address>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(deserr::Deserr, Debug)]
+    #[deserr(deny_unknown_fields)]
+    struct Address {
+        city: String,
+    }
+
+    fn deserialize(form: &str) -> Result<Signup, FormError> {
+        let config = QueryConfig::new().brackets(true);
+        let value = QueryValue::from_query_with_config(form, config).unwrap();
+        deserr::deserialize::<Signup, _, FormError>(value)
+    }
+
+    #[test]
+    fn error_msg() {
+        let err = deserialize("name=doggo").unwrap_err();
+        insta::assert_snapshot!(err, @"Missing field `age`");
+
+        let err = deserialize("name=doggo&age=old").unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for field `age`: expected a positive integer, but found a string: `old`");
+
+        let err = deserialize("name=doggo&age=3&adress[city]=Paris").unwrap_err();
+        insta::assert_snapshot!(err, @"Unknown field `adress`: did you mean `address`? expected one of `name`, `age`, `address`");
+
+        let err = deserialize("name=doggo&age=3&address=Paris").unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for field `address`: expected multiple fields, but found a string: `Paris`");
+
+        let err = deserialize("name=doggo&age=3&address[town]=Paris").unwrap_err();
+        insta::assert_snapshot!(err, @"Unknown field `town` inside `address`: expected one of `city`");
+    }
+}
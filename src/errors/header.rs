@@ -0,0 +1,108 @@
+//! This module implements the error messages of HTTP headers deserialization errors.
+//!
+//! The messages are the same as the ones of the query parameters, but they talk about
+//! headers, e.g. "Missing header `x-api-key`".
+
+use crate::{DeserializeError, MergeWithError};
+use deserr::{ErrorKind, IntoValue, ValuePointerRef};
+use std::{convert::Infallible, fmt::Display, ops::ControlFlow};
+
+use super::query_params::error_message;
+
+#[derive(Debug, Clone)]
+pub struct HeaderError(String);
+
+impl Display for HeaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl HeaderError {
+    fn new(msg: String) -> Self {
+        HeaderError(msg)
+    }
+}
+
+impl DeserializeError for HeaderError {
+    fn error<V: IntoValue>(
+        _self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let message = error_message(error, location, "header");
+
+        ControlFlow::Break(HeaderError::new(message))
+    }
+}
+
+impl MergeWithError<HeaderError> for HeaderError {
+    fn merge(
+        _self_: Option<Self>,
+        other: HeaderError,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(other)
+    }
+}
+
+impl<E: std::error::Error> MergeWithError<E> for HeaderError {
+    fn merge(
+        self_: Option<Self>,
+        other: E,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        HeaderError::error::<Infallible>(
+            self_,
+            ErrorKind::Unexpected {
+                msg: other.to_string(),
+            },
+            merge_location,
+        )
+    }
+}
+
+#[cfg(all(test, feature = "http"))]
+mod tests {
+    use http::{HeaderMap, HeaderName, HeaderValue};
+
+    use super::*;
+    use crate::headers::HeadersValue;
+
+    #[allow(dead_code)]
+    #[derive(deserr::Deserr, Debug)]
+    #[deserr(rename_all = kebab-case)]
+    struct Headers {
+        x_api_key: String,
+        x_request_id: u64,
+    }
+
+    fn deserialize(headers: &[(&str, &[u8])]) -> Result<Headers, HeaderError> {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).unwrap();
+            map.append(name, HeaderValue::from_bytes(value).unwrap());
+        }
+        deserr::deserialize::<Headers, _, HeaderError>(HeadersValue::from(map))
+    }
+
+    #[test]
+    fn error_msg() {
+        let err = deserialize(&[("x-request-id", b"12")]).unwrap_err();
+        insta::assert_snapshot!(err, @"Missing header `x-api-key`");
+
+        let err = deserialize(&[("X-Api-Key", b"s3cr3t"), ("x-request-id", b"doggo")]).unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for header `x-request-id`: expected a positive integer, but found a string: `doggo`");
+
+        let err = deserialize(&[
+            ("x-api-key", b"a"),
+            ("x-api-key", b"b"),
+            ("x-request-id", b"12"),
+        ])
+        .unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for header `x-api-key`: expected a string, but found multiple values");
+
+        let err = deserialize(&[("x-api-key", b"\xff\xfe"), ("x-request-id", b"12")]).unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for header `x-api-key`: expected a string, but found a byte string");
+    }
+}
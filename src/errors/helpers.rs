@@ -1,27 +1,60 @@
 //! Provide generic function that could be useful when creating your own
 //! error type.
 
+use std::fmt::Display;
+
 use strsim::damerau_levenshtein;
 
 /// Compute a did you mean message from a received string and a list of
 /// accepted strings.
 pub fn did_you_mean(received: &str, accepted: &[&str]) -> String {
+    match closest_match(received, accepted) {
+        None => String::new(),
+        Some(accepted) => format!("did you mean `{}`? ", accepted),
+    }
+}
+
+/// Return the accepted string which is the closest to the received one, if it is close
+/// enough to be considered as a typo.
+pub fn closest_match<'a>(received: &str, accepted: &[&'a str]) -> Option<&'a str> {
     let typo_allowed = match received.len() {
         // no typos are allowed, we can early return
-        0..=3 => return String::new(),
+        0..=3 => return None,
         4..=7 => 1,
         8..=12 => 2,
         13..=17 => 3,
         18..=24 => 4,
         _ => 5,
     };
-    match accepted
+    accepted
         .iter()
-        .map(|accepted| (accepted, damerau_levenshtein(received, accepted)))
+        .map(|accepted| (*accepted, damerau_levenshtein(received, accepted)))
         .filter(|(_, distance)| distance <= &typo_allowed)
         .min_by(|(_, d1), (_, d2)| d1.cmp(d2))
-    {
+        .map(|(accepted, _)| accepted)
+}
+
+/// Compute a description of the bounds of a `range` or `length` attribute.
+/// e.g. `between 1 and 1000` or `greater than or equal to 1`.
+pub fn bounds_description(min: Option<&dyn Display>, max: Option<&dyn Display>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("between {min} and {max}"),
+        (Some(min), None) => format!("greater than or equal to {min}"),
+        (None, Some(max)) => format!("less than or equal to {max}"),
+        (None, None) => "without bounds".to_owned(),
+    }
+}
+
+/// Compute a description of a list of fields.
+/// e.g. `` `a`, `b` and `c` `` when `last_separator` is `and`.
+pub fn fields_description(fields: &[&str], last_separator: &str) -> String {
+    let mut fields = fields
+        .iter()
+        .map(|field| format!("`{field}`"))
+        .collect::<Vec<_>>();
+    match fields.pop() {
         None => String::new(),
-        Some((accepted, _)) => format!("did you mean `{}`? ", accepted),
+        Some(last) if fields.is_empty() => last,
+        Some(last) => format!("{} {last_separator} {last}", fields.join(", ")),
     }
 }
@@ -10,7 +10,7 @@ use deserr::{ErrorKind, IntoValue, ValueKind, ValuePointerRef};
 
 use crate::{DeserializeError, MergeWithError, Sequence};
 
-use super::helpers::did_you_mean;
+use super::helpers::{bounds_description, did_you_mean, fields_description};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct JsonError(String);
@@ -57,9 +57,11 @@ pub fn value_kinds_description_json(kinds: &[ValueKind]) -> String {
             ValueKind::Integer => 2,
             ValueKind::NegativeInteger => 3,
             ValueKind::Float => 4,
-            ValueKind::String => 5,
-            ValueKind::Sequence => 6,
-            ValueKind::Map => 7,
+            ValueKind::Number => 5,
+            ValueKind::String => 6,
+            ValueKind::Bytes => 7,
+            ValueKind::Sequence => 8,
+            ValueKind::Map => 9,
         }
     }
     // Return a description of a single value kind, preceded by an article
@@ -69,8 +71,9 @@ pub fn value_kinds_description_json(kinds: &[ValueKind]) -> String {
             ValueKind::Boolean => "a boolean",
             ValueKind::Integer => "a positive integer",
             ValueKind::NegativeInteger => "a negative integer",
-            ValueKind::Float => "a number",
+            ValueKind::Float | ValueKind::Number => "a number",
             ValueKind::String => "a string",
+            ValueKind::Bytes => "a byte string",
             ValueKind::Sequence => "an array",
             ValueKind::Map => "an object",
         }
@@ -79,12 +82,15 @@ pub fn value_kinds_description_json(kinds: &[ValueKind]) -> String {
     fn description_rec(kinds: &[ValueKind], count_items: &mut usize, message: &mut String) {
         let (msg_part, rest): (_, &[ValueKind]) = match kinds {
             [] => (String::new(), &[]),
-            [ValueKind::Integer | ValueKind::NegativeInteger, ValueKind::Float, rest @ ..] => {
-                ("a number".to_owned(), rest)
-            }
-            [ValueKind::Integer, ValueKind::NegativeInteger, ValueKind::Float, rest @ ..] => {
-                ("a number".to_owned(), rest)
-            }
+            [ValueKind::Integer | ValueKind::NegativeInteger, ValueKind::Float, rest @ ..] => (
+                "a number".to_owned(),
+                rest.strip_prefix(&[ValueKind::Number]).unwrap_or(rest),
+            ),
+            [ValueKind::Integer, ValueKind::NegativeInteger, ValueKind::Float, rest @ ..] => (
+                "a number".to_owned(),
+                rest.strip_prefix(&[ValueKind::Number]).unwrap_or(rest),
+            ),
+            [ValueKind::Float, ValueKind::Number, rest @ ..] => ("a number".to_owned(), rest),
             [ValueKind::Integer, ValueKind::NegativeInteger, rest @ ..] => {
                 ("an integer".to_owned(), rest)
             }
@@ -140,6 +146,17 @@ pub fn value_description_with_kind_json(v: &serde_json::Value) -> String {
     }
 }
 
+/// Return a description of the value, see [`value_description_with_kind_json`].
+/// The byte strings, which have no JSON representation, are shown as an array of bytes, and
+/// the numbers given as text are shown as is, instead of being rounded by serde_json.
+pub(crate) fn value_description_json<V: IntoValue>(value: deserr::Value<V>) -> String {
+    match value {
+        deserr::Value::Bytes(bytes) => format!("a byte string: `{bytes:?}`"),
+        deserr::Value::Number(x) => format!("a number: `{x}`"),
+        value => value_description_with_kind_json(&serde_json::Value::from(value)),
+    }
+}
+
 impl DeserializeError for JsonError {
     fn error<V: IntoValue>(
         _self_: Option<Self>,
@@ -151,7 +168,7 @@ impl DeserializeError for JsonError {
         message.push_str(&match error {
             ErrorKind::IncorrectValueKind { actual, accepted } => {
                 let expected = value_kinds_description_json(accepted);
-                let received = value_description_with_kind_json(&serde_json::Value::from(actual));
+                let received = value_description_json(actual);
 
                 let location = location_json_description(location, " at");
 
@@ -204,6 +221,64 @@ impl DeserializeError for JsonError {
                 let location = location_json_description(location, " at");
                 format!("Invalid value{location}: {msg}")
             }
+            ErrorKind::OutOfRange { actual, min, max } => {
+                let location = location_json_description(location, " at");
+                format!(
+                    "Invalid value{location}: expected a value {}, but found {actual}",
+                    bounds_description(min, max)
+                )
+            }
+            ErrorKind::BadLength { actual, min, max } => {
+                let location = location_json_description(location, " at");
+                format!(
+                    "Invalid value length{location}: expected a length {}, but found {actual}",
+                    bounds_description(
+                        min.as_ref().map(|min| min as &dyn Display),
+                        max.as_ref().map(|max| max as &dyn Display)
+                    )
+                )
+            }
+            ErrorKind::EmptyValue => {
+                let location = location_json_description(location, " at");
+                format!("Invalid value{location}: expected a non-empty value")
+            }
+            ErrorKind::PatternMismatch { value, pattern } => {
+                let location = location_json_description(location, " at");
+                format!("Invalid value{location}: expected a value matching the pattern `{pattern}`, but found `{value}`")
+            }
+            ErrorKind::MissingRequiredFields { field, missing } => {
+                let location = location_json_description(location, " inside");
+                let missing_description = fields_description(missing, "and");
+                if missing.len() == 1 {
+                    format!("Missing field {missing_description}{location}: it is required when `{field}` is specified")
+                } else {
+                    format!("Missing fields {missing_description}{location}: they are required when `{field}` is specified")
+                }
+            }
+            ErrorKind::ConflictingFields { field, conflicting } => {
+                let location = location_json_description(location, " inside");
+                let conflicting_description = fields_description(conflicting, "and");
+                if conflicting.len() == 1 {
+                    format!("Conflicting field {conflicting_description}{location}: it cannot be specified together with `{field}`")
+                } else {
+                    format!("Conflicting fields {conflicting_description}{location}: they cannot be specified together with `{field}`")
+                }
+            }
+            ErrorKind::NotExactlyOneOf { fields, present } => {
+                let location = location_json_description(location, " inside");
+                let expected = fields_description(fields, "or");
+                if present.is_empty() {
+                    format!("Missing field{location}: expected exactly one of {expected}")
+                } else {
+                    format!(
+                        "Conflicting fields {}{location}: expected exactly one of {expected}",
+                        fields_description(present, "and")
+                    )
+                }
+            }
+            ErrorKind::Syntax { msg, line, column } => {
+                format!("Invalid JSON at line {line}, column {column}: {msg}")
+            }
         });
 
         ControlFlow::Break(JsonError::new(message))
@@ -236,6 +311,72 @@ impl<E: std::error::Error> MergeWithError<E> for JsonError {
     }
 }
 
+/// An error raised while reading the body of a Json request, before its payload is
+/// deserialized, e.g. by the [`AwebJson`](crate::actix_web::AwebJson) and
+/// [`AxumJson`](crate::axum::AxumJson) extractors.
+///
+/// It is given to the error type of the extractors through [`MergeWithError`], to let them
+/// choose how to report it. It doesn't implement [`std::error::Error`] so that the error
+/// types of deserr can implement a dedicated `MergeWithError<JsonBodyError>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonBodyError {
+    /// The payload is larger than the limit, when known.
+    PayloadTooLarge { limit: Option<usize> },
+    /// The `Content-Type` header is missing.
+    MissingContentType,
+    /// The `Content-Type` header is not accepted.
+    InvalidContentType(String),
+}
+
+impl JsonBodyError {
+    /// The status code of the response rejecting the request: `413 Payload Too Large` or
+    /// `415 Unsupported Media Type`.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            JsonBodyError::PayloadTooLarge { .. } => 413,
+            JsonBodyError::MissingContentType | JsonBodyError::InvalidContentType(_) => 415,
+        }
+    }
+}
+
+impl Display for JsonBodyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonBodyError::PayloadTooLarge { limit: Some(limit) } => {
+                write!(
+                    f,
+                    "The payload is larger than allowed (limit: {limit} bytes)"
+                )
+            }
+            JsonBodyError::PayloadTooLarge { limit: None } => {
+                write!(f, "The payload is larger than allowed")
+            }
+            JsonBodyError::MissingContentType => {
+                write!(
+                    f,
+                    "Missing `Content-Type` header: expected `application/json`"
+                )
+            }
+            JsonBodyError::InvalidContentType(content_type) => {
+                write!(
+                    f,
+                    "Invalid `Content-Type` header `{content_type}`: expected `application/json`"
+                )
+            }
+        }
+    }
+}
+
+impl MergeWithError<JsonBodyError> for JsonError {
+    fn merge(
+        _self_: Option<Self>,
+        other: JsonBodyError,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(JsonError::new(other.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,6 +392,8 @@ mod tests {
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::NegativeInteger]), @"a negative integer");
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Integer]), @"a positive integer");
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::String]), @"a string");
+        insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Number]), @"a number");
+        insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Bytes]), @"a byte string");
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Sequence]), @"an array");
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Map]), @"an object");
 
@@ -259,6 +402,8 @@ mod tests {
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Sequence, ValueKind::NegativeInteger]), @"a negative integer or an array");
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Integer, ValueKind::Float]), @"a number");
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger]), @"a number");
+        insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger, ValueKind::Number]), @"a number");
+        insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Number, ValueKind::Float, ValueKind::String]), @"a number or a string");
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger, ValueKind::Null]), @"null or a number");
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Boolean, ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger, ValueKind::Null]), @"null, a boolean, or a number");
         insta::assert_snapshot!(value_kinds_description_json(&[ValueKind::Null, ValueKind::Boolean, ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger, ValueKind::Null]), @"null, a boolean, or a number");
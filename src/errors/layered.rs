@@ -0,0 +1,172 @@
+//! This module implements the error messages of layered configurations deserialization
+//! errors, see the [`layered`](crate::layered) module.
+//!
+//! The messages start with the location of the invalid value and, when it is known, the
+//! source it comes from, e.g. "`db.port` from environment variable APP_DB_PORT: expected a
+//! positive integer, but found a string: `\"doggo\"`".
+
+use std::{convert::Infallible, fmt::Display, ops::ControlFlow};
+
+use crate::{DeserializeError, MergeWithError, Sequence, ValuePointer, ValuePointerComponent};
+use deserr::{ErrorKind, IntoValue, ValuePointerRef};
+
+use super::helpers::{bounds_description, did_you_mean, fields_description};
+use super::json::{value_description_json, value_kinds_description_json};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayeredError {
+    location: ValuePointer,
+    source: Option<String>,
+    message: String,
+}
+
+impl LayeredError {
+    /// The location of the invalid value.
+    pub fn location(&self) -> &ValuePointer {
+        &self.location
+    }
+
+    /// The source of the invalid value, if it is known.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// Set the source of the invalid value.
+    pub fn with_source(mut self, source: Option<String>) -> Self {
+        self.source = source;
+        self
+    }
+}
+
+impl Display for LayeredError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut location = String::new();
+        for component in &self.location.path {
+            match component {
+                ValuePointerComponent::Key(key) if location.is_empty() => location.push_str(key),
+                ValuePointerComponent::Key(key) => {
+                    location.push('.');
+                    location.push_str(key);
+                }
+                ValuePointerComponent::Index(index) => location.push_str(&format!("[{index}]")),
+            }
+        }
+        match (location.is_empty(), &self.source) {
+            (true, _) => write!(f, "Invalid configuration: {}", self.message),
+            (false, Some(source)) => write!(f, "`{location}` from {source}: {}", self.message),
+            (false, None) => write!(f, "`{location}`: {}", self.message),
+        }
+    }
+}
+
+impl DeserializeError for LayeredError {
+    fn error<V: IntoValue>(
+        _self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let mut location = location.to_owned();
+        let message = match error {
+            ErrorKind::IncorrectValueKind { actual, accepted } => {
+                let expected = value_kinds_description_json(accepted);
+                let received = value_description_json(actual);
+                format!("expected {expected}, but found {received}")
+            }
+            ErrorKind::MissingField { field } => {
+                location
+                    .path
+                    .push(ValuePointerComponent::Key(field.to_owned()));
+                "missing value".to_owned()
+            }
+            ErrorKind::UnknownKey { key, accepted } => {
+                location
+                    .path
+                    .push(ValuePointerComponent::Key(key.to_owned()));
+                format!(
+                    "unknown field, {}expected one of {}",
+                    did_you_mean(key, accepted),
+                    fields_description(accepted, "or")
+                )
+            }
+            ErrorKind::UnknownValue { value, accepted } => format!(
+                "unknown value `{value}`, {}expected one of {}",
+                did_you_mean(value, accepted),
+                fields_description(accepted, "or")
+            ),
+            ErrorKind::BadSequenceLen { actual, expected } => {
+                format!("expected {expected} elements, but found {}", actual.len())
+            }
+            ErrorKind::Unexpected { msg } => msg,
+            ErrorKind::OutOfRange { actual, min, max } => format!(
+                "expected a value {}, but found {actual}",
+                bounds_description(min, max)
+            ),
+            ErrorKind::BadLength { actual, min, max } => format!(
+                "expected a length {}, but found {actual}",
+                bounds_description(
+                    min.as_ref().map(|min| min as &dyn Display),
+                    max.as_ref().map(|max| max as &dyn Display)
+                )
+            ),
+            ErrorKind::EmptyValue => "expected a non-empty value".to_owned(),
+            ErrorKind::PatternMismatch { value, pattern } => {
+                format!("expected a value matching the pattern `{pattern}`, but found `{value}`")
+            }
+            ErrorKind::MissingRequiredFields { field, missing } => format!(
+                "missing {}, required when `{field}` is specified",
+                fields_description(missing, "and")
+            ),
+            ErrorKind::ConflictingFields { field, conflicting } => format!(
+                "{} cannot be specified together with `{field}`",
+                fields_description(conflicting, "and")
+            ),
+            ErrorKind::NotExactlyOneOf {
+                fields,
+                present: [],
+            } => format!(
+                "expected exactly one of {}",
+                fields_description(fields, "or")
+            ),
+            ErrorKind::NotExactlyOneOf { fields, present } => format!(
+                "expected exactly one of {}, but found {}",
+                fields_description(fields, "or"),
+                fields_description(present, "and")
+            ),
+            ErrorKind::Syntax { msg, line, column } => {
+                format!("invalid syntax at line {line}, column {column}: {msg}")
+            }
+        };
+
+        ControlFlow::Break(LayeredError {
+            location,
+            source: None,
+            message,
+        })
+    }
+}
+
+impl MergeWithError<LayeredError> for LayeredError {
+    fn merge(
+        _self_: Option<Self>,
+        other: LayeredError,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(other)
+    }
+}
+
+impl<E: std::error::Error> MergeWithError<E> for LayeredError {
+    fn merge(
+        self_: Option<Self>,
+        other: E,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        LayeredError::error::<Infallible>(
+            self_,
+            ErrorKind::Unexpected {
+                msg: other.to_string(),
+            },
+            merge_location,
+        )
+    }
+}
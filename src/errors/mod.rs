@@ -1,8 +1,22 @@
 //! This module holds some pre-made error types to eases your usage of deserr
 
+pub mod args;
+pub mod env;
+pub mod form;
+pub mod header;
 pub mod helpers;
 pub mod json;
+pub mod layered;
+pub mod multipart;
+pub mod path;
 pub mod query_params;
 
-pub use json::JsonError;
+pub use args::ArgsError;
+pub use env::EnvError;
+pub use form::FormError;
+pub use header::HeaderError;
+pub use json::{JsonBodyError, JsonError};
+pub use layered::LayeredError;
+pub use multipart::MultipartError;
+pub use path::PathError;
 pub use query_params::QueryParamError;
@@ -0,0 +1,140 @@
+//! This module implements the error messages of `multipart/form-data` deserialization errors.
+//!
+//! The messages are the same as the ones of the query parameters, but they talk about
+//! parts, e.g. "Missing part `image`".
+
+use crate::{DeserializeError, MergeWithError};
+use deserr::{ErrorKind, IntoValue, ValuePointerRef};
+use std::{convert::Infallible, fmt::Display, ops::ControlFlow};
+
+use super::query_params::error_message;
+
+#[derive(Debug, Clone)]
+pub struct MultipartError(String);
+
+impl Display for MultipartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl MultipartError {
+    fn new(msg: String) -> Self {
+        MultipartError(msg)
+    }
+}
+
+impl DeserializeError for MultipartError {
+    fn error<V: IntoValue>(
+        _self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let message = error_message(error, location, "part");
+
+        ControlFlow::Break(MultipartError::new(message))
+    }
+}
+
+impl MergeWithError<MultipartError> for MultipartError {
+    fn merge(
+        _self_: Option<Self>,
+        other: MultipartError,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(other)
+    }
+}
+
+impl<E: std::error::Error> MergeWithError<E> for MultipartError {
+    fn merge(
+        self_: Option<Self>,
+        other: E,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        MultipartError::error::<Infallible>(
+            self_,
+            ErrorKind::Unexpected {
+                msg: other.to_string(),
+            },
+            merge_location,
+        )
+    }
+}
+
+#[cfg(all(test, feature = "multipart"))]
+mod tests {
+    use super::*;
+    use crate::multipart::{File, MultipartMap, MultipartValue};
+
+    #[allow(dead_code)]
+    #[derive(deserr::Deserr, Debug)]
+    struct Upload {
+        title: String,
+        public: bool,
+        image: File,
+    }
+
+    fn text(s: &str) -> MultipartValue {
+        MultipartValue::String(s.to_owned())
+    }
+
+    fn file(name: &str) -> MultipartValue {
+        let mut file = MultipartMap::default();
+        file.append(String::from("file_name"), text(name));
+        file.append(String::from("content_type"), text("image/png"));
+        file.append(
+            String::from("content"),
+            MultipartValue::Bytes(b"bork".to_vec()),
+        );
+        MultipartValue::Map(file)
+    }
+
+    fn deserialize(parts: Vec<(&str, MultipartValue)>) -> Result<Upload, MultipartError> {
+        let mut map = MultipartMap::default();
+        for (name, value) in parts {
+            map.append(name.to_owned(), value);
+        }
+        deserr::deserialize::<Upload, _, MultipartError>(MultipartValue::Map(map))
+    }
+
+    #[test]
+    fn error_msg() {
+        let err =
+            deserialize(vec![("public", text("true")), ("image", file("doggo.png"))]).unwrap_err();
+        insta::assert_snapshot!(err, @"Missing part `title`");
+
+        let err = deserialize(vec![
+            ("title", text("doggo")),
+            ("public", text("yes")),
+            ("image", file("doggo.png")),
+        ])
+        .unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for part `public`: expected a boolean, but found a string: `yes`");
+
+        let err = deserialize(vec![
+            ("title", text("doggo")),
+            ("public", text("true")),
+            ("image", text("doggo.png")),
+        ])
+        .unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for part `image`: expected multiple parts, but found a string: `doggo.png`");
+
+        let err = deserialize(vec![
+            ("title", text("doggo")),
+            ("public", file("true.png")),
+            ("image", file("doggo.png")),
+        ])
+        .unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for part `public`: expected a boolean, but found multiple parts");
+
+        let err = deserialize(vec![
+            ("title", text("doggo")),
+            ("public", text("true")),
+            ("image", file("doggo.png")),
+            ("image", file("kefir.png")),
+        ])
+        .unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for part `image`: expected multiple parts, but found multiple values");
+    }
+}
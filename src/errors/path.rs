@@ -0,0 +1,93 @@
+//! This module implements the error messages of path parameters deserialization errors.
+//!
+//! The messages are the same as the ones of the query parameters, but they talk about
+//! path parameters, e.g. "Invalid value type for path parameter `uid`".
+
+use crate::{DeserializeError, MergeWithError};
+use deserr::{ErrorKind, IntoValue, ValuePointerRef};
+use std::{convert::Infallible, fmt::Display, ops::ControlFlow};
+
+use super::query_params::error_message;
+
+#[derive(Debug, Clone)]
+pub struct PathError(String);
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PathError {
+    fn new(msg: String) -> Self {
+        PathError(msg)
+    }
+}
+
+impl DeserializeError for PathError {
+    fn error<V: IntoValue>(
+        _self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let message = error_message(error, location, "path parameter");
+
+        ControlFlow::Break(PathError::new(message))
+    }
+}
+
+impl MergeWithError<PathError> for PathError {
+    fn merge(
+        _self_: Option<Self>,
+        other: PathError,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(other)
+    }
+}
+
+impl<E: std::error::Error> MergeWithError<E> for PathError {
+    fn merge(
+        self_: Option<Self>,
+        other: E,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        PathError::error::<Infallible>(
+            self_,
+            ErrorKind::Unexpected {
+                msg: other.to_string(),
+            },
+            merge_location,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_string::{QueryConfig, QueryValue};
+
+    #[allow(dead_code)]
+    #[derive(deserr::Deserr, Debug)]
+    struct DocumentPath {
+        uid: String,
+        id: u64,
+    }
+
+    fn deserialize(params: &[(&str, &str)]) -> Result<DocumentPath, PathError> {
+        let pairs = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()));
+        let value = QueryValue::from_pairs(pairs, QueryConfig::default()).unwrap();
+        deserr::deserialize::<DocumentPath, _, PathError>(value)
+    }
+
+    #[test]
+    fn error_msg() {
+        let err = deserialize(&[("uid", "movies")]).unwrap_err();
+        insta::assert_snapshot!(err, @"Missing path parameter `id`");
+
+        let err = deserialize(&[("uid", "movies"), ("id", "doggo")]).unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for path parameter `id`: expected a positive integer, but found a string: `doggo`");
+    }
+}
@@ -8,7 +8,8 @@ use crate::{DeserializeError, MergeWithError, Sequence};
 use deserr::{ErrorKind, IntoValue, ValueKind, ValuePointerRef};
 use std::{convert::Infallible, fmt::Display, ops::ControlFlow};
 
-use super::helpers::did_you_mean;
+use super::helpers::{bounds_description, did_you_mean, fields_description};
+use super::json::value_kinds_description_json;
 
 #[derive(Debug, Clone)]
 pub struct QueryParamError(String);
@@ -31,77 +32,150 @@ impl deserr::DeserializeError for QueryParamError {
         error: deserr::ErrorKind<V>,
         location: ValuePointerRef,
     ) -> ControlFlow<Self, Self> {
-        let mut message = String::new();
+        let message = error_message(error, location, "parameter");
 
-        message.push_str(&match error {
-            ErrorKind::IncorrectValueKind { actual, accepted } => {
-                let expected = value_kinds_description_query_param(accepted);
-                let received = value_description_with_kind_query_param(actual);
-
-                let location = location_query_param_description(location, " for parameter");
+        ControlFlow::Break(QueryParamError::new(message))
+    }
+}
 
-                format!("Invalid value type{location}: expected {expected}, but found {received}")
-            }
-            ErrorKind::MissingField { field } => {
-                let location = location_query_param_description(location, " inside");
-                format!("Missing parameter `{field}`{location}")
-            }
-            ErrorKind::UnknownKey { key, accepted } => {
-                let location = location_query_param_description(location, " inside");
-                format!(
-                    "Unknown parameter `{}`{location}: {}expected one of {}",
-                    key,
-                    did_you_mean(key, accepted),
-                    accepted
-                        .iter()
-                        .map(|accepted| format!("`{}`", accepted))
-                        .collect::<Vec<String>>()
-                        .join(", ")
+/// Return the message of an error happening while deserializing strings and maps of strings,
+/// such as query parameters. The `noun` is the name given to the keys, e.g. `parameter`.
+pub(crate) fn error_message<V: IntoValue>(
+    error: ErrorKind<V>,
+    location: ValuePointerRef,
+    noun: &str,
+) -> String {
+    match error {
+        ErrorKind::IncorrectValueKind { actual, accepted } => {
+            let expected = value_kinds_description(accepted, noun);
+            let received = value_description_with_kind(actual, noun);
+
+            let location = location_query_param_description(location, &format!(" for {noun}"));
+
+            format!("Invalid value type{location}: expected {expected}, but found {received}")
+        }
+        ErrorKind::MissingField { field } => {
+            let location = location_query_param_description(location, " inside");
+            format!("Missing {noun} `{field}`{location}")
+        }
+        ErrorKind::UnknownKey { key, accepted } => {
+            let location = location_query_param_description(location, " inside");
+            format!(
+                "Unknown {noun} `{}`{location}: {}expected one of {}",
+                key,
+                did_you_mean(key, accepted),
+                accepted
+                    .iter()
+                    .map(|accepted| format!("`{}`", accepted))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        }
+        ErrorKind::UnknownValue { value, accepted } => {
+            let location = location_query_param_description(location, &format!(" for {noun}"));
+            format!(
+                "Unknown value `{}`{location}: {}expected one of {}",
+                value,
+                did_you_mean(value, accepted),
+                accepted
+                    .iter()
+                    .map(|accepted| format!("`{}`", accepted))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            )
+        }
+        ErrorKind::BadSequenceLen { actual, expected } => {
+            let location = location_query_param_description(location, &format!(" for {noun}"));
+            let len = actual.len();
+            let value: crate::Value<V> = crate::Value::Sequence(actual);
+            format!(
+                "Invalid array len{}. Received {} elements instead of {}: `{}`",
+                location,
+                len,
+                expected,
+                serde_json::to_string(&serde_json::Value::from(value)).unwrap()
+            )
+        }
+        ErrorKind::Unexpected { msg } => {
+            let location = location_query_param_description(location, &format!(" in {noun}"));
+            format!("Invalid value{location}: {msg}")
+        }
+        ErrorKind::OutOfRange { actual, min, max } => {
+            let location = location_query_param_description(location, &format!(" for {noun}"));
+            format!(
+                "Invalid value{location}: expected a value {}, but found {actual}",
+                bounds_description(min, max)
+            )
+        }
+        ErrorKind::BadLength { actual, min, max } => {
+            let location = location_query_param_description(location, &format!(" for {noun}"));
+            format!(
+                "Invalid value length{location}: expected a length {}, but found {actual}",
+                bounds_description(
+                    min.as_ref().map(|min| min as &dyn Display),
+                    max.as_ref().map(|max| max as &dyn Display)
                 )
+            )
+        }
+        ErrorKind::EmptyValue => {
+            let location = location_query_param_description(location, &format!(" for {noun}"));
+            format!("Invalid value{location}: expected a non-empty value")
+        }
+        ErrorKind::PatternMismatch { value, pattern } => {
+            let location = location_query_param_description(location, &format!(" for {noun}"));
+            format!("Invalid value{location}: expected a value matching the pattern `{pattern}`, but found `{value}`")
+        }
+        ErrorKind::MissingRequiredFields { field, missing } => {
+            let location = location_query_param_description(location, " inside");
+            let missing_description = fields_description(missing, "and");
+            if missing.len() == 1 {
+                format!("Missing {noun} {missing_description}{location}: it is required when `{field}` is specified")
+            } else {
+                format!("Missing {noun}s {missing_description}{location}: they are required when `{field}` is specified")
             }
-            ErrorKind::UnknownValue { value, accepted } => {
-                let location = location_query_param_description(location, " for parameter");
-                format!(
-                    "Unknown value `{}`{location}: {}expected one of {}",
-                    value,
-                    did_you_mean(value, accepted),
-                    accepted
-                        .iter()
-                        .map(|accepted| format!("`{}`", accepted))
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                )
+        }
+        ErrorKind::ConflictingFields { field, conflicting } => {
+            let location = location_query_param_description(location, " inside");
+            let conflicting_description = fields_description(conflicting, "and");
+            if conflicting.len() == 1 {
+                format!("Conflicting {noun} {conflicting_description}{location}: it cannot be specified together with `{field}`")
+            } else {
+                format!("Conflicting {noun}s {conflicting_description}{location}: they cannot be specified together with `{field}`")
             }
-            ErrorKind::BadSequenceLen { actual, expected } => {
-                let location = location_query_param_description(location, " for parameter");
-                let len = actual.len();
-                let value: crate::Value<V> = crate::Value::Sequence(actual);
+        }
+        ErrorKind::NotExactlyOneOf { fields, present } => {
+            let location = location_query_param_description(location, " inside");
+            let expected = fields_description(fields, "or");
+            if present.is_empty() {
+                format!("Missing {noun}{location}: expected exactly one of {expected}")
+            } else {
                 format!(
-                    "Invalid array len{}. Received {} elements instead of {}: `{}`",
-                    location,
-                    len,
-                    expected,
-                    serde_json::to_string(&serde_json::Value::from(value)).unwrap()
+                    "Conflicting {noun}s {}{location}: expected exactly one of {expected}",
+                    fields_description(present, "and")
                 )
             }
-            ErrorKind::Unexpected { msg } => {
-                let location = location_query_param_description(location, " in parameter");
-                format!("Invalid value{location}: {msg}")
-            }
-        });
-
-        ControlFlow::Break(QueryParamError::new(message))
+        }
+        ErrorKind::Syntax { msg, line, column } => {
+            format!("Invalid syntax at line {line}, column {column}: {msg}")
+        }
     }
 }
 
-/// Return a description of the list of value kinds for query parameters
-/// Since query parameters are always treated as strings, we always return
-/// "a string" for now.
-pub fn value_kinds_description_query_param(_accepted: &[ValueKind]) -> String {
-    "a string".to_owned()
+/// Return a description of the list of value kinds for query parameters.
+/// Query parameters are strings, but they can be coerced into numbers and booleans,
+/// so the description is the same as for JSON values, except for arrays and objects
+/// which are described as multiple values and multiple parameters.
+pub fn value_kinds_description_query_param(accepted: &[ValueKind]) -> String {
+    value_kinds_description(accepted, "parameter")
+}
+
+fn value_kinds_description(accepted: &[ValueKind], noun: &str) -> String {
+    value_kinds_description_json(accepted)
+        .replace("an array", "multiple values")
+        .replace("an object", &format!("multiple {noun}s"))
 }
 
-fn value_description_with_kind_query_param<V: IntoValue>(actual: deserr::Value<V>) -> String {
+fn value_description_with_kind<V: IntoValue>(actual: deserr::Value<V>, noun: &str) -> String {
     match actual {
         deserr::Value::Null => "null".to_owned(),
         deserr::Value::Boolean(x) => format!("a boolean: `{x}`"),
@@ -112,11 +186,15 @@ fn value_description_with_kind_query_param<V: IntoValue>(actual: deserr::Value<V
         deserr::Value::Float(x) => {
             format!("a number: `{x}`")
         }
+        deserr::Value::Number(x) => {
+            format!("a number: `{x}`")
+        }
         deserr::Value::String(x) => {
             format!("a string: `{x}`")
         }
+        deserr::Value::Bytes(_) => "a byte string".to_owned(),
         deserr::Value::Sequence(_) => "multiple values".to_owned(),
-        deserr::Value::Map(_) => "multiple parameters".to_owned(),
+        deserr::Value::Map(_) => format!("multiple {noun}s"),
     }
 }
 
@@ -179,24 +257,24 @@ mod tests {
 
     #[test]
     fn test_value_kinds_description_query_param() {
-        insta::assert_snapshot!(value_kinds_description_query_param(&[]), @"a string");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[]), @"a different value");
 
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Boolean]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::NegativeInteger]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer]), @"a string");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Boolean]), @"a boolean");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer]), @"a positive integer");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::NegativeInteger]), @"a negative integer");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer]), @"a positive integer");
         insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::String]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Sequence]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Map]), @"a string");
-
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer, ValueKind::Boolean]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Null, ValueKind::Integer]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Sequence, ValueKind::NegativeInteger]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer, ValueKind::Float]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger, ValueKind::Null]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Boolean, ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger, ValueKind::Null]), @"a string");
-        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Null, ValueKind::Boolean, ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger, ValueKind::Null]), @"a string");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Sequence]), @"multiple values");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Map]), @"multiple parameters");
+
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer, ValueKind::Boolean]), @"a boolean or a positive integer");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Null, ValueKind::Integer]), @"null or a positive integer");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Sequence, ValueKind::NegativeInteger]), @"a negative integer or multiple values");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer, ValueKind::Float]), @"a number");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger]), @"a number");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger, ValueKind::Null]), @"null or a number");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Boolean, ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger, ValueKind::Null]), @"null, a boolean, or a number");
+        insta::assert_snapshot!(value_kinds_description_query_param(&[ValueKind::Null, ValueKind::Boolean, ValueKind::Integer, ValueKind::Float, ValueKind::NegativeInteger, ValueKind::Null]), @"null, a boolean, or a number");
     }
 
     #[test]
@@ -220,7 +298,13 @@ mod tests {
         }
         let value = json!({ "me": [2] });
         let err = deserr::deserialize::<Incorrect, _, QueryParamError>(value).unwrap_err();
-        insta::assert_snapshot!(err, @"Invalid value type for parameter `me`: expected a string, but found multiple values");
+        insta::assert_snapshot!(err, @"Invalid value type for parameter `me`: expected a positive integer, but found multiple values");
+
+        // the expected kinds are described like in JSON since the strings are coerced,
+        // the strings which can't be coerced are still described as strings
+        let value = json!({ "me": "two" });
+        let err = deserr::deserialize::<Incorrect, _, QueryParamError>(value).unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type for parameter `me`: expected a positive integer, but found a string: `two`");
 
         #[allow(dead_code)]
         #[derive(deserr::Deserr, Debug)]
@@ -0,0 +1,147 @@
+//! An HTTP headers backend for deserr, built on [`http::HeaderMap`].
+//!
+//! The header names are case-insensitive: they are always given to deserr in lowercase, thus
+//! the `rename` attributes must be lowercase too, e.g. `#[deserr(rename = "x-api-key")]`.
+//! A header specified multiple times is a sequence of values. Since every header value is a
+//! string, [`HeadersValue`] coerces them into numbers and booleans when needed, see
+//! [`IntoValue::COERCE_STRINGS`]. A header value which isn't valid UTF-8 is a byte string,
+//! which is an error unless the field accepts bytes.
+//!
+//! ```
+//! use deserr::{errors::HeaderError, headers::HeadersValue, Deserr};
+//! use http::{HeaderMap, HeaderValue};
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! #[deserr(rename_all = kebab-case)]
+//! struct Headers {
+//!     x_request_id: u64,
+//!     #[deserr(default)]
+//!     x_forwarded_for: Vec<String>,
+//! }
+//!
+//! let mut headers = HeaderMap::new();
+//! headers.insert("X-Request-Id", HeaderValue::from_static("42"));
+//! headers.append("X-Forwarded-For", HeaderValue::from_static("10.0.0.1"));
+//! headers.append("X-Forwarded-For", HeaderValue::from_static("10.0.0.2"));
+//!
+//! let value = HeadersValue::from(headers);
+//! let headers = deserr::deserialize::<Headers, _, HeaderError>(value).unwrap();
+//! assert_eq!(headers.x_request_id, 42);
+//! assert_eq!(headers.x_forwarded_for, ["10.0.0.1", "10.0.0.2"]);
+//! ```
+
+use http::header::{Entry, HeaderName};
+use http::HeaderMap;
+
+use crate::{IntoValue, Map, Value, ValueKind};
+
+/// A value extracted from HTTP headers.
+#[derive(Debug, Clone)]
+pub enum HeadersValue {
+    /// All the headers of a request.
+    Headers(Headers),
+    /// The value of a header.
+    String(String),
+    /// The value of a header which isn't valid UTF-8.
+    Bytes(Vec<u8>),
+    /// The values of a header specified multiple times.
+    Sequence(Vec<HeadersValue>),
+}
+
+impl From<HeaderMap> for HeadersValue {
+    fn from(headers: HeaderMap) -> Self {
+        HeadersValue::Headers(Headers(headers))
+    }
+}
+
+/// The headers of a request, as a map from the lowercase header names to their values.
+#[derive(Debug, Clone, Default)]
+pub struct Headers(HeaderMap);
+
+impl Headers {
+    fn values(values: impl Iterator<Item = http::HeaderValue>) -> HeadersValue {
+        // the header values aren't required to be valid UTF-8
+        let mut values = values
+            .map(|value| match String::from_utf8(value.as_bytes().to_vec()) {
+                Ok(s) => HeadersValue::String(s),
+                Err(e) => HeadersValue::Bytes(e.into_bytes()),
+            })
+            .collect::<Vec<_>>();
+        if values.len() == 1 {
+            values.pop().unwrap()
+        } else {
+            HeadersValue::Sequence(values)
+        }
+    }
+}
+
+impl Map for Headers {
+    type Value = HeadersValue;
+    type Iter = std::vec::IntoIter<(String, HeadersValue)>;
+
+    fn len(&self) -> usize {
+        self.0.keys_len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let name = HeaderName::from_bytes(key.as_bytes()).ok()?;
+        match self.0.entry(name) {
+            Entry::Occupied(entry) => Some(Self::values(entry.remove_entry_mult().1)),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        let mut headers = Vec::with_capacity(self.0.keys_len());
+        let mut current: Option<(HeaderName, Vec<http::HeaderValue>)> = None;
+        // the name is only yielded with the first value of each header
+        for (name, value) in self.0 {
+            match name {
+                Some(name) => {
+                    if let Some((name, values)) = current.take() {
+                        headers.push((name.to_string(), Self::values(values.into_iter())));
+                    }
+                    current = Some((name, vec![value]));
+                }
+                None => {
+                    if let Some((_, values)) = current.as_mut() {
+                        values.push(value);
+                    }
+                }
+            }
+        }
+        if let Some((name, values)) = current {
+            headers.push((name.to_string(), Self::values(values.into_iter())));
+        }
+        headers.into_iter()
+    }
+}
+
+impl IntoValue for HeadersValue {
+    type Sequence = Vec<HeadersValue>;
+    type Map = Headers;
+
+    const COERCE_STRINGS: bool = true;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            HeadersValue::Headers(_) => ValueKind::Map,
+            HeadersValue::String(_) => ValueKind::String,
+            HeadersValue::Bytes(_) => ValueKind::Bytes,
+            HeadersValue::Sequence(_) => ValueKind::Sequence,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            HeadersValue::Headers(headers) => Value::Map(headers),
+            HeadersValue::String(s) => Value::String(s),
+            HeadersValue::Bytes(bytes) => Value::Bytes(bytes),
+            HeadersValue::Sequence(seq) => Value::Sequence(seq),
+        }
+    }
+
+    fn string_into_sequence(s: String) -> Result<Vec<HeadersValue>, String> {
+        Ok(vec![HeadersValue::String(s)])
+    }
+}
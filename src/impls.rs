@@ -1,10 +1,11 @@
 use crate::{
-    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, Sequence, Value,
-    ValueKind, ValuePointerRef,
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, OwnedValue, Sequence,
+    Value, ValueKind, ValuePointerRef,
 };
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::{Infallible, TryFrom},
+    fmt::Display,
     hash::Hash,
     marker::PhantomData,
     num::{
@@ -89,7 +90,7 @@ where
         value: Value<V>,
         location: ValuePointerRef,
     ) -> Result<Self, E> {
-        match value {
+        match value.coerce_to_boolean() {
             Value::Boolean(b) => Ok(b),
             v => Err(take_cf_content(E::error(
                 None,
@@ -103,6 +104,25 @@ where
     }
 }
 
+/// Return an [`ErrorKind::OutOfRange`] error for an integer given as a [`Value::Number`] which
+/// doesn't fit between the `min` and `max` of the integer type.
+fn integer_out_of_range<T: Display, E: DeserializeError>(
+    x: &str,
+    min: T,
+    max: T,
+    location: ValuePointerRef,
+) -> E {
+    take_cf_content(E::error::<Infallible>(
+        None,
+        ErrorKind::OutOfRange {
+            actual: &x,
+            min: Some(&min),
+            max: Some(&max),
+        },
+        location,
+    ))
+}
+
 macro_rules! deserialize_impl_integer {
     ($t:ty) => {
         impl<E> Deserr<E> for $t
@@ -126,7 +146,7 @@ macro_rules! deserialize_impl_integer {
                     )
                 };
 
-                match value {
+                match value.coerce_to_number() {
                     Value::Integer(x) => <$t>::try_from(x).or_else(|_| {
                         Err(take_cf_content(E::error::<V>(
                             None,
@@ -139,6 +159,10 @@ macro_rules! deserialize_impl_integer {
                             location,
                         )))
                     }),
+                    Value::Number(x) if $crate::value::is_integer_text(&x) && !x.starts_with('-') => {
+                        x.parse::<$t>()
+                            .map_err(|_| integer_out_of_range(&x, <$t>::MIN, <$t>::MAX, location))
+                    }
                     v => Err(take_cf_content(err(v))),
                 }
             }
@@ -176,7 +200,7 @@ macro_rules! deserialize_impl_non_zero_integer {
                     )
                 };
 
-                match value {
+                match value.coerce_to_number() {
                     Value::Integer(x) if x == 0 => {
                       Err(take_cf_content(E::error::<V>(
                           None,
@@ -201,6 +225,10 @@ macro_rules! deserialize_impl_non_zero_integer {
                             location,
                         )))
                     }),
+                    Value::Number(x) if $crate::value::is_integer_text(&x) && !x.starts_with('-') => {
+                        x.parse::<$t>()
+                            .map_err(|_| integer_out_of_range(&x, <$t>::MIN, <$t>::MAX, location))
+                    }
                     v => Err(take_cf_content(err(v))),
                 }
             }
@@ -238,7 +266,7 @@ macro_rules! deserialize_impl_negative_integer {
                     )
                 };
 
-                match value {
+                match value.coerce_to_number() {
                     Value::Integer(x) => <$t>::try_from(x).or_else(|_| {
                         Err(take_cf_content(E::error::<V>(
                             None,
@@ -263,6 +291,10 @@ macro_rules! deserialize_impl_negative_integer {
                             location,
                         )))
                     }),
+                    Value::Number(x) if $crate::value::is_integer_text(&x) => {
+                        x.parse::<$t>()
+                            .map_err(|_| integer_out_of_range(&x, <$t>::MIN, <$t>::MAX, location))
+                    }
                     v => Err(take_cf_content(err(v))),
                 }
             }
@@ -300,7 +332,7 @@ macro_rules! deserialize_impl_non_zero_negative_integer {
                     )
                 };
 
-                match value {
+                match value.coerce_to_number() {
                     Value::Integer(x) if x == 0 => {
                       Err(take_cf_content(E::error::<V>(
                           None,
@@ -349,6 +381,10 @@ macro_rules! deserialize_impl_non_zero_negative_integer {
                             location,
                         )))
                     }),
+                    Value::Number(x) if $crate::value::is_integer_text(&x) => {
+                        x.parse::<$t>()
+                            .map_err(|_| integer_out_of_range(&x, <$t>::MIN, <$t>::MAX, location))
+                    }
                     v => Err(take_cf_content(err(v))),
                 }
             }
@@ -373,10 +409,23 @@ macro_rules! deserialize_impl_float {
                 value: Value<V>,
                 location: ValuePointerRef,
             ) -> Result<Self, E> {
-                match value {
+                match value.coerce_to_number() {
                     Value::Integer(x) => Ok(x as $t),
                     Value::NegativeInteger(x) => Ok(x as $t),
                     Value::Float(x) => Ok(x as $t),
+                    Value::Number(x) => match x.parse::<$t>() {
+                        Ok(n) if n.is_finite() => Ok(n),
+                        _ => Err($crate::take_cf_content(E::error::<V>(
+                            None,
+                            ErrorKind::Unexpected {
+                                msg: format!(
+                                    "value: `{x}` is outside of the range of a `{}`",
+                                    stringify!($t)
+                                ),
+                            },
+                            location,
+                        ))),
+                    },
                     v => Err($crate::take_cf_content(E::error(
                         None,
                         ErrorKind::IncorrectValueKind {
@@ -468,6 +517,36 @@ where
     }
 }
 
+/// Deserialize each byte of a byte string as a positive integer, so that the byte strings
+/// of the binary formats can be deserialized into sequences of integers such as `Vec<u8>`.
+fn deserialize_bytes<T, E>(bytes: Vec<u8>, location: ValuePointerRef) -> Result<Vec<T>, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    let mut error = None;
+    let mut vec = Vec::with_capacity(bytes.len());
+    for (index, byte) in bytes.into_iter().enumerate() {
+        let value = Value::<Infallible>::Integer(byte.into());
+        match T::deserialize_from_value(value, location.push_index(index)) {
+            Ok(value) => {
+                vec.push(value);
+            }
+            Err(e) => {
+                error = match E::merge(error, e, location.push_index(index)) {
+                    ControlFlow::Continue(e) => Some(e),
+                    ControlFlow::Break(e) => return Err(e),
+                };
+            }
+        }
+    }
+    if let Some(e) = error {
+        Err(e)
+    } else {
+        Ok(vec)
+    }
+}
+
 impl<T, E> Deserr<E> for Vec<T>
 where
     T: Deserr<E>,
@@ -477,7 +556,7 @@ where
         value: Value<V>,
         location: ValuePointerRef,
     ) -> Result<Self, E> {
-        match value {
+        match value.coerce_to_sequence() {
             Value::Sequence(seq) => {
                 let mut error = None;
                 let mut vec = Vec::with_capacity(seq.len());
@@ -502,6 +581,7 @@ where
                     Ok(vec)
                 }
             }
+            Value::Bytes(bytes) => deserialize_bytes(bytes, location),
             v => Err(take_cf_content(E::error(
                 None,
                 ErrorKind::IncorrectValueKind {
@@ -676,7 +756,7 @@ where
         value: Value<V>,
         location: ValuePointerRef,
     ) -> Result<Self, E> {
-        match value {
+        match value.coerce_to_sequence() {
             Value::Sequence(seq) => {
                 let mut error = None;
                 let mut set = HashSet::with_capacity(seq.len());
@@ -722,7 +802,7 @@ where
         value: Value<V>,
         location: ValuePointerRef,
     ) -> Result<Self, E> {
-        match value {
+        match value.coerce_to_sequence() {
             Value::Sequence(seq) => {
                 let mut error = None;
                 let mut set = BTreeSet::new();
@@ -807,6 +887,22 @@ where
                     panic!("Could not convert Vec<T> into [T; N]")
                 }
             }
+            Value::Bytes(bytes) if bytes.len() != N => {
+                // the bytes are reported as a sequence of integers, like any other sequence
+                let actual = bytes.into_iter().map(OwnedValue::from).collect();
+                Err(take_cf_content(E::error::<OwnedValue>(
+                    None,
+                    ErrorKind::BadSequenceLen {
+                        actual,
+                        expected: N,
+                    },
+                    location,
+                )))
+            }
+            Value::Bytes(bytes) => match deserialize_bytes(bytes, location)?.try_into() {
+                Ok(ret) => Ok(ret),
+                Err(_) => panic!("Could not convert Vec<T> into [T; N]"),
+            },
             v => Err(take_cf_content(E::error(
                 None,
                 ErrorKind::IncorrectValueKind {
@@ -0,0 +1,633 @@
+//! A JSON5 backend for deserr, for the configuration files edited by humans.
+//!
+//! JSON5 is a superset of JSON, and thus of JSONC, which also accepts:
+//! - `// line` and `/* block */` comments,
+//! - trailing commas in objects and arrays,
+//! - unquoted keys, such as `{ port: 7700 }`, and single-quoted strings,
+//! - hexadecimal numbers, `Infinity`, `NaN`, leading and trailing decimal points, and
+//!   explicit plus signs.
+//!
+//! The parsed values remember their position in the text, see [`Spanned::span_of`], so that
+//! the errors can point to the invalid value in the file.
+//!
+//! ```
+//! use deserr::{errors::JsonError, Deserr};
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Config {
+//!     db: Db,
+//!     features: Vec<String>,
+//! }
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Db {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! let text = r#"{
+//!     // the database is local in development
+//!     db: { host: 'localhost', port: 0x1538 },
+//!     features: ["search", "vectors",],
+//! }"#;
+//! let config = deserr::json5::deserialize::<Config, JsonError>(text).unwrap();
+//! assert_eq!(config.db, Db { host: String::from("localhost"), port: 5432 });
+//!
+//! let error = deserr::json5::deserialize::<Config, JsonError>("{ db: { host: 'localhost' port: 5432 } }").unwrap_err();
+//! assert_eq!(error.to_string(), "Invalid JSON at line 1, column 27: expected `,` or `}`");
+//! ```
+
+use std::convert::Infallible;
+use std::fmt::Display;
+
+use crate::ordered_map::{IntoIter, OrderedMap};
+use crate::value::{decimal_text, exact_float};
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, Value, ValueKind,
+    ValuePointer, ValuePointerComponent, ValuePointerRef,
+};
+
+/// Parse the given JSON5 text and deserialize it. The syntax errors are reported as an
+/// [`ErrorKind::Syntax`].
+pub fn deserialize<T, E>(text: &str) -> Result<T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    let value = parse(text).map_err(|error| {
+        take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Syntax {
+                msg: error.msg,
+                line: error.line,
+                column: error.column,
+            },
+            ValuePointerRef::Origin,
+        ))
+    })?;
+    crate::deserialize(value)
+}
+
+/// Parse the given JSON5 text.
+pub fn parse(text: &str) -> Result<Spanned, Json5SyntaxError> {
+    let mut parser = Parser {
+        text,
+        offset: 0,
+        line: 1,
+        column: 0,
+        depth: 0,
+    };
+    parser.skip_whitespace()?;
+    let value = parser.value()?;
+    parser.skip_whitespace()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+/// A syntax error found while parsing a JSON5 text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Json5SyntaxError {
+    pub msg: String,
+    /// The line of the error, starting from 1.
+    pub line: usize,
+    /// The column of the error, in characters, starting from 1.
+    pub column: usize,
+}
+
+impl Display for Json5SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {} column {}",
+            self.msg, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for Json5SyntaxError {}
+
+/// A position in a JSON5 text. The lines and the columns, in characters, start from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The positions of the first and the last characters of a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// A JSON5 value with its position in the text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned {
+    pub value: Json5Value,
+    pub span: Span,
+}
+
+impl Spanned {
+    /// Return the span of the value at the given location, if there is one.
+    ///
+    /// It can be used to show where an error happened, with an error type keeping its
+    /// location such as [`LayeredError`](crate::errors::LayeredError).
+    pub fn span_of(&self, location: &ValuePointer) -> Option<Span> {
+        let mut value = self;
+        for component in &location.path {
+            value = match (component, &value.value) {
+                (ValuePointerComponent::Key(key), Json5Value::Object(map)) => map.0.get(key)?,
+                (ValuePointerComponent::Index(index), Json5Value::Array(seq)) => seq.get(*index)?,
+                _ => return None,
+            };
+        }
+        Some(value.span)
+    }
+}
+
+/// A value parsed from a JSON5 text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json5Value {
+    Null,
+    Boolean(bool),
+    Integer(u64),
+    NegativeInteger(i64),
+    Float(f64),
+    /// A number which doesn't fit in the other variants, or, with the `arbitrary-precision`
+    /// feature, a decimal number which would be changed by a float, as its exact text, see
+    /// [`Value::Number`].
+    Number(String),
+    String(String),
+    Array(Vec<Spanned>),
+    Object(Json5Map),
+}
+
+/// The members of a JSON5 object, in the order of the text. If a key is specified multiple
+/// times, its last value is kept.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Json5Map(OrderedMap<Spanned>);
+
+impl Map for Json5Map {
+    type Value = Spanned;
+    type Iter = IntoIter<Spanned>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        self.0.remove(key)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        self.0.into_iter()
+    }
+}
+
+impl IntoValue for Spanned {
+    type Sequence = Vec<Spanned>;
+    type Map = Json5Map;
+
+    fn kind(&self) -> ValueKind {
+        match self.value {
+            Json5Value::Null => ValueKind::Null,
+            Json5Value::Boolean(_) => ValueKind::Boolean,
+            Json5Value::Integer(_) => ValueKind::Integer,
+            Json5Value::NegativeInteger(_) => ValueKind::NegativeInteger,
+            Json5Value::Float(_) => ValueKind::Float,
+            Json5Value::Number(_) => ValueKind::Number,
+            Json5Value::String(_) => ValueKind::String,
+            Json5Value::Array(_) => ValueKind::Sequence,
+            Json5Value::Object(_) => ValueKind::Map,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self.value {
+            Json5Value::Null => Value::Null,
+            Json5Value::Boolean(b) => Value::Boolean(b),
+            Json5Value::Integer(x) => Value::Integer(x),
+            Json5Value::NegativeInteger(x) => Value::NegativeInteger(x),
+            Json5Value::Float(x) => Value::Float(x),
+            Json5Value::Number(x) => Value::Number(x),
+            Json5Value::String(s) => Value::String(s),
+            Json5Value::Array(seq) => Value::Sequence(seq),
+            Json5Value::Object(map) => Value::Map(map),
+        }
+    }
+}
+
+/// The maximum number of nested objects and arrays, to not overflow the stack while parsing.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    text: &'a str,
+    offset: usize,
+    /// The position of the last character read.
+    line: usize,
+    column: usize,
+    /// The number of objects and arrays containing the value being parsed.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.offset..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 0;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The position of the next character.
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column + 1,
+        }
+    }
+
+    /// An error at the next character, or at the last one at the end of the text.
+    fn error(&self, msg: &str) -> Json5SyntaxError {
+        let column = if self.peek().is_some() {
+            self.column + 1
+        } else {
+            self.column
+        };
+        Json5SyntaxError {
+            msg: msg.to_owned(),
+            line: self.line,
+            column,
+        }
+    }
+
+    /// Skip the whitespaces and the comments.
+    fn skip_whitespace(&mut self) -> Result<(), Json5SyntaxError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() || c == '\u{feff}' => {
+                    self.bump();
+                }
+                Some('/') if self.text[self.offset..].starts_with("//") => {
+                    while !matches!(self.bump(), Some('\n') | None) {}
+                }
+                Some('/') if self.text[self.offset..].starts_with("/*") => {
+                    self.bump();
+                    self.bump();
+                    while !self.text[self.offset..].starts_with("*/") {
+                        if self.bump().is_none() {
+                            return Err(self.error("EOF while parsing a comment"));
+                        }
+                    }
+                    self.bump();
+                    self.bump();
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Spanned, Json5SyntaxError> {
+        let start = self.position();
+        let value = match self.peek() {
+            None => return Err(self.error("EOF while parsing a value")),
+            Some('{' | '[') if self.depth == MAX_DEPTH => {
+                return Err(self.error("recursion limit exceeded"))
+            }
+            Some(c @ ('{' | '[')) => {
+                self.depth += 1;
+                let value = if c == '{' {
+                    self.object()
+                } else {
+                    self.array()
+                };
+                self.depth -= 1;
+                value?
+            }
+            Some(quote @ ('"' | '\'')) => Json5Value::String(self.string(quote)?),
+            Some(c) if c.is_ascii_digit() || matches!(c, '+' | '-' | '.') => self.number()?,
+            Some(c) if is_identifier_start(c) => match self.identifier().as_str() {
+                "null" => Json5Value::Null,
+                "true" => Json5Value::Boolean(true),
+                "false" => Json5Value::Boolean(false),
+                "Infinity" => Json5Value::Float(f64::INFINITY),
+                "NaN" => Json5Value::Float(f64::NAN),
+                _ => {
+                    return Err(Json5SyntaxError {
+                        msg: String::from("expected value"),
+                        line: start.line,
+                        column: start.column,
+                    })
+                }
+            },
+            Some(_) => return Err(self.error("expected value")),
+        };
+        let end = Position {
+            line: self.line,
+            column: self.column,
+        };
+        Ok(Spanned {
+            value,
+            span: Span { start, end },
+        })
+    }
+
+    fn object(&mut self) -> Result<Json5Value, Json5SyntaxError> {
+        self.bump();
+        let mut map = Json5Map::default();
+        loop {
+            self.skip_whitespace()?;
+            let key = match self.peek() {
+                Some('}') => break,
+                Some(quote @ ('"' | '\'')) => self.string(quote)?,
+                Some(c) if is_identifier_start(c) => self.identifier(),
+                None => return Err(self.error("EOF while parsing an object")),
+                Some(_) => return Err(self.error("key must be a string or an identifier")),
+            };
+            self.skip_whitespace()?;
+            if !self.eat(':') {
+                return Err(match self.peek() {
+                    None => self.error("EOF while parsing an object"),
+                    Some(_) => self.error("expected `:`"),
+                });
+            }
+            self.skip_whitespace()?;
+            let value = self.value()?;
+            map.0.insert(key, value);
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some('}') => break,
+                None => return Err(self.error("EOF while parsing an object")),
+                Some(_) => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+        self.bump();
+        Ok(Json5Value::Object(map))
+    }
+
+    fn array(&mut self) -> Result<Json5Value, Json5SyntaxError> {
+        self.bump();
+        let mut seq = Vec::new();
+        loop {
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(']') => break,
+                None => return Err(self.error("EOF while parsing a list")),
+                Some(_) => seq.push(self.value()?),
+            }
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some(']') => break,
+                None => return Err(self.error("EOF while parsing a list")),
+                Some(_) => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+        self.bump();
+        Ok(Json5Value::Array(seq))
+    }
+
+    fn identifier(&mut self) -> String {
+        let mut identifier = String::new();
+        while let Some(c) = self.peek().filter(|c| is_identifier_part(*c)) {
+            self.bump();
+            identifier.push(c);
+        }
+        identifier
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, Json5SyntaxError> {
+        self.bump();
+        let mut s = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("EOF while parsing a string")),
+                Some('\n' | '\r') => return Err(self.error("unescaped newline in a string")),
+                Some('\\') => {
+                    self.bump();
+                    if let Some(c) = self.escape()? {
+                        s.push(c);
+                    }
+                }
+                Some(c) => {
+                    self.bump();
+                    if c == quote {
+                        return Ok(s);
+                    }
+                    s.push(c);
+                }
+            }
+        }
+    }
+
+    /// Parse the escape sequence following a backslash. An escaped newline is a line
+    /// continuation and doesn't produce any character.
+    fn escape(&mut self) -> Result<Option<char>, Json5SyntaxError> {
+        let c = match self.peek() {
+            None => return Err(self.error("EOF while parsing a string")),
+            Some(c) if c.is_ascii_digit() && c != '0' => {
+                return Err(self.error("invalid escape"));
+            }
+            Some('0') if self.text[self.offset + 1..].starts_with(|c: char| c.is_ascii_digit()) => {
+                return Err(self.error("invalid escape"));
+            }
+            Some('x') => {
+                self.bump();
+                let code = self.hex_digits(2)?;
+                return Ok(char::from_u32(code));
+            }
+            Some('u') => {
+                self.bump();
+                let mut code = self.hex_digits(4)?;
+                if (0xD800..0xDC00).contains(&code) && self.text[self.offset..].starts_with("\\u") {
+                    self.bump();
+                    self.bump();
+                    let low = self.hex_digits(4)?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(self.error("invalid unicode code point"));
+                    }
+                    code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                }
+                return match char::from_u32(code) {
+                    Some(c) => Ok(Some(c)),
+                    None => Err(self.error("invalid unicode code point")),
+                };
+            }
+            Some(c) => c,
+        };
+        self.bump();
+        Ok(match c {
+            'b' => Some('\u{8}'),
+            'f' => Some('\u{c}'),
+            'n' => Some('\n'),
+            'r' => {
+                // `\` followed by `\r\n` is a single line continuation
+                self.eat('\n');
+                None
+            }
+            't' => Some('\t'),
+            'v' => Some('\u{b}'),
+            '0' => Some('\0'),
+            '\n' | '\r' | '\u{2028}' | '\u{2029}' => None,
+            c => Some(c),
+        })
+    }
+
+    fn hex_digits(&mut self, count: usize) -> Result<u32, Json5SyntaxError> {
+        let mut code = 0;
+        for _ in 0..count {
+            match self.peek().and_then(|c| c.to_digit(16)) {
+                Some(digit) => {
+                    self.bump();
+                    code = code * 16 + digit;
+                }
+                None => return Err(self.error("invalid escape")),
+            }
+        }
+        Ok(code)
+    }
+
+    fn number(&mut self) -> Result<Json5Value, Json5SyntaxError> {
+        let start = self.offset;
+        let negative = self.peek() == Some('-');
+        if matches!(self.peek(), Some('+' | '-')) {
+            self.bump();
+        }
+        if self.peek().is_some_and(is_identifier_start) {
+            return match self.identifier().as_str() {
+                "Infinity" if negative => Ok(Json5Value::Float(f64::NEG_INFINITY)),
+                "Infinity" => Ok(Json5Value::Float(f64::INFINITY)),
+                "NaN" => Ok(Json5Value::Float(f64::NAN)),
+                _ => Err(self.error("invalid number")),
+            };
+        }
+
+        let digits_start = self.offset;
+        let hexadecimal = self.text[self.offset..].starts_with("0x")
+            || self.text[self.offset..].starts_with("0X");
+        if hexadecimal {
+            self.bump();
+            self.bump();
+            let digits_start = self.offset;
+            while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                self.bump();
+            }
+            let digits = &self.text[digits_start..self.offset];
+            if digits.is_empty() {
+                return Err(self.error("invalid number"));
+            }
+            let digits = match u128::from_str_radix(digits, 16) {
+                Ok(x) => x.to_string(),
+                Err(_) => {
+                    let digits = format!("{}{digits}", "0".repeat(digits.len() % 2));
+                    let bytes = (0..digits.len())
+                        .step_by(2)
+                        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap_or_default())
+                        .collect::<Vec<_>>();
+                    decimal_text(&bytes)
+                }
+            };
+            return Ok(integer_value(&digits, negative));
+        }
+
+        let mut float = false;
+        while let Some(c) = self.peek() {
+            match c {
+                '0'..='9' => (),
+                '.' | 'e' | 'E' => float = true,
+                '+' | '-' if matches!(self.text[..self.offset].chars().last(), Some('e' | 'E')) => {
+                }
+                _ => break,
+            }
+            self.bump();
+        }
+        let literal = &self.text[start..self.offset];
+        let digits = &self.text[digits_start..self.offset];
+        if digits.is_empty() || digits == "." {
+            return Err(self.error("invalid number"));
+        }
+        // like in JSON, an integer part can't have leading zeros
+        let integer_part = digits.split(['.', 'e', 'E']).next().unwrap_or_default();
+        if integer_part.len() > 1 && integer_part.starts_with('0') {
+            return Err(self.error("invalid number"));
+        }
+        if !float {
+            return Ok(integer_value(digits, negative));
+        }
+        let literal = normalize_decimal(literal);
+        match literal.parse::<f64>() {
+            Ok(_) if cfg!(feature = "arbitrary-precision") && exact_float(&literal).is_none() => {
+                Ok(Json5Value::Number(literal))
+            }
+            Ok(x) if x.is_finite() => Ok(Json5Value::Float(x)),
+            Ok(_) => Err(self.error("number out of range")),
+            Err(_) => Err(self.error("invalid number")),
+        }
+    }
+}
+
+/// Return the value of an integer given by its decimal digits. The integers which don't fit
+/// in 64 bits are kept as text.
+fn integer_value(digits: &str, negative: bool) -> Json5Value {
+    match digits.parse::<u64>() {
+        Ok(x) if !negative => return Json5Value::Integer(x),
+        Ok(x) => {
+            if let Some(x) = 0i64.checked_sub_unsigned(x) {
+                return Json5Value::NegativeInteger(x);
+            }
+        }
+        Err(_) => (),
+    }
+    let digits = digits.trim_start_matches('0');
+    let sign = if negative { "-" } else { "" };
+    Json5Value::Number(format!("{sign}{digits}"))
+}
+
+/// Write a decimal number as in JSON, without explicit plus sign, and with digits before and
+/// after its decimal point, e.g. `+.5` becomes `0.5`.
+fn normalize_decimal(literal: &str) -> String {
+    let literal = literal.strip_prefix('+').unwrap_or(literal);
+    let (sign, literal) = match literal.strip_prefix('-') {
+        Some(literal) => ("-", literal),
+        None => ("", literal),
+    };
+    let (mantissa, exponent) = match literal.find(['e', 'E']) {
+        Some(index) => literal.split_at(index),
+        None => (literal, ""),
+    };
+    let leading_zero = if mantissa.starts_with('.') { "0" } else { "" };
+    let trailing_zero = if mantissa.ends_with('.') { "0" } else { "" };
+    format!("{sign}{leading_zero}{mantissa}{trailing_zero}{exponent}")
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '$' || c == '_'
+}
+
+fn is_identifier_part(c: char) -> bool {
+    c.is_alphanumeric() || c == '$' || c == '_' || c == '\u{200c}' || c == '\u{200d}'
+}
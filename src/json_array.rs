@@ -0,0 +1,661 @@
+//! Deserialize the elements of a JSON array one at a time, instead of reading the whole
+//! array in memory.
+//!
+//! A [`Deserr`] type is deserialized from a value that is already parsed, so deserializing a
+//! `Vec<T>` requires the whole array. Instead, the payload containing the array is read
+//! lazily: each element is parsed and deserialized on its own into a `Result<T, E>` when the
+//! iterator or the stream reaches it. Only the element being read is kept in memory.
+//!
+//! There is no lazy [`Deserr`] type, such as a `Stream<T>` field yielding its elements on
+//! demand: [`Deserr::deserialize_from_value`] is given a [`Value`](crate::Value) that the
+//! backend has already parsed, and [`Sequence::len`](crate::Sequence::len) needs the whole
+//! sequence upfront. It would require a pull-based parsing API in every backend, so only the
+//! readers and streams of this module, which parse the payload themselves, are lazy.
+//!
+//! An element is read entirely in memory before being deserialized. To bound the memory used
+//! by a payload coming from the users, limit the length of the elements with
+//! [`JsonArrayReader::max_element_length`].
+//!
+//! The errors of an element are located at its index in the array, e.g. `[2].id`, and the
+//! syntax errors give their position in the whole payload. A syntax error outside of the
+//! elements, such as a missing comma or bracket, ends the iteration.
+//!
+//! The array can also be nested in objects, such as the `documents` of
+//! `{ "documents": [...] }`, by giving the keys leading to it with
+//! [`JsonArrayReader::key_path`].
+//!
+//! By default, the next elements are still deserialized after an invalid element, so that
+//! every invalid element is reported. See [`JsonArrayReader::stop_on_error`] to stop at the
+//! first error instead.
+//!
+//! ```
+//! use deserr::{errors::JsonError, Deserr};
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Document {
+//!     id: u32,
+//! }
+//!
+//! let payload = r#"[{ "id": 1 }, { "id": "doggo" }, { "id": 3 }]"#;
+//! let mut documents = deserr::json_array::from_reader::<Document, JsonError, _>(payload.as_bytes());
+//!
+//! assert_eq!(documents.next().unwrap().unwrap(), Document { id: 1 });
+//! assert_eq!(
+//!     documents.next().unwrap().unwrap_err().to_string(),
+//!     "Invalid value type at `[1].id`: expected a positive integer, but found a string: `\"doggo\"`"
+//! );
+//! assert_eq!(documents.next().unwrap().unwrap(), Document { id: 3 });
+//! assert!(documents.next().is_none());
+//! ```
+
+use std::convert::Infallible;
+use std::io::{BufRead, BufReader, Read};
+use std::marker::PhantomData;
+
+use crate::ndjson::read_error;
+use crate::serde_json::syntax_error_at;
+use crate::{take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, ValuePointerRef};
+
+/// Deserialize the elements of the array read from the given reader, see
+/// [`JsonArrayReader`].
+pub fn from_reader<T, E, R>(reader: R) -> JsonArrayReader<BufReader<R>, T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+    R: Read,
+{
+    JsonArrayReader::new(BufReader::new(reader))
+}
+
+/// Deserialize the elements of the array contained in the given stream of chunks of bytes,
+/// see [`JsonArrayStream`].
+#[cfg(feature = "futures")]
+pub fn from_stream<T, E, S>(stream: S) -> JsonArrayStream<S, T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    JsonArrayStream::new(stream)
+}
+
+/// Where the [`Scanner`] is in the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Before the array, or before the object containing the next key of the key path.
+    Start,
+    /// Before a key of an object of the key path, or its closing brace.
+    Key,
+    /// Inside a key of an object of the key path.
+    InKey,
+    /// Before the colon following a key.
+    Colon,
+    /// Inside the value of a key which is not the next key of the key path.
+    Skip,
+    /// Before the first element, or the closing bracket of an empty array.
+    First,
+    /// Before an element following a comma.
+    Next,
+    /// Inside an element.
+    Element,
+    /// After the closing bracket, inside the objects of the key path.
+    Tail,
+    /// After the closing bracket and the objects of the key path.
+    End,
+}
+
+/// What the [`Scanner`] found in the bytes given to it.
+#[derive(Debug)]
+enum Step {
+    /// The bytes of a complete element, with the position of its first byte.
+    Element {
+        bytes: Vec<u8>,
+        index: usize,
+        line: usize,
+        column: usize,
+    },
+    /// An object of the key path doesn't contain the key at the given level of the path.
+    MissingKey { level: usize },
+    /// The element at the given index is longer than the limit.
+    ElementTooLong { index: usize, limit: usize },
+    /// A key of the object at the given level of the key path is longer than the limit.
+    KeyTooLong { level: usize, limit: usize },
+    /// A syntax error outside of the elements.
+    Error {
+        msg: &'static str,
+        line: usize,
+        column: usize,
+    },
+}
+
+impl Step {
+    fn is_element(&self) -> bool {
+        matches!(self, Step::Element { .. })
+    }
+
+    fn deserialize<T, E>(self, path: &[String]) -> Result<T, E>
+    where
+        T: Deserr<E>,
+        E: DeserializeError,
+    {
+        match self {
+            Step::Element {
+                bytes,
+                index,
+                line,
+                column,
+            } => at_path(ValuePointerRef::Origin, path, |array| {
+                let location = array.push_index(index);
+                match serde_json::from_slice::<serde_json::Value>(&bytes) {
+                    Ok(value) => T::deserialize_from_value(value.into_value(), location),
+                    Err(error) => {
+                        // the position of the error is relative to the start of the element
+                        let (line, column) = match error.line() {
+                            1 => (line, column + error.column().saturating_sub(1)),
+                            error_line => (line + error_line - 1, error.column()),
+                        };
+                        Err(syntax_error_at(error, line, column, location))
+                    }
+                }
+            }),
+            Step::MissingKey { level } => {
+                at_path(ValuePointerRef::Origin, &path[..level], |object| {
+                    Err(take_cf_content(E::error::<Infallible>(
+                        None,
+                        ErrorKind::MissingField {
+                            field: &path[level],
+                        },
+                        object,
+                    )))
+                })
+            }
+            Step::ElementTooLong { index, limit } => {
+                at_path(ValuePointerRef::Origin, path, |array| {
+                    Err(too_long("element", limit, array.push_index(index)))
+                })
+            }
+            Step::KeyTooLong { level, limit } => {
+                at_path(ValuePointerRef::Origin, &path[..level], |object| {
+                    Err(too_long("key", limit, object))
+                })
+            }
+            Step::Error { msg, line, column } => Err(take_cf_content(E::error::<Infallible>(
+                None,
+                ErrorKind::Syntax {
+                    msg: msg.to_owned(),
+                    line,
+                    column,
+                },
+                ValuePointerRef::Origin,
+            ))),
+        }
+    }
+}
+
+/// Return the error of an element or a key longer than the limit.
+fn too_long<E: DeserializeError>(what: &str, limit: usize, location: ValuePointerRef) -> E {
+    take_cf_content(E::error::<Infallible>(
+        None,
+        ErrorKind::Unexpected {
+            msg: format!("The {what} is longer than allowed (limit: {limit} bytes)"),
+        },
+        location,
+    ))
+}
+
+/// Call `f` with the location of the value at the end of the given keys.
+fn at_path<R>(
+    location: ValuePointerRef,
+    keys: &[String],
+    f: impl FnOnce(ValuePointerRef) -> R,
+) -> R {
+    match keys.split_first() {
+        Some((key, keys)) => at_path(location.push_key(key), keys, f),
+        None => f(location),
+    }
+}
+
+/// Split the bytes of an array into the bytes of its elements, by following the strings and
+/// the nesting of the values. The elements themselves are validated when they are parsed,
+/// and the values surrounding the array in the objects of the key path are skipped without
+/// being validated.
+#[derive(Debug)]
+struct Scanner {
+    /// The keys leading to the array, in nested objects.
+    path: Vec<String>,
+    /// The number of keys of the path already found.
+    level: usize,
+    state: State,
+    /// The bytes of the current element or key.
+    element: Vec<u8>,
+    /// The maximum length of an element or a key in bytes.
+    max_element_length: Option<usize>,
+    index: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// The position of the last byte read.
+    line: usize,
+    column: usize,
+    /// The position of the first byte of the current element.
+    start: (usize, usize),
+}
+
+impl Scanner {
+    fn new() -> Self {
+        Scanner {
+            path: Vec::new(),
+            level: 0,
+            state: State::Start,
+            element: Vec::new(),
+            max_element_length: None,
+            index: 0,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            line: 1,
+            column: 0,
+            start: (1, 0),
+        }
+    }
+
+    /// Follow the strings and the nesting of the values. Return whether the byte is a comma
+    /// or a closing bracket or brace outside of any string or nested value.
+    fn nest(&mut self, byte: u8) -> bool {
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if byte == b'\\' {
+                self.escaped = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+            }
+            return false;
+        }
+        match byte {
+            b'"' => self.in_string = true,
+            b'[' | b'{' => self.depth += 1,
+            b']' | b'}' if self.depth > 0 => self.depth -= 1,
+            b',' | b']' | b'}' if self.depth == 0 => return true,
+            _ => (),
+        }
+        false
+    }
+
+    /// Whether the current element or key is longer than the limit.
+    fn too_long(&self) -> Option<usize> {
+        self.max_element_length
+            .filter(|limit| self.element.len() > *limit)
+    }
+
+    /// The state following the closing bracket of the array.
+    fn after_array(&mut self) -> State {
+        if self.path.is_empty() {
+            State::End
+        } else {
+            self.depth = self.path.len();
+            State::Tail
+        }
+    }
+
+    /// Read the given bytes until the end of an element or an error.
+    /// Return the number of bytes read.
+    fn scan(&mut self, input: &[u8]) -> (usize, Option<Step>) {
+        for (i, &byte) in input.iter().enumerate() {
+            if byte == b'\n' {
+                self.line += 1;
+                self.column = 0;
+            } else {
+                self.column += 1;
+            }
+            let (line, column) = (self.line, self.column);
+            let error = |msg| Step::Error { msg, line, column };
+            let inside_value = matches!(
+                self.state,
+                State::InKey | State::Skip | State::Element | State::Tail
+            );
+            if !inside_value && byte.is_ascii_whitespace() {
+                continue;
+            }
+
+            match self.state {
+                State::Start if self.level < self.path.len() => {
+                    if byte != b'{' {
+                        return (i + 1, Some(error("expected an object")));
+                    }
+                    self.state = State::Key;
+                    continue;
+                }
+                State::Start if byte == b'[' => {
+                    self.state = State::First;
+                    continue;
+                }
+                State::Start => return (i + 1, Some(error("expected an array"))),
+                State::Key if byte == b'"' => {
+                    self.state = State::InKey;
+                    self.in_string = true;
+                    self.element.push(byte);
+                    continue;
+                }
+                State::Key if byte == b'}' => {
+                    let step = Step::MissingKey { level: self.level };
+                    return (i + 1, Some(step));
+                }
+                State::Key => return (i + 1, Some(error("key must be a string"))),
+                State::InKey => {
+                    self.nest(byte);
+                    self.element.push(byte);
+                    if let Some(limit) = self.too_long() {
+                        let step = Step::KeyTooLong {
+                            level: self.level,
+                            limit,
+                        };
+                        return (i + 1, Some(step));
+                    }
+                    if !self.in_string {
+                        self.state = State::Colon;
+                    }
+                    continue;
+                }
+                State::Colon if byte == b':' => {
+                    let key = serde_json::from_slice::<String>(&self.element);
+                    self.element.clear();
+                    if key.is_ok_and(|key| key == self.path[self.level]) {
+                        self.level += 1;
+                        self.state = State::Start;
+                    } else {
+                        self.state = State::Skip;
+                    }
+                    continue;
+                }
+                State::Colon => return (i + 1, Some(error("expected `:`"))),
+                State::Skip => {
+                    if self.nest(byte) {
+                        match byte {
+                            b',' => self.state = State::Key,
+                            b'}' => {
+                                let step = Step::MissingKey { level: self.level };
+                                return (i + 1, Some(step));
+                            }
+                            _ => return (i + 1, Some(error("expected `,` or `}`"))),
+                        }
+                    }
+                    continue;
+                }
+                State::Tail => {
+                    self.nest(byte);
+                    if self.depth == 0 {
+                        self.state = State::End;
+                    }
+                    continue;
+                }
+                State::End => return (i + 1, Some(error("trailing characters"))),
+                State::First if byte == b']' => {
+                    self.state = self.after_array();
+                    continue;
+                }
+                State::Next if byte == b']' => return (i + 1, Some(error("trailing comma"))),
+                State::First | State::Next => {
+                    self.state = State::Element;
+                    self.start = (self.line, self.column);
+                }
+                State::Element => (),
+            }
+
+            // a closing brace at the top of an element is left to the parsing of the element
+            if self.nest(byte) && byte != b'}' {
+                self.state = if byte == b',' {
+                    State::Next
+                } else {
+                    self.after_array()
+                };
+                let (line, column) = self.start;
+                let step = Step::Element {
+                    bytes: std::mem::take(&mut self.element),
+                    index: self.index,
+                    line,
+                    column,
+                };
+                self.index += 1;
+                return (i + 1, Some(step));
+            }
+            self.element.push(byte);
+            if let Some(limit) = self.too_long() {
+                let step = Step::ElementTooLong {
+                    index: self.index,
+                    limit,
+                };
+                return (i + 1, Some(step));
+            }
+        }
+        (input.len(), None)
+    }
+
+    /// Return the syntax error, if any, at the end of the input.
+    fn finish(&self) -> Option<Step> {
+        let msg = match self.state {
+            State::End => return None,
+            State::Start => "EOF while parsing a value",
+            State::First | State::Next | State::Element => "EOF while parsing a list",
+            State::Key | State::InKey | State::Colon | State::Skip | State::Tail => {
+                "EOF while parsing an object"
+            }
+        };
+        Some(Step::Error {
+            msg,
+            line: self.line,
+            column: self.column,
+        })
+    }
+}
+
+/// An iterator deserializing the elements of an array read from a reader, yielding a
+/// `Result<T, E>` per element.
+///
+/// The iteration ends after an error of the reader, or after an element longer than the
+/// limit.
+pub struct JsonArrayReader<R, T, E> {
+    reader: R,
+    scanner: Scanner,
+    stop_on_error: bool,
+    done: bool,
+    _phantom: PhantomData<fn() -> (T, E)>,
+}
+
+impl<R: BufRead, T, E> JsonArrayReader<R, T, E> {
+    pub fn new(reader: R) -> Self {
+        JsonArrayReader {
+            reader,
+            scanner: Scanner::new(),
+            stop_on_error: false,
+            done: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Set the keys leading to the array, when it is nested in objects, e.g. `["documents"]`
+    /// for `{ "documents": [...] }`. The errors of the elements are then located at their
+    /// index in the nested array, e.g. `.documents[2].id`, and a missing key is reported as
+    /// an [`ErrorKind::MissingField`]. By default the payload is the array itself.
+    pub fn key_path<I>(mut self, path: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.scanner.path = path.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the maximum length of an element in bytes, and of the keys of the key path. A
+    /// longer element yields an error and ends the iteration, without being read further. By
+    /// default the length of the elements is not limited.
+    pub fn max_element_length(mut self, max_element_length: usize) -> Self {
+        self.scanner.max_element_length = Some(max_element_length);
+        self
+    }
+
+    /// Whether the iteration ends after the first invalid element. By default, every
+    /// element is deserialized.
+    pub fn stop_on_error(mut self, stop_on_error: bool) -> Self {
+        self.stop_on_error = stop_on_error;
+        self
+    }
+}
+
+impl<R, T, E> Iterator for JsonArrayReader<R, T, E>
+where
+    R: BufRead,
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let input = match self.reader.fill_buf() {
+                Ok(input) => input,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(read_error(error)));
+                }
+            };
+            let step = if input.is_empty() {
+                self.done = true;
+                self.scanner.finish()?
+            } else {
+                let (consumed, step) = self.scanner.scan(input);
+                self.reader.consume(consumed);
+                match step {
+                    Some(step) => step,
+                    None => continue,
+                }
+            };
+            let is_element = step.is_element();
+            let result = step.deserialize(&self.scanner.path);
+            self.done |= !is_element || (self.stop_on_error && result.is_err());
+            return Some(result);
+        }
+        None
+    }
+}
+
+/// A [`Stream`](futures::Stream) deserializing the elements of an array contained in a
+/// stream of chunks of bytes, such as the body of a request, yielding a `Result<T, E>` per
+/// element.
+///
+/// The stream ends after an error of the underlying stream, or after an element longer than
+/// the limit.
+#[cfg(feature = "futures")]
+pub struct JsonArrayStream<S, T, E> {
+    stream: S,
+    chunk: Vec<u8>,
+    /// The number of bytes of the chunk already read.
+    read: usize,
+    scanner: Scanner,
+    stop_on_error: bool,
+    /// Whether the underlying stream has ended.
+    ended: bool,
+    done: bool,
+    _phantom: PhantomData<fn() -> (T, E)>,
+}
+
+#[cfg(feature = "futures")]
+impl<S, T, E> JsonArrayStream<S, T, E> {
+    pub fn new(stream: S) -> Self {
+        JsonArrayStream {
+            stream,
+            chunk: Vec::new(),
+            read: 0,
+            scanner: Scanner::new(),
+            stop_on_error: false,
+            ended: false,
+            done: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Set the keys leading to the array, when it is nested in objects, e.g. `["documents"]`
+    /// for `{ "documents": [...] }`. The errors of the elements are then located at their
+    /// index in the nested array, e.g. `.documents[2].id`, and a missing key is reported as
+    /// an [`ErrorKind::MissingField`]. By default the payload is the array itself.
+    pub fn key_path<I>(mut self, path: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.scanner.path = path.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the maximum length of an element in bytes, and of the keys of the key path. A
+    /// longer element yields an error and ends the stream, without being read further. By
+    /// default the length of the elements is not limited.
+    pub fn max_element_length(mut self, max_element_length: usize) -> Self {
+        self.scanner.max_element_length = Some(max_element_length);
+        self
+    }
+
+    /// Whether the stream ends after the first invalid element. By default, every element
+    /// is deserialized.
+    pub fn stop_on_error(mut self, stop_on_error: bool) -> Self {
+        self.stop_on_error = stop_on_error;
+        self
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<S, B, SE, T, E> futures::Stream for JsonArrayStream<S, T, E>
+where
+    S: futures::Stream<Item = Result<B, SE>> + Unpin,
+    B: AsRef<[u8]>,
+    SE: std::fmt::Display,
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        use futures::StreamExt;
+        use std::task::Poll;
+
+        let this = &mut *self;
+        while !this.done {
+            let step = if this.read < this.chunk.len() {
+                let (consumed, step) = this.scanner.scan(&this.chunk[this.read..]);
+                this.read += consumed;
+                match step {
+                    Some(step) => step,
+                    None => continue,
+                }
+            } else if this.ended {
+                this.done = true;
+                match this.scanner.finish() {
+                    Some(step) => step,
+                    None => break,
+                }
+            } else {
+                match futures::ready!(this.stream.poll_next_unpin(cx)) {
+                    Some(Ok(chunk)) => {
+                        this.chunk.clear();
+                        this.chunk.extend_from_slice(chunk.as_ref());
+                        this.read = 0;
+                    }
+                    Some(Err(error)) => {
+                        this.done = true;
+                        return Poll::Ready(Some(Err(read_error(error))));
+                    }
+                    None => this.ended = true,
+                }
+                continue;
+            };
+            let is_element = step.is_element();
+            let result = step.deserialize(&this.scanner.path);
+            this.done |= !is_element || (this.stop_on_error && result.is_err());
+            return Poll::Ready(Some(result));
+        }
+        Poll::Ready(None)
+    }
+}
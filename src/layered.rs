@@ -0,0 +1,314 @@
+//! A layered configuration backend for deserr.
+//!
+//! Several sources, such as the default values, a configuration file, the environment
+//! variables and the command line, are merged into a single [`LayeredValue`]. Each layer
+//! takes precedence over the previous ones: the maps are merged key by key, and any other
+//! value, including the sequences, replaces the value of the previous layers.
+//!
+//! The source of each value is remembered, so that a [`LayeredError`] can tell where an
+//! invalid value comes from.
+//!
+//! ```
+//! use deserr::{env::EnvConfig, layered::Layers, Deserr};
+//! use serde_json::json;
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Config {
+//!     db: Db,
+//! }
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Db {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! let defaults = json!({ "db": { "host": "localhost", "port": 5432 } });
+//! let file = json!({ "db": { "host": "db.example.com" } });
+//! let vars = [(String::from("APP_DB_PORT"), String::from("6432"))];
+//! let env = EnvConfig::new("APP").separator("_");
+//!
+//! let layers = Layers::new()
+//!     .layer("defaults", defaults.clone())
+//!     .layer("file `config.json`", file.clone())
+//!     .env(vars, &env);
+//! let config = layers.deserialize::<Config>().unwrap();
+//! assert_eq!(config.db, Db { host: String::from("db.example.com"), port: 6432 });
+//!
+//! let vars = [(String::from("APP_DB_PORT"), String::from("doggo"))];
+//! let layers = Layers::new()
+//!     .layer("defaults", defaults)
+//!     .layer("file `config.json`", file)
+//!     .env(vars, &env);
+//! let error = layers.deserialize::<Config>().unwrap_err();
+//! assert_eq!(
+//!     error.to_string(),
+//!     "`db.port` from environment variable APP_DB_PORT: expected a positive integer, but found a string: `\"doggo\"`"
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::env::{EnvConfig, EnvValue};
+use crate::errors::LayeredError;
+use crate::{
+    Deserr, IntoValue, Map, Sequence, Value, ValueKind, ValuePointer, ValuePointerComponent,
+};
+
+/// The layers of a configuration, and the source of each of their values.
+#[derive(Debug, Clone, Default)]
+pub struct Layers {
+    value: LayeredValue,
+    sources: BTreeMap<ValuePointer, String>,
+}
+
+impl Layers {
+    pub fn new() -> Self {
+        Layers::default()
+    }
+
+    /// Add a layer taking precedence over the previous ones. Its values are described as
+    /// coming from the given source, e.g. ``"file `config.toml`"``.
+    pub fn layer<V: IntoValue>(self, source: impl Into<String>, value: V) -> Self {
+        let source = source.into();
+        self.layer_with(value, |_| source.clone())
+    }
+
+    /// Add a layer taking precedence over the previous ones. The source of each of its values
+    /// is described by the given function, from the location of the value.
+    pub fn layer_with<V: IntoValue>(
+        mut self,
+        value: V,
+        describe: impl Fn(&ValuePointer) -> String,
+    ) -> Self {
+        let layer = LayeredValue::from_value(value.into_value());
+        let mut location = ValuePointer::default();
+        merge(
+            &mut self.value,
+            layer,
+            &mut location,
+            &mut self.sources,
+            &describe,
+        );
+        self
+    }
+
+    /// Add the given environment variables as a layer taking precedence over the previous
+    /// ones. Its values are described by the name of their environment variable.
+    pub fn env(self, vars: impl IntoIterator<Item = (String, String)>, config: &EnvConfig) -> Self {
+        let value = EnvValue::from_vars(vars, config);
+        self.layer_with(value, |location| {
+            let keys = location
+                .path
+                .iter()
+                .filter_map(|component| match component {
+                    ValuePointerComponent::Key(key) => Some(key.as_str()),
+                    ValuePointerComponent::Index(_) => None,
+                });
+            format!("environment variable {}", config.variable_name(keys))
+        })
+    }
+
+    /// Return the source of the value at the given location, if it comes from a single layer.
+    ///
+    /// The values inside a sequence have the source of the sequence.
+    pub fn source(&self, location: &ValuePointer) -> Option<&str> {
+        find_source(&self.sources, location)
+    }
+
+    /// Deserialize the merged layers. The errors tell the source of the invalid values.
+    pub fn deserialize<T: Deserr<LayeredError>>(self) -> Result<T, LayeredError> {
+        let Layers { value, sources } = self;
+        crate::deserialize::<T, _, LayeredError>(value).map_err(|error| {
+            let source = find_source(&sources, error.location()).map(str::to_owned);
+            error.with_source(source)
+        })
+    }
+
+    /// Return the merged value, forgetting the source of its values.
+    pub fn into_value(self) -> LayeredValue {
+        self.value
+    }
+}
+
+fn find_source<'a>(
+    sources: &'a BTreeMap<ValuePointer, String>,
+    location: &ValuePointer,
+) -> Option<&'a str> {
+    let mut location = location.clone();
+    loop {
+        if let Some(source) = sources.get(&location) {
+            return Some(source);
+        }
+        match location.path.pop() {
+            Some(ValuePointerComponent::Index(_)) => (),
+            _ => return None,
+        }
+    }
+}
+
+/// Merge the layer into the base value, and record the sources of the new values.
+fn merge(
+    base: &mut LayeredValue,
+    layer: LayeredValue,
+    location: &mut ValuePointer,
+    sources: &mut BTreeMap<ValuePointer, String>,
+    describe: &dyn Fn(&ValuePointer) -> String,
+) {
+    match (base, layer) {
+        (LayeredValue::Map(base), LayeredValue::Map(layer)) => {
+            for (key, value) in layer.0 {
+                location.path.push(ValuePointerComponent::Key(key.clone()));
+                match base.0.get_mut(&key) {
+                    Some(base) => merge(base, value, location, sources, describe),
+                    None => {
+                        record_sources(&value, location, sources, describe);
+                        base.0.insert(key, value);
+                    }
+                }
+                location.path.pop();
+            }
+        }
+        (base, layer) => {
+            sources.retain(|source, _| !source.path.starts_with(&location.path));
+            record_sources(&layer, location, sources, describe);
+            *base = layer;
+        }
+    }
+}
+
+/// Record the source of every value which isn't a map.
+fn record_sources(
+    value: &LayeredValue,
+    location: &mut ValuePointer,
+    sources: &mut BTreeMap<ValuePointer, String>,
+    describe: &dyn Fn(&ValuePointer) -> String,
+) {
+    match value {
+        LayeredValue::Map(map) => {
+            for (key, value) in &map.0 {
+                location.path.push(ValuePointerComponent::Key(key.clone()));
+                record_sources(value, location, sources, describe);
+                location.path.pop();
+            }
+        }
+        _ => {
+            sources.insert(location.clone(), describe(location));
+        }
+    }
+}
+
+/// A value merged from several layers.
+///
+/// The strings are coerced into numbers, booleans, and comma-separated sequences when
+/// needed, since some sources, such as the environment variables, only contain strings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayeredValue {
+    Null,
+    Boolean(bool),
+    Integer(u64),
+    NegativeInteger(i64),
+    Float(f64),
+    Number(String),
+    String(String),
+    Bytes(Vec<u8>),
+    Sequence(Vec<LayeredValue>),
+    Map(LayeredMap),
+}
+
+impl Default for LayeredValue {
+    fn default() -> Self {
+        LayeredValue::Map(LayeredMap::default())
+    }
+}
+
+impl LayeredValue {
+    fn from_value<V: IntoValue>(value: Value<V>) -> Self {
+        match value {
+            Value::Null => LayeredValue::Null,
+            Value::Boolean(b) => LayeredValue::Boolean(b),
+            Value::Integer(x) => LayeredValue::Integer(x),
+            Value::NegativeInteger(x) => LayeredValue::NegativeInteger(x),
+            Value::Float(x) => LayeredValue::Float(x),
+            Value::Number(x) => LayeredValue::Number(x),
+            Value::String(s) => LayeredValue::String(s),
+            Value::Bytes(bytes) => LayeredValue::Bytes(bytes),
+            Value::Sequence(seq) => LayeredValue::Sequence(
+                seq.into_iter()
+                    .map(|value| LayeredValue::from_value(value.into_value()))
+                    .collect(),
+            ),
+            Value::Map(map) => LayeredValue::Map(LayeredMap(
+                map.into_iter()
+                    .map(|(key, value)| (key, LayeredValue::from_value(value.into_value())))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+/// The keys of a [`LayeredValue`], sorted by name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayeredMap(BTreeMap<String, LayeredValue>);
+
+impl Map for LayeredMap {
+    type Value = LayeredValue;
+    type Iter = std::collections::btree_map::IntoIter<String, LayeredValue>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        self.0.remove(key)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        self.0.into_iter()
+    }
+}
+
+impl IntoValue for LayeredValue {
+    type Sequence = Vec<LayeredValue>;
+    type Map = LayeredMap;
+
+    const COERCE_STRINGS: bool = true;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            LayeredValue::Null => ValueKind::Null,
+            LayeredValue::Boolean(_) => ValueKind::Boolean,
+            LayeredValue::Integer(_) => ValueKind::Integer,
+            LayeredValue::NegativeInteger(_) => ValueKind::NegativeInteger,
+            LayeredValue::Float(_) => ValueKind::Float,
+            LayeredValue::Number(_) => ValueKind::Number,
+            LayeredValue::String(_) => ValueKind::String,
+            LayeredValue::Bytes(_) => ValueKind::Bytes,
+            LayeredValue::Sequence(_) => ValueKind::Sequence,
+            LayeredValue::Map(_) => ValueKind::Map,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            LayeredValue::Null => Value::Null,
+            LayeredValue::Boolean(b) => Value::Boolean(b),
+            LayeredValue::Integer(x) => Value::Integer(x),
+            LayeredValue::NegativeInteger(x) => Value::NegativeInteger(x),
+            LayeredValue::Float(x) => Value::Float(x),
+            LayeredValue::Number(x) => Value::Number(x),
+            LayeredValue::String(s) => Value::String(s),
+            LayeredValue::Bytes(bytes) => Value::Bytes(bytes),
+            LayeredValue::Sequence(seq) => Value::Sequence(seq),
+            LayeredValue::Map(map) => Value::Map(map),
+        }
+    }
+
+    fn string_into_sequence(s: String) -> Result<Vec<LayeredValue>, String> {
+        EnvValue::string_into_sequence(s).map(|seq| {
+            IntoIterator::into_iter(seq)
+                .map(|value| LayeredValue::from_value(value.into_value()))
+                .collect()
+        })
+    }
+}
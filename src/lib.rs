@@ -8,15 +8,41 @@
 
 #[cfg(feature = "actix-web")]
 pub mod actix_web;
+pub mod args;
 #[cfg(feature = "axum")]
 pub mod axum;
+#[cfg(feature = "ciborium")]
+pub mod ciborium;
+mod decimal;
+pub mod env;
 pub mod errors;
+#[cfg(feature = "http")]
+pub mod headers;
 mod impls;
+#[cfg(feature = "json5")]
+pub mod json5;
+#[cfg(feature = "serde-json")]
+pub mod json_array;
+pub mod layered;
+#[cfg(feature = "multipart")]
+pub mod multipart;
+#[cfg(feature = "serde-json")]
+pub mod ndjson;
+mod ordered_map;
+mod owned_value;
+#[cfg(feature = "poem")]
+pub mod poem;
+pub mod query_string;
+#[cfg(feature = "rmpv")]
+pub mod rmpv;
 #[cfg(feature = "serde-cs")]
 pub mod serde_cs;
 #[cfg(feature = "serde-json")]
 pub mod serde_json;
+pub mod validators;
 mod value;
+#[cfg(feature = "warp")]
+pub mod warp;
 
 extern crate self as deserr;
 
@@ -81,9 +107,17 @@ will parse the following:
 }
 ```
 */
+pub use decimal::Decimal;
 pub use deserr_internal::Deserr;
-pub use value::{IntoValue, Map, Sequence, Value, ValueKind, ValuePointer, ValuePointerRef};
+pub use owned_value::{DuplicateKey, OwnedMap, OwnedValue};
+pub use value::{
+    IntoValue, Map, Sequence, Value, ValueKind, ValuePointer, ValuePointerComponent,
+    ValuePointerRef,
+};
 
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
 use std::ops::ControlFlow;
 
 /// A trait for types that can be deserialized from a [`Value`]. The generic type
@@ -187,6 +221,49 @@ pub enum ErrorKind<'a, V: IntoValue> {
     Unexpected {
         msg: String,
     },
+    /// The value is outside of the bounds given by the `range` attribute.
+    OutOfRange {
+        actual: &'a dyn Display,
+        min: Option<&'a dyn Display>,
+        max: Option<&'a dyn Display>,
+    },
+    /// The length of the value is outside of the bounds given by the `length` attribute.
+    BadLength {
+        actual: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    /// The value is empty even though the `non_empty` attribute was specified.
+    EmptyValue,
+    /// The value doesn't match the regular expression given by the `pattern` attribute.
+    PatternMismatch {
+        value: &'a str,
+        pattern: &'a str,
+    },
+    /// The fields in `missing` are required by the `requires` attribute because `field` was specified.
+    MissingRequiredFields {
+        field: &'a str,
+        missing: &'a [&'a str],
+    },
+    /// The fields in `conflicting` can't be specified together with `field` because of the
+    /// `conflicts_with` attribute.
+    ConflictingFields {
+        field: &'a str,
+        conflicting: &'a [&'a str],
+    },
+    /// The `exactly_one_of` attribute expected exactly one of `fields` to be specified,
+    /// but the fields in `present` were specified instead.
+    NotExactlyOneOf {
+        fields: &'a [&'a str],
+        present: &'a [&'a str],
+    },
+    /// The payload couldn't be parsed, e.g. a malformed Json body.
+    /// The `line` and `column` of the error start at 1.
+    Syntax {
+        msg: String,
+        line: usize,
+        column: usize,
+    },
 }
 
 /// A trait for errors returned by [`deserialize_from_value`](Deserr::deserialize_from_value).
@@ -198,6 +275,41 @@ pub trait DeserializeError: Sized + MergeWithError<Self> {
     ) -> ControlFlow<Self, Self>;
 }
 
+/// A collection capturing the unknown fields of a struct, see the `#[deserr(extra)]` field attribute.
+///
+/// The field is first initialized with its `Default` value, then each unknown field is
+/// deserialized into a [`Self::Value`](ExtraFields::Value) and inserted with [`insert_extra`](ExtraFields::insert_extra).
+pub trait ExtraFields: Default {
+    /// The type of the values of the unknown fields.
+    type Value;
+
+    /// Insert an unknown field.
+    fn insert_extra(&mut self, key: String, value: Self::Value);
+}
+
+impl<K, T, S> ExtraFields for HashMap<K, T, S>
+where
+    K: From<String> + Hash + Eq,
+    S: BuildHasher + Default,
+{
+    type Value = T;
+
+    fn insert_extra(&mut self, key: String, value: T) {
+        self.insert(K::from(key), value);
+    }
+}
+
+impl<K, T> ExtraFields for BTreeMap<K, T>
+where
+    K: From<String> + Ord,
+{
+    type Value = T;
+
+    fn insert_extra(&mut self, key: String, value: T) {
+        self.insert(K::from(key), value);
+    }
+}
+
 /// Used by the derive proc macro. Do not use.
 #[doc(hidden)]
 pub enum FieldState<T> {
@@ -0,0 +1,166 @@
+//! A `multipart/form-data` backend for deserr, built on [`multer`].
+//!
+//! The parts of a request are a map from the name of the part to its value:
+//! - A text part is a string, which is coerced into a number or a boolean when needed,
+//!   see [`IntoValue::COERCE_STRINGS`].
+//! - A file part, which has a file name, is deserialized into a [`File`].
+//! - A part specified multiple times is a sequence of values.
+//!
+//! ```
+//! use deserr::{errors::MultipartError, multipart::{File, MultipartValue}, Deserr};
+//!
+//! #[derive(Deserr, Debug)]
+//! struct Upload {
+//!     title: String,
+//!     public: bool,
+//!     image: File,
+//! }
+//!
+//! let body = "--X-BOUNDARY\r\n\
+//!     Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+//!     Doggo\r\n\
+//!     --X-BOUNDARY\r\n\
+//!     Content-Disposition: form-data; name=\"public\"\r\n\r\n\
+//!     true\r\n\
+//!     --X-BOUNDARY\r\n\
+//!     Content-Disposition: form-data; name=\"image\"; filename=\"doggo.png\"\r\n\
+//!     Content-Type: image/png\r\n\r\n\
+//!     bork\r\n\
+//!     --X-BOUNDARY--\r\n";
+//! let stream = futures::stream::once(async move { Ok::<_, std::convert::Infallible>(body) });
+//! let multipart = multer::Multipart::new(stream, "X-BOUNDARY");
+//!
+//! let value = futures::executor::block_on(MultipartValue::from_multipart(multipart)).unwrap();
+//! let upload = deserr::deserialize::<Upload, _, MultipartError>(value).unwrap();
+//! assert_eq!(upload.title, "Doggo");
+//! assert!(upload.public);
+//! assert_eq!(upload.image.file_name.as_deref(), Some("doggo.png"));
+//! assert_eq!(upload.image.content_type.as_deref(), Some("image/png"));
+//! assert_eq!(upload.image.content, b"bork");
+//! ```
+
+use crate::{Deserr, IntoValue, Map, Value, ValueKind};
+
+/// A file sent in a part of a `multipart/form-data` request.
+///
+/// A file part is given to deserr as a map containing the `file_name`, the `content_type`
+/// and the `content` of the file, the content being a [`Value::Bytes`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserr)]
+pub struct File {
+    #[deserr(default)]
+    pub file_name: Option<String>,
+    #[deserr(default)]
+    pub content_type: Option<String>,
+    pub content: Vec<u8>,
+}
+
+/// A value parsed from a `multipart/form-data` request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultipartValue {
+    Map(MultipartMap),
+    String(String),
+    Sequence(Vec<MultipartValue>),
+    /// The content of a file part.
+    Bytes(Vec<u8>),
+}
+
+impl MultipartValue {
+    /// Read all the parts of a multipart request into a map of parts.
+    pub async fn from_multipart(
+        mut multipart: multer::Multipart<'_>,
+    ) -> Result<Self, multer::Error> {
+        let mut map = MultipartMap::default();
+        while let Some(field) = multipart.next_field().await? {
+            let name = field.name().unwrap_or_default().to_owned();
+            let value = match field.file_name() {
+                Some(file_name) => {
+                    let mut file = MultipartMap::default();
+                    file.append(
+                        String::from("file_name"),
+                        MultipartValue::String(file_name.to_owned()),
+                    );
+                    if let Some(content_type) = field.content_type() {
+                        file.append(
+                            String::from("content_type"),
+                            MultipartValue::String(content_type.to_string()),
+                        );
+                    }
+                    let content = field.bytes().await?;
+                    file.append(
+                        String::from("content"),
+                        MultipartValue::Bytes(content.into()),
+                    );
+                    MultipartValue::Map(file)
+                }
+                None => MultipartValue::String(field.text().await?),
+            };
+            map.append(name, value);
+        }
+        Ok(MultipartValue::Map(map))
+    }
+}
+
+/// The parts of a multipart request, in the order they were first specified.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MultipartMap(Vec<(String, MultipartValue)>);
+
+impl MultipartMap {
+    /// Insert a part, or append the value to the previous values of the part if there was one.
+    pub fn append(&mut self, key: String, value: MultipartValue) {
+        match self.0.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, MultipartValue::Sequence(values))) => values.push(value),
+            Some((_, v)) => {
+                let previous = std::mem::replace(v, MultipartValue::Sequence(Vec::new()));
+                *v = MultipartValue::Sequence(vec![previous, value]);
+            }
+            None => self.0.push((key, value)),
+        }
+    }
+}
+
+impl Map for MultipartMap {
+    type Value = MultipartValue;
+    type Iter = std::vec::IntoIter<(String, MultipartValue)>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let index = self.0.iter().position(|(k, _)| k == key)?;
+        Some(self.0.remove(index).1)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        self.0.into_iter()
+    }
+}
+
+impl IntoValue for MultipartValue {
+    type Sequence = Vec<MultipartValue>;
+    type Map = MultipartMap;
+
+    const COERCE_STRINGS: bool = true;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            MultipartValue::Map(_) => ValueKind::Map,
+            MultipartValue::String(_) => ValueKind::String,
+            MultipartValue::Sequence(_) => ValueKind::Sequence,
+            MultipartValue::Bytes(_) => ValueKind::Bytes,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            MultipartValue::Map(map) => Value::Map(map),
+            MultipartValue::String(s) => Value::String(s),
+            MultipartValue::Sequence(seq) => Value::Sequence(seq),
+            MultipartValue::Bytes(bytes) => Value::Bytes(bytes),
+        }
+    }
+
+    fn string_into_sequence(s: String) -> Result<Vec<MultipartValue>, String> {
+        Ok(vec![MultipartValue::String(s)])
+    }
+}
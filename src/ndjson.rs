@@ -0,0 +1,346 @@
+//! A newline-delimited JSON backend for deserr, deserializing a payload one line at a time
+//! instead of reading it entirely in memory.
+//!
+//! Every line is a JSON value deserialized on its own into a `Result<T, E>`, and the blank
+//! lines are ignored. The errors are located at the index of their line, starting from 0,
+//! as if the lines were the elements of an array: the errors of the third line are at `[2]`.
+//! The syntax errors give the number of their line in the whole payload, starting from 1.
+//!
+//! By default, the next lines are still deserialized after an error, so that every invalid
+//! line is reported. See [`NdJsonReader::stop_on_error`] to stop at the first error instead.
+//!
+//! A line is read entirely in memory before being deserialized. To bound the memory used by
+//! a payload coming from the users, limit the length of the lines with
+//! [`NdJsonReader::max_line_length`].
+//!
+//! ```
+//! use deserr::{errors::JsonError, Deserr};
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Document {
+//!     id: u32,
+//! }
+//!
+//! let payload = "{ \"id\": 1 }\n{ \"id\": \"doggo\" }\n\n{ \"id\": 3 }\n";
+//! let mut lines = deserr::ndjson::from_reader::<Document, JsonError, _>(payload.as_bytes());
+//!
+//! assert_eq!(lines.next().unwrap().unwrap(), Document { id: 1 });
+//! assert_eq!(
+//!     lines.next().unwrap().unwrap_err().to_string(),
+//!     "Invalid value type at `[1].id`: expected a positive integer, but found a string: `\"doggo\"`"
+//! );
+//! assert_eq!(lines.next().unwrap().unwrap(), Document { id: 3 });
+//! assert!(lines.next().is_none());
+//! ```
+
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, Read};
+use std::marker::PhantomData;
+
+use crate::serde_json::syntax_error_at;
+use crate::{take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, ValuePointerRef};
+
+/// Deserialize the lines read from the given reader, see [`NdJsonReader`].
+pub fn from_reader<T, E, R>(reader: R) -> NdJsonReader<BufReader<R>, T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+    R: Read,
+{
+    NdJsonReader::new(BufReader::new(reader))
+}
+
+/// Deserialize the lines of the given stream of chunks of bytes, see [`NdJsonStream`].
+#[cfg(feature = "futures")]
+pub fn from_stream<T, E, S>(stream: S) -> NdJsonStream<S, T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    NdJsonStream::new(stream)
+}
+
+/// Deserialize the line at the given index, or return `None` if it is blank.
+fn deserialize_line<T, E>(line: &[u8], index: usize) -> Option<Result<T, E>>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+    // without its line ending, the positions of the syntax errors are on the first line
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    let origin = ValuePointerRef::Origin;
+    let location = origin.push_index(index);
+    Some(match serde_json::from_slice::<serde_json::Value>(line) {
+        Ok(value) => T::deserialize_from_value(value.into_value(), location),
+        Err(error) => {
+            let column = error.column();
+            Err(syntax_error_at(error, index + 1, column, location))
+        }
+    })
+}
+
+/// Return an error located at the index of the line if the line, without its line ending,
+/// is longer than the limit.
+fn check_line_length<E: DeserializeError>(
+    line: &[u8],
+    max_line_length: Option<usize>,
+    index: usize,
+) -> Result<(), E> {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    match max_line_length {
+        Some(limit) if line.len() > limit => Err(take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!("The line is longer than allowed (limit: {limit} bytes)"),
+            },
+            ValuePointerRef::Origin.push_index(index),
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Whether the content type is `application/x-ndjson`, ignoring its parameters.
+#[cfg(any(feature = "actix-web", feature = "axum"))]
+pub(crate) fn is_ndjson_content_type(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    mime.eq_ignore_ascii_case("application/x-ndjson")
+}
+
+/// The message of a request rejected because of its content type, if it is missing or not
+/// accepted.
+#[cfg(any(feature = "actix-web", feature = "axum"))]
+pub(crate) fn content_type_error(content_type: Option<&str>) -> String {
+    match content_type {
+        None => String::from("Missing `Content-Type` header: expected `application/x-ndjson`"),
+        Some(content_type) => format!(
+            "Invalid `Content-Type` header `{content_type}`: expected `application/x-ndjson`"
+        ),
+    }
+}
+
+/// Convert an error of the underlying reader or stream into an `E`.
+pub(crate) fn read_error<E: DeserializeError>(error: impl Display) -> E {
+    take_cf_content(E::error::<Infallible>(
+        None,
+        ErrorKind::Unexpected {
+            msg: format!("Failed to read the payload: {error}"),
+        },
+        ValuePointerRef::Origin,
+    ))
+}
+
+/// An iterator deserializing the lines of a reader, yielding a `Result<T, E>` per line.
+///
+/// The iteration ends after an error of the reader, or after a line longer than the limit.
+pub struct NdJsonReader<R, T, E> {
+    reader: R,
+    line: Vec<u8>,
+    index: usize,
+    max_line_length: Option<usize>,
+    stop_on_error: bool,
+    done: bool,
+    _phantom: PhantomData<fn() -> (T, E)>,
+}
+
+impl<R: BufRead, T, E> NdJsonReader<R, T, E> {
+    pub fn new(reader: R) -> Self {
+        NdJsonReader {
+            reader,
+            line: Vec::new(),
+            index: 0,
+            max_line_length: None,
+            stop_on_error: false,
+            done: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Set the maximum length of a line in bytes, without its line ending. A longer line
+    /// yields an error and ends the iteration, without being read further. By default the
+    /// length of the lines is not limited.
+    pub fn max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = Some(max_line_length);
+        self
+    }
+
+    /// Whether the iteration ends after the first invalid line. By default, every line is
+    /// deserialized.
+    pub fn stop_on_error(mut self, stop_on_error: bool) -> Self {
+        self.stop_on_error = stop_on_error;
+        self
+    }
+}
+
+impl<R, T, E> Iterator for NdJsonReader<R, T, E>
+where
+    R: BufRead,
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.line.clear();
+            let read = match self.max_line_length {
+                // a line at the limit still fits with a `\r\n` line ending, and a longer one
+                // is known to be too long without being read further
+                Some(limit) => (&mut self.reader)
+                    .take(limit as u64 + 2)
+                    .read_until(b'\n', &mut self.line),
+                None => self.reader.read_until(b'\n', &mut self.line),
+            };
+            match read {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    let index = self.index;
+                    self.index += 1;
+                    if let Err(error) = check_line_length(&self.line, self.max_line_length, index) {
+                        self.done = true;
+                        return Some(Err(error));
+                    }
+                    if let Some(result) = deserialize_line(&self.line, index) {
+                        self.done = self.stop_on_error && result.is_err();
+                        return Some(result);
+                    }
+                }
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(read_error(error)));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A [`Stream`](futures::Stream) deserializing the lines of a stream of chunks of bytes,
+/// such as the body of a request, yielding a `Result<T, E>` per line.
+///
+/// Only the line being read is kept in memory. The stream ends after an error of the
+/// underlying stream, or after a line longer than the limit.
+#[cfg(feature = "futures")]
+pub struct NdJsonStream<S, T, E> {
+    stream: S,
+    buffer: Vec<u8>,
+    /// The start of the current line in the buffer.
+    start: usize,
+    /// The end of the part of the buffer already searched for a newline.
+    scanned: usize,
+    index: usize,
+    max_line_length: Option<usize>,
+    stop_on_error: bool,
+    /// Whether the underlying stream has ended.
+    ended: bool,
+    done: bool,
+    _phantom: PhantomData<fn() -> (T, E)>,
+}
+
+#[cfg(feature = "futures")]
+impl<S, T, E> NdJsonStream<S, T, E> {
+    pub fn new(stream: S) -> Self {
+        NdJsonStream {
+            stream,
+            buffer: Vec::new(),
+            start: 0,
+            scanned: 0,
+            index: 0,
+            max_line_length: None,
+            stop_on_error: false,
+            ended: false,
+            done: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Set the maximum length of a line in bytes, without its line ending. A longer line
+    /// yields an error and ends the stream, without being buffered further. By default the
+    /// length of the lines is not limited.
+    pub fn max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = Some(max_line_length);
+        self
+    }
+
+    /// Whether the stream ends after the first invalid line. By default, every line is
+    /// deserialized.
+    pub fn stop_on_error(mut self, stop_on_error: bool) -> Self {
+        self.stop_on_error = stop_on_error;
+        self
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<S, B, SE, T, E> futures::Stream for NdJsonStream<S, T, E>
+where
+    S: futures::Stream<Item = Result<B, SE>> + Unpin,
+    B: AsRef<[u8]>,
+    SE: Display,
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        use futures::StreamExt;
+        use std::task::Poll;
+
+        let this = &mut *self;
+        while !this.done {
+            let newline = this.buffer[this.scanned..].iter().position(|b| *b == b'\n');
+            let end = match newline {
+                Some(position) => this.scanned + position + 1,
+                // the last line may not end with a newline
+                None if this.ended => {
+                    this.done = true;
+                    this.buffer.len()
+                }
+                None => {
+                    // only the next chunks will have to be searched for a newline
+                    this.scanned = this.buffer.len();
+                    let line = &this.buffer[this.start..];
+                    if let Err(error) = check_line_length(line, this.max_line_length, this.index) {
+                        this.done = true;
+                        return Poll::Ready(Some(Err(error)));
+                    }
+                    match futures::ready!(this.stream.poll_next_unpin(cx)) {
+                        Some(Ok(chunk)) => {
+                            this.buffer.drain(..this.start);
+                            this.scanned -= this.start;
+                            this.start = 0;
+                            this.buffer.extend_from_slice(chunk.as_ref());
+                        }
+                        Some(Err(error)) => {
+                            this.done = true;
+                            return Poll::Ready(Some(Err(read_error(error))));
+                        }
+                        None => this.ended = true,
+                    }
+                    continue;
+                }
+            };
+            let line = &this.buffer[this.start..end];
+            if let Err(error) = check_line_length(line, this.max_line_length, this.index) {
+                this.done = true;
+                return Poll::Ready(Some(Err(error)));
+            }
+            let index = this.index;
+            this.index += 1;
+            this.start = end;
+            this.scanned = end;
+            if let Some(result) = deserialize_line(line, index) {
+                this.done |= this.stop_on_error && result.is_err();
+                return Poll::Ready(Some(result));
+            }
+        }
+        Poll::Ready(None)
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+
+/// A map keeping its entries in their insertion order, with constant time lookups and removals.
+///
+/// It backs the maps of the backends parsing their own formats, which must be able to hold a
+/// lot of keys since they come straight from the users.
+#[derive(Clone)]
+pub(crate) struct OrderedMap<V> {
+    /// The entries in their insertion order, a removed entry leaving a hole behind.
+    entries: Vec<Option<(String, V)>>,
+    /// The position of each key in `entries`.
+    indexes: HashMap<String, usize>,
+}
+
+/// The iterator over the remaining entries of an [`OrderedMap`], in their insertion order.
+pub(crate) type IntoIter<V> = std::iter::Flatten<std::vec::IntoIter<Option<(String, V)>>>;
+
+impl<V> OrderedMap<V> {
+    pub fn len(&self) -> usize {
+        self.indexes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indexes.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.indexes.contains_key(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&V> {
+        let index = *self.indexes.get(key)?;
+        self.entries[index].as_ref().map(|(_, value)| value)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        let index = *self.indexes.get(key)?;
+        self.entries[index].as_mut().map(|(_, value)| value)
+    }
+
+    /// Insert a value at the given key, returning the previous value of the key if there
+    /// was one. The key keeps its position in the map.
+    pub fn insert(&mut self, key: String, value: V) -> Option<V> {
+        match self.get_mut(&key) {
+            Some(previous) => Some(std::mem::replace(previous, value)),
+            None => {
+                self.push(key, value);
+                None
+            }
+        }
+    }
+
+    /// Return the value of the given key, inserting the result of `default` if there was none.
+    pub fn get_or_insert_with(&mut self, key: String, default: impl FnOnce() -> V) -> &mut V {
+        let index = match self.indexes.get(&key) {
+            Some(index) => *index,
+            None => self.push(key, default()),
+        };
+        &mut self.entries[index].as_mut().unwrap().1
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        let index = self.indexes.remove(key)?;
+        self.entries[index].take().map(|(_, value)| value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.entries
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key.as_str(), value))
+    }
+
+    pub fn into_iter(self) -> IntoIter<V> {
+        self.entries.into_iter().flatten()
+    }
+
+    fn push(&mut self, key: String, value: V) -> usize {
+        let index = self.entries.len();
+        self.indexes.insert(key.clone(), index);
+        self.entries.push(Some((key, value)));
+        index
+    }
+}
+
+impl<V> Default for OrderedMap<V> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            indexes: HashMap::new(),
+        }
+    }
+}
+
+/// Two maps are equal if they contain the same entries in the same order.
+impl<V: PartialEq> PartialEq for OrderedMap<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<V: Eq> Eq for OrderedMap<V> {}
+
+impl<V: Debug> Debug for OrderedMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().flatten())
+            .finish()
+    }
+}
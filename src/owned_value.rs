@@ -0,0 +1,447 @@
+use std::fmt;
+use std::ops::ControlFlow;
+
+use crate::ordered_map::{IntoIter, OrderedMap};
+use crate::value::exact_float;
+use crate::{
+    DeserializeError, Deserr, ErrorKind, IntoValue, Map, Sequence, Value, ValueKind,
+    ValuePointerRef,
+};
+
+/// An owned value which doesn't depend on any serialization format.
+///
+/// It can be deserialized like any other [`IntoValue`], and deserialized from any value to
+/// capture an arbitrary sub-tree. It is usually built with the [`value!`](crate::value!) macro.
+///
+/// ```
+/// use deserr::{errors::JsonError, value, Deserr, OwnedValue};
+///
+/// #[derive(Deserr, Debug, PartialEq)]
+/// struct Search {
+///     q: String,
+///     limit: u32,
+///     // kept as is, to be forwarded to another service
+///     filter: OwnedValue,
+/// }
+///
+/// let search = value!({ "q": "doggo", "limit": 20, "filter": { "age": [-1, null] } });
+/// let search = deserr::deserialize::<Search, _, JsonError>(search).unwrap();
+/// assert_eq!(search.filter, value!({ "age": [-1, null] }));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum OwnedValue {
+    #[default]
+    Null,
+    Boolean(bool),
+    Integer(u64),
+    NegativeInteger(i64),
+    Float(f64),
+    Number(String),
+    String(String),
+    Bytes(Vec<u8>),
+    Sequence(Vec<OwnedValue>),
+    Map(OwnedMap),
+}
+
+impl OwnedValue {
+    /// Return a number given as its text, which is a float if the float is written back as
+    /// the same text.
+    fn from_number(text: String) -> Self {
+        match exact_float(&text) {
+            Some(x) => OwnedValue::Float(x),
+            None => OwnedValue::Number(text),
+        }
+    }
+}
+
+/// The entries of an [`OwnedValue::Map`], in their insertion order.
+///
+/// A key can only be in the map once: inserting an existing key is rejected with a
+/// [`DuplicateKey`] error, and collecting entries with a duplicated key panics.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OwnedMap(OrderedMap<OwnedValue>);
+
+impl OwnedMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a value at the given key, unless the key is already in the map.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: OwnedValue,
+    ) -> Result<(), DuplicateKey> {
+        let key = key.into();
+        if self.0.contains_key(&key) {
+            return Err(DuplicateKey(key));
+        }
+        self.0.insert(key, value);
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&OwnedValue> {
+        self.0.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut OwnedValue> {
+        self.0.get_mut(key)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &OwnedValue)> {
+        self.0.iter()
+    }
+}
+
+/// # Panics
+///
+/// Panics if a key is duplicated.
+impl<K: Into<String>> FromIterator<(K, OwnedValue)> for OwnedMap {
+    fn from_iter<I: IntoIterator<Item = (K, OwnedValue)>>(iter: I) -> Self {
+        let mut map = OwnedMap::new();
+        for (key, value) in iter {
+            if let Err(e) = map.insert(key, value) {
+                panic!("{e}");
+            }
+        }
+        map
+    }
+}
+
+/// The error returned when inserting a key already present in an [`OwnedMap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateKey(pub String);
+
+impl fmt::Display for DuplicateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the key `{}` is specified multiple times", self.0)
+    }
+}
+
+impl std::error::Error for DuplicateKey {}
+
+impl Map for OwnedMap {
+    type Value = OwnedValue;
+    type Iter = IntoIter<OwnedValue>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        self.0.remove(key)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        self.0.into_iter()
+    }
+}
+
+impl IntoValue for OwnedValue {
+    type Sequence = Vec<OwnedValue>;
+    type Map = OwnedMap;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            OwnedValue::Null => ValueKind::Null,
+            OwnedValue::Boolean(_) => ValueKind::Boolean,
+            OwnedValue::Integer(_) => ValueKind::Integer,
+            OwnedValue::NegativeInteger(_) => ValueKind::NegativeInteger,
+            OwnedValue::Float(_) => ValueKind::Float,
+            OwnedValue::Number(_) => ValueKind::Number,
+            OwnedValue::String(_) => ValueKind::String,
+            OwnedValue::Bytes(_) => ValueKind::Bytes,
+            OwnedValue::Sequence(_) => ValueKind::Sequence,
+            OwnedValue::Map(_) => ValueKind::Map,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            OwnedValue::Null => Value::Null,
+            OwnedValue::Boolean(b) => Value::Boolean(b),
+            OwnedValue::Integer(x) => Value::Integer(x),
+            OwnedValue::NegativeInteger(x) => Value::NegativeInteger(x),
+            OwnedValue::Float(x) => Value::Float(x),
+            OwnedValue::Number(x) => Value::Number(x),
+            OwnedValue::String(s) => Value::String(s),
+            OwnedValue::Bytes(bytes) => Value::Bytes(bytes),
+            OwnedValue::Sequence(seq) => Value::Sequence(seq),
+            OwnedValue::Map(map) => Value::Map(map),
+        }
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for OwnedValue {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        let mut error: Option<E> = None;
+        Ok(match value {
+            Value::Null => OwnedValue::Null,
+            Value::Boolean(b) => OwnedValue::Boolean(b),
+            Value::Integer(x) => OwnedValue::Integer(x),
+            Value::NegativeInteger(x) => OwnedValue::NegativeInteger(x),
+            Value::Float(x) => OwnedValue::Float(x),
+            Value::Number(x) => OwnedValue::from_number(x),
+            Value::String(s) => OwnedValue::String(s),
+            Value::Bytes(bytes) => OwnedValue::Bytes(bytes),
+            Value::Sequence(seq) => {
+                let mut values = Vec::with_capacity(seq.len());
+                for (index, value) in seq.into_iter().enumerate() {
+                    let result = Self::deserialize_from_value(
+                        value.into_value(),
+                        location.push_index(index),
+                    );
+                    match result {
+                        Ok(value) => values.push(value),
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_index(index)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    return Err(e);
+                }
+                OwnedValue::Sequence(values)
+            }
+            Value::Map(map) => {
+                let mut entries = OwnedMap::new();
+                for (key, value) in map.into_iter() {
+                    let result =
+                        Self::deserialize_from_value(value.into_value(), location.push_key(&key));
+                    match result {
+                        Ok(value) => {
+                            if let Err(duplicate) = entries.insert(key, value) {
+                                let msg = duplicate.to_string();
+                                let kind = ErrorKind::Unexpected { msg };
+                                error = match E::error::<V>(error, kind, location) {
+                                    ControlFlow::Continue(e) => Some(e),
+                                    ControlFlow::Break(e) => return Err(e),
+                                };
+                            }
+                        }
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_key(&key)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    return Err(e);
+                }
+                OwnedValue::Map(entries)
+            }
+        })
+    }
+}
+
+impl<V: IntoValue> From<Value<V>> for OwnedValue {
+    fn from(value: Value<V>) -> Self {
+        match value {
+            Value::Null => OwnedValue::Null,
+            Value::Boolean(b) => OwnedValue::Boolean(b),
+            Value::Integer(x) => OwnedValue::Integer(x),
+            Value::NegativeInteger(x) => OwnedValue::NegativeInteger(x),
+            Value::Float(x) => OwnedValue::Float(x),
+            Value::Number(x) => OwnedValue::from_number(x),
+            Value::String(s) => OwnedValue::String(s),
+            Value::Bytes(bytes) => OwnedValue::Bytes(bytes),
+            Value::Sequence(seq) => OwnedValue::Sequence(
+                seq.into_iter()
+                    .map(|value| OwnedValue::from(value.into_value()))
+                    .collect(),
+            ),
+            Value::Map(map) => {
+                // the last value of a duplicated key is kept, deserialize the value to
+                // reject them instead
+                let mut entries = OrderedMap::default();
+                for (key, value) in map.into_iter() {
+                    entries.insert(key, OwnedValue::from(value.into_value()));
+                }
+                OwnedValue::Map(OwnedMap(entries))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde-json")]
+impl From<serde_json::Value> for OwnedValue {
+    fn from(value: serde_json::Value) -> Self {
+        OwnedValue::from(value.into_value())
+    }
+}
+
+/// The byte strings become arrays of numbers, and the floats that can't be represented in
+/// JSON become `null`.
+#[cfg(feature = "serde-json")]
+impl From<OwnedValue> for serde_json::Value {
+    fn from(value: OwnedValue) -> Self {
+        serde_json::Value::from(value.into_value())
+    }
+}
+
+impl From<bool> for OwnedValue {
+    fn from(b: bool) -> Self {
+        OwnedValue::Boolean(b)
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for OwnedValue {
+                fn from(x: $t) -> Self {
+                    OwnedValue::Integer(x as u64)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {
+        $(
+            /// The positive numbers are [`OwnedValue::Integer`]s, as when they are parsed.
+            impl From<$t> for OwnedValue {
+                fn from(x: $t) -> Self {
+                    match u64::try_from(x) {
+                        Ok(x) => OwnedValue::Integer(x),
+                        Err(_) => OwnedValue::NegativeInteger(x as i64),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, usize);
+from_signed!(i8, i16, i32, i64, isize);
+
+/// The numbers which don't fit in 64 bits are [`OwnedValue::Number`]s.
+impl From<u128> for OwnedValue {
+    fn from(x: u128) -> Self {
+        match u64::try_from(x) {
+            Ok(x) => OwnedValue::Integer(x),
+            Err(_) => OwnedValue::Number(x.to_string()),
+        }
+    }
+}
+
+/// The numbers which don't fit in 64 bits are [`OwnedValue::Number`]s.
+impl From<i128> for OwnedValue {
+    fn from(x: i128) -> Self {
+        match (u64::try_from(x), i64::try_from(x)) {
+            (Ok(x), _) => OwnedValue::Integer(x),
+            (_, Ok(x)) => OwnedValue::NegativeInteger(x),
+            _ => OwnedValue::Number(x.to_string()),
+        }
+    }
+}
+
+impl From<f32> for OwnedValue {
+    fn from(x: f32) -> Self {
+        OwnedValue::Float(f64::from(x))
+    }
+}
+
+impl From<f64> for OwnedValue {
+    fn from(x: f64) -> Self {
+        OwnedValue::Float(x)
+    }
+}
+
+impl From<&str> for OwnedValue {
+    fn from(s: &str) -> Self {
+        OwnedValue::String(s.to_owned())
+    }
+}
+
+impl From<String> for OwnedValue {
+    fn from(s: String) -> Self {
+        OwnedValue::String(s)
+    }
+}
+
+impl<T: Into<OwnedValue>> From<Option<T>> for OwnedValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(OwnedValue::Null, Into::into)
+    }
+}
+
+impl<T: Into<OwnedValue>> From<Vec<T>> for OwnedValue {
+    fn from(values: Vec<T>) -> Self {
+        OwnedValue::Sequence(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<OwnedMap> for OwnedValue {
+    fn from(map: OwnedMap) -> Self {
+        OwnedValue::Map(map)
+    }
+}
+
+/// Build an [`OwnedValue`] with a JSON-like syntax.
+///
+/// The keys of the maps are string literals or identifiers of variables, and the values are
+/// `null`, arrays, maps or any expression convertible into an [`OwnedValue`].
+///
+/// ```
+/// use deserr::{value, OwnedValue};
+///
+/// let name = "kefir";
+/// let doggo = value!({
+///     "name": name,
+///     "age": 2 + 1,
+///     "toys": ["ball", null],
+///     "owner": {},
+/// });
+/// let OwnedValue::Map(doggo) = doggo else { panic!() };
+/// assert_eq!(doggo.get("age"), Some(&OwnedValue::Integer(3)));
+/// ```
+#[macro_export]
+macro_rules! value {
+    // the elements of a sequence are munched one token at a time, until a comma
+    (@seq [$($done:expr,)*] []) => { ::std::vec![$($done,)*] };
+    (@seq [$($done:expr,)*] [$($current:tt)+]) => {
+        ::std::vec![$($done,)* $crate::value!($($current)+),]
+    };
+    (@seq [$($done:expr,)*] [$($current:tt)+] , $($rest:tt)*) => {
+        $crate::value!(@seq [$($done,)* $crate::value!($($current)+),] [] $($rest)*)
+    };
+    (@seq [$($done:expr,)*] [$($current:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::value!(@seq [$($done,)*] [$($current)* $next] $($rest)*)
+    };
+
+    // same for the entries of a map, whose key is a single token
+    (@map [$($done:expr,)*]) => {
+        <$crate::OwnedMap as ::std::iter::FromIterator<(::std::string::String, $crate::OwnedValue)>>::from_iter([$($done,)*])
+    };
+    (@map [$($done:expr,)*] $key:tt : $($rest:tt)*) => {
+        $crate::value!(@entry [$($done,)*] $key [] $($rest)*)
+    };
+    (@entry [$($done:expr,)*] $key:tt [$($current:tt)+]) => {
+        $crate::value!(@map [$($done,)* (::std::string::String::from($key), $crate::value!($($current)+)),])
+    };
+    (@entry [$($done:expr,)*] $key:tt [$($current:tt)+] , $($rest:tt)*) => {
+        $crate::value!(@map [$($done,)* (::std::string::String::from($key), $crate::value!($($current)+)),] $($rest)*)
+    };
+    (@entry [$($done:expr,)*] $key:tt [$($current:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::value!(@entry [$($done,)*] $key [$($current)* $next] $($rest)*)
+    };
+
+    (null) => { $crate::OwnedValue::Null };
+    ([$($tt:tt)*]) => { $crate::OwnedValue::Sequence($crate::value!(@seq [] [] $($tt)*)) };
+    ({$($tt:tt)*}) => { $crate::OwnedValue::Map($crate::value!(@map [] $($tt)*)) };
+    ($other:expr) => { $crate::OwnedValue::from($other) };
+}
@@ -0,0 +1,9 @@
+#[cfg(feature = "serde-json")]
+mod query_parameters;
+#[cfg(feature = "serde-json")]
+mod serde_json;
+
+#[cfg(feature = "serde-json")]
+pub use self::query_parameters::PoemQuery;
+#[cfg(feature = "serde-json")]
+pub use self::serde_json::PoemJson;
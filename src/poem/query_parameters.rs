@@ -0,0 +1,61 @@
+use std::marker::PhantomData;
+
+use crate::errors::QueryParamError;
+use crate::query_string::{QueryConfig, QueryValue};
+use crate::{DeserializeError, Deserr};
+use poem::http::StatusCode;
+use poem::{FromRequest, IntoResponse, Request, RequestBody, Response};
+
+/// Extractor for typed data from the query parameters of a request
+/// deserialised by deserr.
+///
+/// ## Extractor
+/// To extract typed data from the query string, the inner type `T` must implement the
+/// [`deserr::Deserr<E>`] trait. The inner type `E` must implement the
+/// [`DeserializeError`] + `IntoResponse` traits.
+///
+/// The numbers and booleans are parsed from their string representation.
+/// The way the query string is parsed can be configured by adding a [`QueryConfig`] to the
+/// request data, for example with the [`AddData`](poem::middleware::AddData) middleware.
+#[derive(Debug)]
+pub struct PoemQuery<T, E>(pub T, PhantomData<E>);
+
+impl<T, E> PoemQuery<T, E> {
+    pub fn new(data: T) -> Self {
+        PoemQuery(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'a, T, E> FromRequest<'a> for PoemQuery<T, E>
+where
+    E: DeserializeError + IntoResponse + 'static,
+    T: Deserr<E> + Send,
+{
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> poem::Result<Self> {
+        let config = req.data::<QueryConfig>().copied().unwrap_or_default();
+        let query = req.uri().query().unwrap_or_default();
+        let value = QueryValue::from_query_with_config(query, config).map_err(|e| {
+            poem::Error::from_string(
+                format!("Failed to deserialize query string: {e}"),
+                StatusCode::BAD_REQUEST,
+            )
+        })?;
+        match deserr::deserialize::<_, _, E>(value) {
+            Ok(data) => Ok(PoemQuery::new(data)),
+            Err(e) => Err(poem::Error::from_response(e.into_response())),
+        }
+    }
+}
+
+impl IntoResponse for QueryParamError {
+    fn into_response(self) -> Response {
+        Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .content_type("text/plain")
+            .body(self.to_string())
+    }
+}
@@ -0,0 +1,74 @@
+use std::marker::PhantomData;
+
+use crate::errors::{JsonBodyError, JsonError};
+use crate::serde_json::{is_json_content_type, syntax_error};
+use crate::{take_cf_content, DeserializeError, Deserr, MergeWithError, ValuePointerRef};
+use poem::http::{header, StatusCode};
+use poem::{FromRequest, IntoResponse, Request, RequestBody, Response};
+
+/// Extractor for typed data from Json request payloads
+/// deserialised by deserr.
+///
+/// ## Extractor
+/// To extract typed data from a request body, the inner type `T` must implement the
+/// [`deserr::Deserr<E>`] trait. The inner type `E` must implement the
+/// [`DeserializeError`] + [`MergeWithError<JsonBodyError>`] + `IntoResponse` traits.
+///
+/// The request is rejected with an `E` if its content type is not `application/json`
+/// or if it is not a valid Json, see [`ErrorKind::Syntax`](crate::ErrorKind::Syntax).
+#[derive(Debug)]
+pub struct PoemJson<T, E>(pub T, PhantomData<E>);
+
+impl<T, E> PoemJson<T, E> {
+    pub fn new(data: T) -> Self {
+        PoemJson(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'a, T, E> FromRequest<'a> for PoemJson<T, E>
+where
+    E: DeserializeError + MergeWithError<JsonBodyError> + IntoResponse + 'static,
+    T: Deserr<E> + Send,
+{
+    async fn from_request(req: &'a Request, body: &mut RequestBody) -> poem::Result<Self> {
+        let body_error = |error: JsonBodyError| {
+            let status = StatusCode::from_u16(error.status_code()).unwrap();
+            let error: E = take_cf_content(E::merge(None, error, ValuePointerRef::Origin));
+            let mut response = error.into_response();
+            response.set_status(status);
+            poem::Error::from_response(response)
+        };
+
+        match req.headers().get(header::CONTENT_TYPE) {
+            None => return Err(body_error(JsonBodyError::MissingContentType)),
+            Some(content_type) => {
+                let content_type = String::from_utf8_lossy(content_type.as_bytes());
+                if !is_json_content_type(&content_type) {
+                    let error = JsonBodyError::InvalidContentType(content_type.into_owned());
+                    return Err(body_error(error));
+                }
+            }
+        }
+
+        let bytes = body.take()?.into_bytes().await?;
+        let value = serde_json::from_slice::<serde_json::Value>(&bytes)
+            .map_err(|e| poem::Error::from_response(syntax_error::<E>(e).into_response()))?;
+        match deserr::deserialize::<_, _, E>(value) {
+            Ok(data) => Ok(PoemJson::new(data)),
+            Err(e) => Err(poem::Error::from_response(e.into_response())),
+        }
+    }
+}
+
+impl IntoResponse for JsonError {
+    fn into_response(self) -> Response {
+        Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .content_type("text/plain")
+            .body(self.to_string())
+    }
+}
@@ -0,0 +1,388 @@
+//! A query string backend for deserr.
+//!
+//! Every value of a query string is a string, thus [`QueryValue`] coerces the strings into
+//! numbers and booleans when needed, see [`IntoValue::COERCE_STRINGS`].
+//!
+//! ```
+//! use deserr::{errors::QueryParamError, query_string::QueryValue, Deserr};
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! #[deserr(deny_unknown_fields)]
+//! struct Search {
+//!     q: String,
+//!     limit: u32,
+//!     exhaustive: bool,
+//! }
+//!
+//! let value = QueryValue::from_query("q=doggo&limit=20&exhaustive=true").unwrap();
+//! let search = deserr::deserialize::<Search, _, QueryParamError>(value).unwrap();
+//! assert_eq!(search, Search { q: String::from("doggo"), limit: 20, exhaustive: true });
+//!
+//! let value = QueryValue::from_query("q=doggo&limit=abc&exhaustive=true").unwrap();
+//! let error = deserr::deserialize::<Search, _, QueryParamError>(value).unwrap_err();
+//! assert_eq!(
+//!     error.to_string(),
+//!     "Invalid value type for parameter `limit`: expected a positive integer, but found a string: `abc`"
+//! );
+//! ```
+//!
+//! By default, the query string is flat: every parameter is a string and only the last value
+//! of a parameter specified multiple times is kept.
+//! Use a [`QueryConfig`] to parse nested, repeated or comma-separated parameters:
+//!
+//! ```
+//! use deserr::{errors::QueryParamError, query_string::{QueryConfig, QueryValue}, Deserr};
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Search {
+//!     tags: Vec<String>,
+//!     ids: Vec<u32>,
+//!     range: Range,
+//! }
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Range {
+//!     min: u32,
+//!     max: u32,
+//! }
+//!
+//! let config = QueryConfig::new().brackets(true).repeated_keys(true).comma_separated(true);
+//! let query = "tags=dog&tags=cat&ids=1,2,3&range[min]=2&range[max]=4";
+//! let value = QueryValue::from_query_with_config(query, config).unwrap();
+//! let search = deserr::deserialize::<Search, _, QueryParamError>(value).unwrap();
+//! assert_eq!(
+//!     search,
+//!     Search {
+//!         tags: vec![String::from("dog"), String::from("cat")],
+//!         ids: vec![1, 2, 3],
+//!         range: Range { min: 2, max: 4 },
+//!     }
+//! );
+//! ```
+
+use serde::de::Error;
+
+use crate::ordered_map::{IntoIter, OrderedMap};
+use crate::{IntoValue, Map, Value, ValueKind};
+
+/// A value parsed from a query string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryValue {
+    String(String),
+    Sequence(Vec<QueryValue>),
+    Map(QueryMap),
+}
+
+impl QueryValue {
+    /// Parse a query string such as `q=doggo&limit=20` into a map of parameters.
+    ///
+    /// If a parameter is specified multiple times, the last value is kept.
+    pub fn from_query(query: &str) -> Result<Self, serde_urlencoded::de::Error> {
+        Self::from_query_with_config(query, QueryConfig::default())
+    }
+
+    /// Parse a query string into a map of parameters, following the given [`QueryConfig`].
+    pub fn from_query_with_config(
+        query: &str,
+        config: QueryConfig,
+    ) -> Result<Self, serde_urlencoded::de::Error> {
+        let pairs = serde_urlencoded::from_str::<Vec<(String, String)>>(query)?;
+        Self::from_pairs(pairs, config)
+    }
+
+    /// Build a map of parameters from already decoded key-value pairs, such as the fields of
+    /// an `application/x-www-form-urlencoded` body, following the given [`QueryConfig`].
+    ///
+    /// With the bracket notation, an error is returned if a parameter is used both as a value
+    /// and as a map, such as in `a=1&a[b]=2`, or if it is nested more than 32 times.
+    pub fn from_pairs(
+        pairs: impl IntoIterator<Item = (String, String)>,
+        config: QueryConfig,
+    ) -> Result<Self, serde_urlencoded::de::Error> {
+        let mut map = QueryMap::default();
+        for (key, value) in pairs {
+            let value = if config.comma_separated && value.contains(',') {
+                QueryValue::Sequence(
+                    value
+                        .split(',')
+                        .map(|s| QueryValue::String(s.to_owned()))
+                        .collect(),
+                )
+            } else {
+                QueryValue::String(value)
+            };
+            let (base, segments) = match config.brackets.then(|| split_brackets(&key)).flatten() {
+                Some((base, segments)) => (base, segments),
+                None => (key.as_str(), Vec::new()),
+            };
+            if segments.len() > MAX_BRACKET_DEPTH {
+                return Err(Error::custom(format!(
+                    "parameter `{base}` is nested more than {MAX_BRACKET_DEPTH} times"
+                )));
+            }
+            let name = ParameterName {
+                base,
+                segments: &segments,
+            };
+            map.entry(base.to_owned())
+                .insert_at(&name, 0, value, config.repeated_keys)?;
+        }
+        Ok(QueryValue::Map(map))
+    }
+
+    /// Insert the value at the path described by the bracket segments of `name`, starting from
+    /// the segment at `depth`.
+    fn insert_at(
+        &mut self,
+        name: &ParameterName,
+        depth: usize,
+        value: QueryValue,
+        repeated: bool,
+    ) -> Result<(), serde_urlencoded::de::Error> {
+        let segment = match name.segments.get(depth) {
+            Some(segment) => segment,
+            None if self.is_placeholder() || !matches!(self, QueryValue::Map(_)) => {
+                self.append_or_replace(value, repeated);
+                return Ok(());
+            }
+            None => return Err(name.conflict(depth)),
+        };
+        match segment {
+            Segment::Key(key) => {
+                let QueryValue::Map(map) = self else {
+                    return Err(name.conflict(depth));
+                };
+                map.entry(key.to_string())
+                    .insert_at(name, depth + 1, value, repeated)
+            }
+            Segment::Push if !self.is_placeholder() && matches!(self, QueryValue::Map(_)) => {
+                Err(name.conflict(depth))
+            }
+            Segment::Push if depth + 1 == name.segments.len() => {
+                self.append_or_replace(value, true);
+                Ok(())
+            }
+            Segment::Push => {
+                let seq = self.as_sequence();
+                seq.push(QueryValue::Map(QueryMap::default()));
+                seq.last_mut()
+                    .unwrap()
+                    .insert_at(name, depth + 1, value, repeated)
+            }
+        }
+    }
+
+    /// Append the value to the sequence of values if `append` is `true`, otherwise replace it.
+    fn append_or_replace(&mut self, value: QueryValue, append: bool) {
+        if !append || self.is_placeholder() {
+            *self = value;
+            return;
+        }
+        let seq = self.as_sequence();
+        match value {
+            QueryValue::Sequence(values) => seq.extend(values),
+            value => seq.push(value),
+        }
+    }
+
+    /// Turn the value into a sequence and return it.
+    /// A string becomes the first element of the sequence, and the empty map of a new
+    /// parameter becomes an empty sequence.
+    fn as_sequence(&mut self) -> &mut Vec<QueryValue> {
+        match self {
+            QueryValue::Sequence(_) => (),
+            QueryValue::String(s) => {
+                *self = QueryValue::Sequence(vec![QueryValue::String(std::mem::take(s))])
+            }
+            QueryValue::Map(_) => *self = QueryValue::Sequence(Vec::new()),
+        }
+        let QueryValue::Sequence(seq) = self else {
+            unreachable!()
+        };
+        seq
+    }
+
+    /// Whether the value was just created by [`QueryMap::entry`] and holds nothing yet.
+    fn is_placeholder(&self) -> bool {
+        matches!(self, QueryValue::Map(map) if map.0.is_empty())
+    }
+}
+
+/// How to parse a query string into a [`QueryValue`].
+///
+/// Every option is disabled by default, which means the parameters are flat strings and only
+/// the last value of a parameter specified multiple times is kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryConfig {
+    brackets: bool,
+    repeated_keys: bool,
+    comma_separated: bool,
+}
+
+impl QueryConfig {
+    /// Create a configuration where every option is disabled.
+    pub const fn new() -> Self {
+        QueryConfig {
+            brackets: false,
+            repeated_keys: false,
+            comma_separated: false,
+        }
+    }
+
+    /// Parse the bracket notation: `a[b]=1` is the parameter `b` nested inside `a`,
+    /// and `a[]=1&a[]=2` is a sequence of values.
+    pub const fn brackets(mut self, enabled: bool) -> Self {
+        self.brackets = enabled;
+        self
+    }
+
+    /// Parse a parameter specified multiple times, such as `a=1&a=2`, as a sequence of values
+    /// instead of keeping only the last value.
+    pub const fn repeated_keys(mut self, enabled: bool) -> Self {
+        self.repeated_keys = enabled;
+        self
+    }
+
+    /// Parse a comma-separated value, such as `a=1,2`, as a sequence of values.
+    ///
+    /// Since the strings are split on every comma, this option should only be enabled if
+    /// none of your parameters can contain a comma.
+    pub const fn comma_separated(mut self, enabled: bool) -> Self {
+        self.comma_separated = enabled;
+        self
+    }
+}
+
+/// The maximum number of brackets in the name of a parameter, like the `qs` library and Rails.
+const MAX_BRACKET_DEPTH: usize = 32;
+
+/// The name of a parameter split into its base name and its bracket segments.
+struct ParameterName<'a> {
+    base: &'a str,
+    segments: &'a [Segment<'a>],
+}
+
+impl ParameterName<'_> {
+    /// Return the error of a parameter whose first `depth` segments are both a value and a map.
+    fn conflict(&self, depth: usize) -> serde_urlencoded::de::Error {
+        let mut name = self.base.to_owned();
+        for segment in &self.segments[..depth] {
+            match segment {
+                Segment::Key(key) => name.push_str(&format!("[{key}]")),
+                Segment::Push => name.push_str("[]"),
+            }
+        }
+        Error::custom(format!("parameter `{name}` is both a value and a map"))
+    }
+}
+
+/// A part of the bracket notation of a parameter name.
+enum Segment<'a> {
+    /// `[key]`
+    Key(&'a str),
+    /// `[]`
+    Push,
+}
+
+/// Split a parameter name such as `a[b][]` into its base name and its bracket segments.
+///
+/// Return `None` if the name doesn't use the bracket notation, or if the brackets are malformed,
+/// in which case the whole name is used as is.
+fn split_brackets(key: &str) -> Option<(&str, Vec<Segment<'_>>)> {
+    let start = key.find('[').filter(|&start| start > 0)?;
+    let (base, mut rest) = key.split_at(start);
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        let inner = rest.strip_prefix('[')?;
+        let end = inner.find(']')?;
+        let segment = &inner[..end];
+        if segment.contains('[') {
+            return None;
+        }
+        segments.push(if segment.is_empty() {
+            Segment::Push
+        } else {
+            Segment::Key(segment)
+        });
+        rest = &inner[end + 1..];
+    }
+    Some((base, segments))
+}
+
+/// The parameters of a query string, in the order they were first specified.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryMap(OrderedMap<QueryValue>);
+
+impl QueryMap {
+    /// Insert a parameter, replacing the previous value of the parameter if there was one.
+    pub fn insert(&mut self, key: String, value: QueryValue) {
+        self.0.insert(key, value);
+    }
+
+    /// Insert a parameter, or append the value to the previous values of the parameter if
+    /// there was one.
+    pub fn append(&mut self, key: String, value: QueryValue) {
+        self.entry(key).append_or_replace(value, true);
+    }
+
+    /// Return the value of a parameter, inserting an empty map if there was none.
+    fn entry(&mut self, key: String) -> &mut QueryValue {
+        self.0
+            .get_or_insert_with(key, || QueryValue::Map(QueryMap::default()))
+    }
+}
+
+/// The last value of a parameter specified multiple times is kept.
+impl FromIterator<(String, QueryValue)> for QueryMap {
+    fn from_iter<I: IntoIterator<Item = (String, QueryValue)>>(iter: I) -> Self {
+        let mut map = QueryMap::default();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl Map for QueryMap {
+    type Value = QueryValue;
+    type Iter = IntoIter<QueryValue>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        self.0.remove(key)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        self.0.into_iter()
+    }
+}
+
+impl IntoValue for QueryValue {
+    type Sequence = Vec<QueryValue>;
+    type Map = QueryMap;
+
+    const COERCE_STRINGS: bool = true;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            QueryValue::String(_) => ValueKind::String,
+            QueryValue::Sequence(_) => ValueKind::Sequence,
+            QueryValue::Map(_) => ValueKind::Map,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            QueryValue::String(s) => Value::String(s),
+            QueryValue::Sequence(seq) => Value::Sequence(seq),
+            QueryValue::Map(map) => Value::Map(map),
+        }
+    }
+
+    fn string_into_sequence(s: String) -> Result<Vec<QueryValue>, String> {
+        Ok(vec![QueryValue::String(s)])
+    }
+}
@@ -0,0 +1,106 @@
+//! A MessagePack backend for deserr, deserializing the [`rmpv::Value`]s.
+//!
+//! The strings that aren't valid UTF-8 and the extension types are deserialized as byte
+//! strings. The keys of the maps are their string or, for integer keys, their decimal
+//! representation. The entries of any other key are ignored.
+//!
+//! ```
+//! use deserr::{errors::JsonError, Deserr};
+//! use rmpv::Value;
+//!
+//! #[derive(Deserr, Debug, PartialEq, Eq)]
+//! struct Request {
+//!     id: u64,
+//!     payload: Vec<u8>,
+//! }
+//!
+//! let value = Value::Map(vec![
+//!     (Value::from("id"), Value::from(42)),
+//!     (Value::from("payload"), Value::Binary(vec![1, 2, 3])),
+//! ]);
+//! let request = deserr::deserialize::<Request, _, JsonError>(value).unwrap();
+//! assert_eq!(request, Request { id: 42, payload: vec![1, 2, 3] });
+//! ```
+
+use rmpv::Value as MValue;
+
+use crate::{IntoValue, Map, Value, ValueKind};
+
+type Entries = Vec<(MValue, MValue)>;
+
+/// Return the key of a map entry, or `None` if it can't be used as a key.
+fn map_key(key: &MValue) -> Option<String> {
+    match key {
+        MValue::String(key) => key.as_str().map(str::to_owned),
+        MValue::Integer(key) => Some(key.to_string()),
+        _ => None,
+    }
+}
+
+impl Map for Entries {
+    type Value = MValue;
+    type Iter = std::iter::FilterMap<
+        std::vec::IntoIter<(MValue, MValue)>,
+        fn((MValue, MValue)) -> Option<(String, MValue)>,
+    >;
+
+    fn len(&self) -> usize {
+        self.iter()
+            .filter(|(key, _)| map_key(key).is_some())
+            .count()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let index = self
+            .iter()
+            .position(|(k, _)| map_key(k).as_deref() == Some(key))?;
+        Some(Vec::remove(self, index).1)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        <Self as IntoIterator>::into_iter(self)
+            .filter_map(|(key, value)| Some((map_key(&key)?, value)))
+    }
+}
+
+impl IntoValue for MValue {
+    type Sequence = Vec<MValue>;
+    type Map = Entries;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            MValue::Nil => ValueKind::Null,
+            MValue::Boolean(_) => ValueKind::Boolean,
+            MValue::Integer(n) if n.is_u64() => ValueKind::Integer,
+            MValue::Integer(_) => ValueKind::NegativeInteger,
+            MValue::F32(_) | MValue::F64(_) => ValueKind::Float,
+            MValue::String(s) if s.is_str() => ValueKind::String,
+            MValue::String(_) | MValue::Binary(_) | MValue::Ext(_, _) => ValueKind::Bytes,
+            MValue::Array(_) => ValueKind::Sequence,
+            MValue::Map(_) => ValueKind::Map,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            MValue::Nil => Value::Null,
+            MValue::Boolean(b) => Value::Boolean(b),
+            MValue::Integer(n) => {
+                if let Some(n) = n.as_u64() {
+                    Value::Integer(n)
+                } else if let Some(n) = n.as_i64() {
+                    Value::NegativeInteger(n)
+                } else {
+                    panic!();
+                }
+            }
+            MValue::F32(x) => Value::Float(x.into()),
+            MValue::F64(x) => Value::Float(x),
+            MValue::String(s) if s.is_str() => Value::String(s.into_str().unwrap()),
+            MValue::String(s) => Value::Bytes(s.into_bytes()),
+            MValue::Binary(bytes) | MValue::Ext(_, bytes) => Value::Bytes(bytes),
+            MValue::Array(seq) => Value::Sequence(seq),
+            MValue::Map(map) => Value::Map(map),
+        }
+    }
+}
@@ -1,5 +1,8 @@
+use std::collections::HashSet;
 use std::ops::ControlFlow;
 
+#[cfg(feature = "arbitrary-precision")]
+use crate::value::exact_float;
 use crate::{
     take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, Sequence, Value,
     ValueKind, ValuePointerRef,
@@ -29,17 +32,7 @@ impl IntoValue for JValue {
         match self {
             JValue::Null => Value::Null,
             JValue::Bool(b) => Value::Boolean(b),
-            JValue::Number(n) => {
-                if let Some(n) = n.as_u64() {
-                    Value::Integer(n)
-                } else if let Some(n) = n.as_i64() {
-                    Value::NegativeInteger(n)
-                } else if let Some(n) = n.as_f64() {
-                    Value::Float(n)
-                } else {
-                    panic!();
-                }
-            }
+            JValue::Number(n) => number_into_value(&n),
             JValue::String(x) => Value::String(x),
             JValue::Array(x) => Value::Sequence(x),
             JValue::Object(x) => Value::Map(x),
@@ -47,24 +40,147 @@ impl IntoValue for JValue {
     }
 
     fn kind(&self) -> ValueKind {
-        match self {
-            JValue::Null => ValueKind::Null,
-            JValue::Bool(_) => ValueKind::Boolean,
-            JValue::Number(n) => {
-                if n.is_u64() {
-                    ValueKind::Integer
-                } else if n.is_i64() {
-                    ValueKind::NegativeInteger
-                } else if n.is_f64() {
-                    ValueKind::Float
-                } else {
-                    panic!();
-                }
+        json_value_kind(self)
+    }
+}
+
+/// With the `arbitrary-precision` feature, the numbers which are not 64 bits integers and
+/// which would be changed by a float keep their exact text, see [`Value::Number`].
+fn number_into_value<V: IntoValue>(n: &Number) -> Value<V> {
+    if let Some(n) = n.as_u64() {
+        Value::Integer(n)
+    } else if let Some(n) = n.as_i64() {
+        Value::NegativeInteger(n)
+    } else if let Some(text) = inexact_text(n) {
+        Value::Number(text.to_owned())
+    } else if let Some(n) = n.as_f64() {
+        Value::Float(n)
+    } else {
+        panic!();
+    }
+}
+
+/// The text of a number which isn't written back as the same float.
+#[cfg(feature = "arbitrary-precision")]
+fn inexact_text(n: &Number) -> Option<&str> {
+    let text = n.as_str();
+    exact_float(text).is_none().then_some(text)
+}
+
+/// Without the `arbitrary-precision` feature, the numbers are already parsed as floats.
+#[cfg(not(feature = "arbitrary-precision"))]
+fn inexact_text(_: &Number) -> Option<&str> {
+    None
+}
+
+fn json_value_kind(value: &JValue) -> ValueKind {
+    match value {
+        JValue::Null => ValueKind::Null,
+        JValue::Bool(_) => ValueKind::Boolean,
+        JValue::Number(n) => {
+            if n.is_u64() {
+                ValueKind::Integer
+            } else if n.is_i64() {
+                ValueKind::NegativeInteger
+            } else if inexact_text(n).is_some() {
+                ValueKind::Number
+            } else if n.is_f64() {
+                ValueKind::Float
+            } else {
+                panic!();
             }
-            JValue::String(_) => ValueKind::String,
-            JValue::Array(_) => ValueKind::Sequence,
-            JValue::Object(_) => ValueKind::Map,
         }
+        JValue::String(_) => ValueKind::String,
+        JValue::Array(_) => ValueKind::Sequence,
+        JValue::Object(_) => ValueKind::Map,
+    }
+}
+
+/// Deserialize a value without cloning it, only its strings and keys are copied.
+impl<'a> IntoValue for &'a JValue {
+    type Sequence = &'a [JValue];
+    type Map = JsonMapRef<'a>;
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            JValue::Null => Value::Null,
+            JValue::Bool(b) => Value::Boolean(*b),
+            JValue::Number(n) => number_into_value(n),
+            JValue::String(x) => Value::String(x.clone()),
+            JValue::Array(x) => Value::Sequence(x.as_slice()),
+            JValue::Object(x) => Value::Map(JsonMapRef::new(x)),
+        }
+    }
+
+    fn kind(&self) -> ValueKind {
+        json_value_kind(self)
+    }
+}
+
+impl<'a> Sequence for &'a [JValue] {
+    type Value = &'a JValue;
+    type Iter = std::slice::Iter<'a, JValue>;
+
+    fn len(&self) -> usize {
+        <[JValue]>::len(self)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        self.iter()
+    }
+}
+
+/// A borrowed JSON object, see the implementation of [`IntoValue`] for `&serde_json::Value`.
+///
+/// The removed keys are remembered instead of being removed from the object.
+pub struct JsonMapRef<'a> {
+    map: &'a JMap<String, JValue>,
+    removed: HashSet<&'a str>,
+}
+
+impl<'a> JsonMapRef<'a> {
+    pub fn new(map: &'a JMap<String, JValue>) -> Self {
+        JsonMapRef {
+            map,
+            removed: HashSet::new(),
+        }
+    }
+}
+
+impl<'a> Map for JsonMapRef<'a> {
+    type Value = &'a JValue;
+    type Iter = JsonMapRefIter<'a>;
+
+    fn len(&self) -> usize {
+        self.map.len() - self.removed.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let (key, value) = self.map.get_key_value(key)?;
+        self.removed.insert(key).then_some(value)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        JsonMapRefIter {
+            iter: self.map.iter(),
+            removed: self.removed,
+        }
+    }
+}
+
+/// The iterator over the keys of a [`JsonMapRef`] that were not removed.
+pub struct JsonMapRefIter<'a> {
+    iter: serde_json::map::Iter<'a>,
+    removed: HashSet<&'a str>,
+}
+
+impl<'a> Iterator for JsonMapRefIter<'a> {
+    type Item = (String, &'a JValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .find(|(key, _)| !self.removed.contains(key.as_str()))
+            .map(|(key, value)| (key.clone(), value))
     }
 }
 
@@ -91,7 +207,21 @@ impl<E: DeserializeError> Deserr<E> for JValue {
                     )));
                 }
             },
+            // without the `arbitrary_precision` feature of serde_json, it is rounded to a float
+            Value::Number(x) => match x.parse::<Number>() {
+                Ok(n) => JValue::Number(n),
+                Err(_) => {
+                    return Err(take_cf_content(E::error::<V>(
+                        error,
+                        ErrorKind::Unexpected {
+                            msg: format!("the number {x} is not representable in JSON"),
+                        },
+                        location,
+                    )));
+                }
+            },
             Value::String(s) => JValue::String(s),
+            Value::Bytes(bytes) => JValue::Array(bytes.into_iter().map(JValue::from).collect()),
             Value::Sequence(seq) => {
                 let mut jseq = Vec::with_capacity(seq.len());
                 for (index, value) in seq.into_iter().enumerate() {
@@ -155,7 +285,10 @@ impl<V: IntoValue> From<Value<V>> for JValue {
             Value::Float(f) => Number::from_f64(f)
                 .map(JValue::Number)
                 .unwrap_or(JValue::Null),
+            Value::Number(x) => x.parse().map(JValue::Number).unwrap_or(JValue::Null),
             Value::String(s) => JValue::String(s),
+            // JSON has no byte strings, they are arrays of numbers as in serde_json
+            Value::Bytes(bytes) => JValue::Array(bytes.into_iter().map(JValue::from).collect()),
             Value::Sequence(s) => JValue::Array(
                 s.into_iter()
                     .map(IntoValue::into_value)
@@ -170,6 +303,55 @@ impl<V: IntoValue> From<Value<V>> for JValue {
     }
 }
 
+/// Whether the value of a `Content-Type` header is `application/json` or a `+json` suffix.
+#[cfg(any(
+    feature = "actix-web",
+    feature = "axum",
+    feature = "poem",
+    feature = "warp"
+))]
+pub(crate) fn is_json_content_type(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    mime == "application/json" || (mime.starts_with("application/") && mime.ends_with("+json"))
+}
+
+/// Convert a Json parsing error into an [`ErrorKind::Syntax`], the position of the error
+/// being removed from its message.
+#[cfg(any(feature = "axum", feature = "poem", feature = "warp"))]
+pub(crate) fn syntax_error<E: DeserializeError>(error: serde_json::Error) -> E {
+    let (line, column) = (error.line(), error.column());
+    syntax_error_at(error, line, column, ValuePointerRef::Origin)
+}
+
+/// Same as [`syntax_error`], but the position of the error and its location are given by
+/// the caller, for the payloads parsed one part at a time.
+pub(crate) fn syntax_error_at<E: DeserializeError>(
+    error: serde_json::Error,
+    line: usize,
+    column: usize,
+    location: ValuePointerRef,
+) -> E {
+    let msg = error.to_string();
+    let msg = msg
+        .strip_suffix(&format!(
+            " at line {} column {}",
+            error.line(),
+            error.column()
+        ))
+        .unwrap_or(&msg)
+        .to_owned();
+    take_cf_content(E::error::<std::convert::Infallible>(
+        None,
+        ErrorKind::Syntax { msg, line, column },
+        location,
+    ))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -209,4 +391,91 @@ mod test {
 
         assert_eq!(value, deserr);
     }
+
+    #[test]
+    fn deserialize_borrowed_value() {
+        use crate::errors::JsonError;
+        use crate::Deserr;
+
+        #[allow(dead_code)]
+        #[derive(Debug, Deserr)]
+        #[deserr(deny_unknown_fields)]
+        struct Doggo {
+            name: String,
+            age: i8,
+            toys: Vec<Option<String>>,
+            #[deserr(default)]
+            owner: Option<JValue>,
+        }
+
+        let value = json!({ "name": "kefir", "age": -1, "toys": ["ball", null], "owner": { "name": "tamo" } });
+        let doggo = crate::deserialize::<Doggo, _, JsonError>(&value).unwrap();
+        insta::assert_debug_snapshot!(doggo, @r###"
+        Doggo {
+            name: "kefir",
+            age: -1,
+            toys: [
+                Some(
+                    "ball",
+                ),
+                None,
+            ],
+            owner: Some(
+                Object {
+                    "name": String("tamo"),
+                },
+            ),
+        }
+        "###);
+
+        let value = json!({ "name": "kefir", "age": 2, "toys": [], "color": "white" });
+        let error = crate::deserialize::<Doggo, _, JsonError>(&value).unwrap_err();
+        insta::assert_snapshot!(error, @"Unknown field `color`: expected one of `name`, `age`, `toys`, `owner`");
+        // the value is only borrowed
+        assert_eq!(value["color"], "white");
+    }
+
+    #[cfg(feature = "arbitrary-precision")]
+    #[test]
+    fn arbitrary_precision() {
+        use crate::errors::JsonError;
+        use crate::{Decimal, Deserr};
+
+        #[allow(dead_code)]
+        #[derive(Debug, Deserr)]
+        struct Numbers {
+            id: u128,
+            price: Decimal,
+            ratio: f64,
+            raw: JValue,
+        }
+
+        let text = r#"{ "id": 340282366920938463463374607431768211455, "price": 12.50, "ratio": 0.5, "raw": [18446744073709551616, 0.1000000000000000000001] }"#;
+        let value: JValue = serde_json::from_str(text).unwrap();
+        let numbers = crate::deserialize::<Numbers, _, JsonError>(value).unwrap();
+        insta::assert_debug_snapshot!(numbers, @r###"
+        Numbers {
+            id: 340282366920938463463374607431768211455,
+            price: Decimal(
+                "12.50",
+            ),
+            ratio: 0.5,
+            raw: Array [
+                Number(18446744073709551616),
+                Number(0.1000000000000000000001),
+            ],
+        }
+        "###);
+        assert_eq!(
+            numbers.raw.to_string(),
+            "[18446744073709551616,0.1000000000000000000001]"
+        );
+
+        let value: JValue = serde_json::from_str("18446744073709551616").unwrap();
+        let error = crate::deserialize::<u64, _, JsonError>(value).unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value: expected a value between 0 and 18446744073709551615, but found 18446744073709551616");
+        let value: JValue = serde_json::from_str("1e400").unwrap();
+        let error = crate::deserialize::<f64, _, JsonError>(value).unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value: value: `1e+400` is outside of the range of a `f64`");
+    }
 }
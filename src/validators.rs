@@ -0,0 +1,363 @@
+//! This module contains the building blocks of the declarative validation attributes:
+//! `range`, `length`, `non_empty` and `pattern` on fields, and `requires`, `conflicts_with`
+//! and `exactly_one_of` on containers.
+//!
+//! The derive macro calls the `check_*` functions right after a field has been deserialized,
+//! or once all the fields of a container have been collected.
+//! If you want one of these attributes to work with your own types, you only need to implement
+//! the corresponding trait.
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    convert::Infallible,
+    fmt::Display,
+    ops::ControlFlow,
+};
+
+#[cfg(feature = "regex")]
+pub use regex::Regex;
+
+use crate::{take_cf_content, DeserializeError, ErrorKind, ValuePointerRef};
+
+/// A value that can be checked by the `range` attribute.
+pub trait RangeValue {
+    /// The type of the bounds given to the attribute.
+    type Bound: PartialOrd + Display;
+
+    /// Return the value to compare against the bounds, or `None` if there is nothing to check.
+    fn range_value(&self) -> Option<&Self::Bound>;
+}
+
+/// A value that can be checked by the `length` and `non_empty` attributes.
+pub trait LengthValue {
+    /// Return the length of the value, or `None` if there is nothing to check.
+    fn length_value(&self) -> Option<usize>;
+}
+
+/// A value that can be checked by the `pattern` attribute.
+pub trait PatternValue {
+    /// Return the string to match against the pattern, or `None` if there is nothing to check.
+    fn pattern_value(&self) -> Option<&str>;
+}
+
+macro_rules! range_value_impl {
+    ($($t:ty),*) => {
+        $(
+            impl RangeValue for $t {
+                type Bound = $t;
+
+                fn range_value(&self) -> Option<&Self::Bound> {
+                    Some(self)
+                }
+            }
+        )*
+    };
+}
+
+range_value_impl!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char);
+
+impl<T: RangeValue> RangeValue for Option<T> {
+    type Bound = T::Bound;
+
+    fn range_value(&self) -> Option<&Self::Bound> {
+        self.as_ref().and_then(T::range_value)
+    }
+}
+
+impl<T: RangeValue> RangeValue for Box<T> {
+    type Bound = T::Bound;
+
+    fn range_value(&self) -> Option<&Self::Bound> {
+        T::range_value(self)
+    }
+}
+
+/// The length of a string is its number of characters, not its number of bytes.
+impl LengthValue for String {
+    fn length_value(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl<T> LengthValue for Vec<T> {
+    fn length_value(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<K, V, S> LengthValue for HashMap<K, V, S> {
+    fn length_value(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<K, V> LengthValue for BTreeMap<K, V> {
+    fn length_value(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T, S> LengthValue for HashSet<T, S> {
+    fn length_value(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T> LengthValue for BTreeSet<T> {
+    fn length_value(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T: LengthValue> LengthValue for Option<T> {
+    fn length_value(&self) -> Option<usize> {
+        self.as_ref().and_then(T::length_value)
+    }
+}
+
+impl<T: LengthValue> LengthValue for Box<T> {
+    fn length_value(&self) -> Option<usize> {
+        T::length_value(self)
+    }
+}
+
+impl PatternValue for String {
+    fn pattern_value(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl<T: PatternValue> PatternValue for Option<T> {
+    fn pattern_value(&self) -> Option<&str> {
+        self.as_ref().and_then(T::pattern_value)
+    }
+}
+
+impl<T: PatternValue> PatternValue for Box<T> {
+    fn pattern_value(&self) -> Option<&str> {
+        T::pattern_value(self)
+    }
+}
+
+/// Return an [`ErrorKind::OutOfRange`] error if the value is lower than `min` or greater than `max`,
+/// or if it can't be compared to them, like a NaN float.
+pub fn check_range<T, E>(
+    value: &T,
+    min: Option<T::Bound>,
+    max: Option<T::Bound>,
+    location: ValuePointerRef,
+) -> Result<(), E>
+where
+    T: RangeValue + ?Sized,
+    E: DeserializeError,
+{
+    let actual = match value.range_value() {
+        Some(actual) => actual,
+        None => return Ok(()),
+    };
+    // a value which can't be compared to a bound, like a NaN float, is never in the range
+    let too_small = min
+        .as_ref()
+        .is_some_and(|min| matches!(actual.partial_cmp(min), None | Some(Ordering::Less)));
+    let too_large = max
+        .as_ref()
+        .is_some_and(|max| matches!(actual.partial_cmp(max), None | Some(Ordering::Greater)));
+
+    if too_small || too_large {
+        Err(take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::OutOfRange {
+                actual,
+                min: min.as_ref().map(|min| min as &dyn Display),
+                max: max.as_ref().map(|max| max as &dyn Display),
+            },
+            location,
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Return an [`ErrorKind::BadLength`] error if the length of the value is lower than `min`
+/// or greater than `max`.
+pub fn check_length<T, E>(
+    value: &T,
+    min: Option<usize>,
+    max: Option<usize>,
+    location: ValuePointerRef,
+) -> Result<(), E>
+where
+    T: LengthValue + ?Sized,
+    E: DeserializeError,
+{
+    let actual = match value.length_value() {
+        Some(actual) => actual,
+        None => return Ok(()),
+    };
+    let too_small = min.is_some_and(|min| actual < min);
+    let too_large = max.is_some_and(|max| actual > max);
+
+    if too_small || too_large {
+        Err(take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::BadLength { actual, min, max },
+            location,
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Return an [`ErrorKind::EmptyValue`] error if the length of the value is zero.
+pub fn check_non_empty<T, E>(value: &T, location: ValuePointerRef) -> Result<(), E>
+where
+    T: LengthValue + ?Sized,
+    E: DeserializeError,
+{
+    if value.length_value() == Some(0) {
+        Err(take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::EmptyValue,
+            location,
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Compile the regex of a `pattern` attribute.
+///
+/// # Panics
+///
+/// Panics if the pattern is not a valid regex, which the derive macro already checks at
+/// compile time.
+#[cfg(feature = "regex")]
+pub fn compile_pattern(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("Invalid regex in the `pattern` attribute")
+}
+
+/// Check the value of a field against the regex of its `pattern` attribute, which is only
+/// compiled once.
+#[cfg(feature = "regex")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check_pattern {
+    ($value:expr, $pattern:literal, $error:ty, $location:expr) => {{
+        static DESERR_PATTERN__: ::std::sync::OnceLock<$crate::validators::Regex> =
+            ::std::sync::OnceLock::new();
+        let regex = DESERR_PATTERN__.get_or_init(|| $crate::validators::compile_pattern($pattern));
+        $crate::validators::check_pattern::<_, $error>($value, regex, $location)
+    }};
+}
+
+#[cfg(not(feature = "regex"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check_pattern {
+    ($($tt:tt)*) => {
+        ::std::compile_error!("The `pattern` attribute requires the `regex` feature of deserr.")
+    };
+}
+
+/// Return an [`ErrorKind::PatternMismatch`] error if the value doesn't match the regex.
+#[cfg(feature = "regex")]
+pub fn check_pattern<T, E>(value: &T, regex: &Regex, location: ValuePointerRef) -> Result<(), E>
+where
+    T: PatternValue + ?Sized,
+    E: DeserializeError,
+{
+    match value.pattern_value() {
+        Some(value) if !regex.is_match(value) => Err(take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::PatternMismatch {
+                value,
+                pattern: regex.as_str(),
+            },
+            location,
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Return an [`ErrorKind::MissingRequiredFields`] error if `field` is present but some of the
+/// `others` fields are missing.
+///
+/// Each field is given as its key name and whether it was present in the map.
+pub fn check_requires<E: DeserializeError>(
+    error: Option<E>,
+    field: (&str, bool),
+    others: &[(&str, bool)],
+    location: ValuePointerRef,
+) -> ControlFlow<E, Option<E>> {
+    let missing = keys_where(others, false);
+    if !field.1 || missing.is_empty() {
+        return ControlFlow::Continue(error);
+    }
+    let kind = ErrorKind::MissingRequiredFields::<Infallible> {
+        field: field.0,
+        missing: &missing,
+    };
+    merge_constraint_error(error, kind, location)
+}
+
+/// Return an [`ErrorKind::ConflictingFields`] error if `field` is present together with
+/// some of the `others` fields.
+///
+/// Each field is given as its key name and whether it was present in the map.
+pub fn check_conflicts_with<E: DeserializeError>(
+    error: Option<E>,
+    field: (&str, bool),
+    others: &[(&str, bool)],
+    location: ValuePointerRef,
+) -> ControlFlow<E, Option<E>> {
+    let conflicting = keys_where(others, true);
+    if !field.1 || conflicting.is_empty() {
+        return ControlFlow::Continue(error);
+    }
+    let kind = ErrorKind::ConflictingFields::<Infallible> {
+        field: field.0,
+        conflicting: &conflicting,
+    };
+    merge_constraint_error(error, kind, location)
+}
+
+/// Return an [`ErrorKind::NotExactlyOneOf`] error if none or more than one of the `fields`
+/// is present.
+///
+/// Each field is given as its key name and whether it was present in the map.
+pub fn check_exactly_one_of<E: DeserializeError>(
+    error: Option<E>,
+    fields: &[(&str, bool)],
+    location: ValuePointerRef,
+) -> ControlFlow<E, Option<E>> {
+    let present = keys_where(fields, true);
+    if present.len() == 1 {
+        return ControlFlow::Continue(error);
+    }
+    let fields = fields.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+    let kind = ErrorKind::NotExactlyOneOf::<Infallible> {
+        fields: &fields,
+        present: &present,
+    };
+    merge_constraint_error(error, kind, location)
+}
+
+fn keys_where<'a>(fields: &[(&'a str, bool)], present: bool) -> Vec<&'a str> {
+    fields
+        .iter()
+        .filter(|(_, p)| *p == present)
+        .map(|(key, _)| *key)
+        .collect()
+}
+
+fn merge_constraint_error<E: DeserializeError>(
+    error: Option<E>,
+    kind: ErrorKind<Infallible>,
+    location: ValuePointerRef,
+) -> ControlFlow<E, Option<E>> {
+    match E::error(error, kind, location) {
+        ControlFlow::Continue(e) => ControlFlow::Continue(Some(e)),
+        ControlFlow::Break(e) => ControlFlow::Break(e),
+    }
+}
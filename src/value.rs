@@ -21,8 +21,9 @@ use std::{
 /// A `ValuePointerRef` is an immutable data structure, so it is cheap to extend and to copy.
 /// However, if you want to store it inside an owned type, you may want to convert it to a
 /// [`ValuePointer`] instead using [`self.to_owned()`](ValuePointerRef::to_owned).
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default)]
 pub enum ValuePointerRef<'a> {
+    #[default]
     Origin,
     Key {
         key: &'a str,
@@ -34,12 +35,6 @@ pub enum ValuePointerRef<'a> {
     },
 }
 
-impl Default for ValuePointerRef<'_> {
-    fn default() -> Self {
-        Self::Origin
-    }
-}
-
 impl<'a> ValuePointerRef<'a> {
     /// Extend `self` such that it points to the next subvalue at the given `key`.
     #[must_use]
@@ -124,7 +119,9 @@ pub enum ValueKind {
     Integer,
     NegativeInteger,
     Float,
+    Number,
     String,
+    Bytes,
     Sequence,
     Map,
 }
@@ -137,7 +134,9 @@ impl Display for ValueKind {
             ValueKind::Integer => write!(f, "Integer"),
             ValueKind::NegativeInteger => write!(f, "NegativeInteger"),
             ValueKind::Float => write!(f, "Float"),
+            ValueKind::Number => write!(f, "Number"),
             ValueKind::String => write!(f, "String"),
+            ValueKind::Bytes => write!(f, "Bytes"),
             ValueKind::Sequence => write!(f, "Sequence"),
             ValueKind::Map => write!(f, "Map"),
         }
@@ -163,12 +162,75 @@ pub enum Value<V: IntoValue> {
     Integer(u64),
     NegativeInteger(i64),
     Float(f64),
+    /// A number given as its exact decimal text, e.g. `340282366920938463463374607431768211455`.
+    ///
+    /// It is used for the integers which don't fit in the other variants, and for the decimal
+    /// numbers of the formats keeping their exact text which would be changed by a float.
+    Number(String),
     String(String),
+    /// A byte string, for the binary formats such as CBOR or MessagePack.
+    Bytes(Vec<u8>),
     Sequence(V::Sequence),
     Map(V::Map),
 }
 
 impl<V: IntoValue> Value<V> {
+    /// If [`V::COERCE_STRINGS`](IntoValue::COERCE_STRINGS) is `true`, parse a string
+    /// containing a number into an integer or a float. The integers which don't fit in 64 bits
+    /// are kept as a [`Value::Number`].
+    ///
+    /// Any other value is returned unchanged.
+    pub fn coerce_to_number(self) -> Self {
+        match self {
+            // the numbers of the standard library may start with a `+`, unlike the numbers of JSON
+            Value::String(s) if V::COERCE_STRINGS && !s.starts_with('+') => {
+                if let Ok(x) = s.parse::<u64>() {
+                    Value::Integer(x)
+                } else if let Ok(x) = s.parse::<i64>() {
+                    Value::NegativeInteger(x)
+                } else if is_integer_text(&s) {
+                    Value::Number(s)
+                } else {
+                    match s.parse::<f64>() {
+                        // `inf` and `NaN` are not numbers for the user
+                        Ok(x) if x.is_finite() => Value::Float(x),
+                        _ => Value::String(s),
+                    }
+                }
+            }
+            value => value,
+        }
+    }
+
+    /// If [`V::COERCE_STRINGS`](IntoValue::COERCE_STRINGS) is `true`, parse a `"true"`
+    /// or `"false"` string into a boolean.
+    ///
+    /// Any other value is returned unchanged.
+    pub fn coerce_to_boolean(self) -> Self {
+        match self {
+            Value::String(s) if V::COERCE_STRINGS => match s.as_str() {
+                "true" => Value::Boolean(true),
+                "false" => Value::Boolean(false),
+                _ => Value::String(s),
+            },
+            value => value,
+        }
+    }
+
+    /// If [`V::COERCE_STRINGS`](IntoValue::COERCE_STRINGS) is `true`, wrap a string into
+    /// a sequence containing only this string, see [`IntoValue::string_into_sequence`].
+    ///
+    /// Any other value is returned unchanged.
+    pub fn coerce_to_sequence(self) -> Self {
+        match self {
+            Value::String(s) if V::COERCE_STRINGS => match V::string_into_sequence(s) {
+                Ok(seq) => Value::Sequence(seq),
+                Err(s) => Value::String(s),
+            },
+            value => value,
+        }
+    }
+
     pub fn kind(&self) -> ValueKind {
         match self {
             Value::Null => ValueKind::Null,
@@ -176,20 +238,102 @@ impl<V: IntoValue> Value<V> {
             Value::Integer(_) => ValueKind::Integer,
             Value::NegativeInteger(_) => ValueKind::NegativeInteger,
             Value::Float(_) => ValueKind::Float,
+            Value::Number(_) => ValueKind::Number,
             Value::String(_) => ValueKind::String,
+            Value::Bytes(_) => ValueKind::Bytes,
             Value::Sequence(_) => ValueKind::Sequence,
             Value::Map(_) => ValueKind::Map,
         }
     }
 }
 
+/// Whether the text is an integer, e.g. `-12`, as found in a [`Value::Number`].
+pub(crate) fn is_integer_text(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Return the float of a decimal text if it is written back as the same text, e.g. `1.5`.
+///
+/// The integers, and the decimals losing their precision or their formatting as a float such
+/// as `0.1000000000000000000001` or `12.50`, are kept as a [`Value::Number`].
+pub(crate) fn exact_float(s: &str) -> Option<f64> {
+    if is_integer_text(s) {
+        return None;
+    }
+    let x = s.parse::<f64>().ok().filter(|x| x.is_finite())?;
+    // the debug representation is the shortest one, with a fractional part or an exponent
+    (format!("{x:?}") == s).then_some(x)
+}
+
+/// Whether the text is a decimal number as written in JSON, e.g. `-12.50e3`.
+pub(crate) fn is_decimal_text(s: &str) -> bool {
+    let s = s.strip_prefix('-').unwrap_or(s);
+    let (s, exponent) = match s.split_once(['e', 'E']) {
+        Some((s, exponent)) => (s, Some(exponent)),
+        None => (s, None),
+    };
+    let (integer, fraction) = match s.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (s, None),
+    };
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    digits(integer)
+        && fraction.map_or(true, digits)
+        && exponent.map_or(true, |exponent| {
+            digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent))
+        })
+}
+
+/// Return the decimal representation of the given big-endian unsigned integer, for the
+/// integers which don't fit in a `u128`.
+#[cfg(any(feature = "ciborium", feature = "json5"))]
+pub(crate) fn decimal_text(bytes: &[u8]) -> String {
+    let mut bytes = bytes.to_vec();
+    let mut digits = Vec::new();
+    while bytes.iter().any(|b| *b != 0) {
+        let mut remainder = 0u32;
+        for byte in bytes.iter_mut() {
+            let current = remainder * 256 + u32::from(*byte);
+            *byte = (current / 10) as u8;
+            remainder = current % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    digits
+        .iter()
+        .rev()
+        .map(|digit| char::from(*digit))
+        .collect()
+}
+
 /// A trait for a value that can be deserialized via [`Deserr`].
 pub trait IntoValue: Sized {
     type Sequence: Sequence<Value = Self>;
     type Map: Map<Value = Self>;
 
+    /// Whether the strings of this format can also represent numbers and booleans.
+    ///
+    /// It is `true` for formats where every value is a string, such as query parameters.
+    /// In this case, the implementations of [`Deserr`] for the numbers and booleans parse them
+    /// from strings, see [`Value::coerce_to_number`] and [`Value::coerce_to_boolean`].
+    /// The sequences also accept a single string, see [`Value::coerce_to_sequence`].
+    const COERCE_STRINGS: bool = false;
+
     fn kind(&self) -> ValueKind;
     fn into_value(self) -> Value<Self>;
+
+    /// Build a sequence containing only the given string.
+    ///
+    /// It is used by [`Value::coerce_to_sequence`] for the formats where a parameter specified
+    /// once can't be told apart from a sequence of one element, such as query parameters.
+    /// By default the string is given back, meaning it can't be turned into a sequence.
+    fn string_into_sequence(s: String) -> Result<Self::Sequence, String> {
+        Err(s)
+    }
 }
 
 /// A sequence of values conforming to [`IntoValue`].
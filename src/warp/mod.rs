@@ -0,0 +1,18 @@
+#[cfg(feature = "serde-json")]
+mod query_parameters;
+#[cfg(feature = "serde-json")]
+mod serde_json;
+
+#[cfg(feature = "serde-json")]
+pub use self::query_parameters::{query, query_with_config, WarpQuery};
+#[cfg(feature = "serde-json")]
+pub use self::serde_json::{json, WarpJson};
+
+/// The rejection of the deserr filters, containing the error returned by deserr.
+///
+/// Find it in a [`recover`](warp::Filter::recover) handler with
+/// [`Rejection::find`](warp::Rejection::find) to turn it into a reply.
+#[derive(Debug)]
+pub struct WarpRejection<E>(pub E);
+
+impl<E: std::fmt::Debug + Send + Sync + 'static> warp::reject::Reject for WarpRejection<E> {}
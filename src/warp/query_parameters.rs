@@ -0,0 +1,75 @@
+use std::convert::Infallible;
+use std::marker::PhantomData;
+
+use super::WarpRejection;
+use crate::errors::QueryParamError;
+use crate::query_string::{QueryConfig, QueryValue};
+use crate::{take_cf_content, DeserializeError, Deserr, ErrorKind, ValuePointerRef};
+use warp::http::StatusCode;
+use warp::{Filter, Rejection, Reply};
+
+/// Typed data from the query parameters of a request deserialised by deserr, extracted by
+/// the [`query`] filter.
+#[derive(Debug)]
+pub struct WarpQuery<T, E>(pub T, PhantomData<E>);
+
+impl<T, E> WarpQuery<T, E> {
+    pub fn new(data: T) -> Self {
+        WarpQuery(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// A filter extracting typed data from the query parameters of a request, deserialised by
+/// deserr.
+///
+/// The type `T` must implement the [`deserr::Deserr<E>`] trait. The type `E` must implement
+/// the [`DeserializeError`] trait. The request is rejected with a [`WarpRejection<E>`] if the
+/// query parameters cannot be deserialized.
+///
+/// The numbers and booleans are parsed from their string representation.
+pub fn query<T, E>() -> impl Filter<Extract = (WarpQuery<T, E>,), Error = Rejection> + Clone
+where
+    E: DeserializeError + std::fmt::Debug + Send + Sync + 'static,
+    T: Deserr<E> + Send,
+{
+    query_with_config(QueryConfig::default())
+}
+
+/// Same as [`query`], with the given [`QueryConfig`].
+pub fn query_with_config<T, E>(
+    config: QueryConfig,
+) -> impl Filter<Extract = (WarpQuery<T, E>,), Error = Rejection> + Clone
+where
+    E: DeserializeError + std::fmt::Debug + Send + Sync + 'static,
+    T: Deserr<E> + Send,
+{
+    warp::query::raw()
+        .or(warp::any().map(String::new))
+        .unify()
+        .and_then(move |query: String| async move {
+            let value = QueryValue::from_query_with_config(&query, config).map_err(|e| {
+                let error: E = take_cf_content(E::error::<Infallible>(
+                    None,
+                    ErrorKind::Unexpected {
+                        msg: format!("Failed to deserialize query string: {e}"),
+                    },
+                    ValuePointerRef::Origin,
+                ));
+                warp::reject::custom(WarpRejection(error))
+            })?;
+            match deserr::deserialize::<_, _, E>(value) {
+                Ok(data) => Ok(WarpQuery::new(data)),
+                Err(e) => Err(warp::reject::custom(WarpRejection(e))),
+            }
+        })
+}
+
+impl Reply for QueryParamError {
+    fn into_response(self) -> warp::reply::Response {
+        warp::reply::with_status(self.to_string(), StatusCode::BAD_REQUEST).into_response()
+    }
+}
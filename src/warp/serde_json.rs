@@ -0,0 +1,70 @@
+use std::marker::PhantomData;
+
+use super::WarpRejection;
+use crate::errors::{JsonBodyError, JsonError};
+use crate::serde_json::{is_json_content_type, syntax_error};
+use crate::{take_cf_content, DeserializeError, Deserr, MergeWithError, ValuePointerRef};
+use warp::http::StatusCode;
+use warp::hyper::body::Bytes;
+use warp::{Filter, Rejection, Reply};
+
+/// Typed data from Json request payloads deserialised by deserr, extracted by the
+/// [`json`] filter.
+#[derive(Debug)]
+pub struct WarpJson<T, E>(pub T, PhantomData<E>);
+
+impl<T, E> WarpJson<T, E> {
+    pub fn new(data: T) -> Self {
+        WarpJson(data, PhantomData)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// A filter extracting typed data from Json request payloads, deserialised by deserr.
+///
+/// The type `T` must implement the [`deserr::Deserr<E>`] trait. The type `E` must implement
+/// the [`DeserializeError`] + [`MergeWithError<JsonBodyError>`] traits.
+///
+/// The request is rejected with a [`WarpRejection<E>`] if its content type is not
+/// `application/json`, if it is not a valid Json, see
+/// [`ErrorKind::Syntax`](crate::ErrorKind::Syntax), or if it cannot be deserialized.
+/// The size of the payload can be limited with the
+/// [`content_length_limit`](warp::body::content_length_limit) filter.
+pub fn json<T, E>() -> impl Filter<Extract = (WarpJson<T, E>,), Error = Rejection> + Clone
+where
+    E: DeserializeError + MergeWithError<JsonBodyError> + std::fmt::Debug + Send + Sync + 'static,
+    T: Deserr<E> + Send,
+{
+    warp::header::optional::<String>("content-type")
+        .and(warp::body::bytes())
+        .and_then(|content_type: Option<String>, bytes: Bytes| async move {
+            let body_error = |error| {
+                let error: E = take_cf_content(E::merge(None, error, ValuePointerRef::Origin));
+                warp::reject::custom(WarpRejection(error))
+            };
+
+            match content_type {
+                None => return Err(body_error(JsonBodyError::MissingContentType)),
+                Some(content_type) if !is_json_content_type(&content_type) => {
+                    return Err(body_error(JsonBodyError::InvalidContentType(content_type)))
+                }
+                Some(_) => (),
+            }
+
+            let value = serde_json::from_slice::<serde_json::Value>(&bytes)
+                .map_err(|e| warp::reject::custom(WarpRejection(syntax_error::<E>(e))))?;
+            match deserr::deserialize::<_, _, E>(value) {
+                Ok(data) => Ok(WarpJson::new(data)),
+                Err(e) => Err(warp::reject::custom(WarpRejection(e))),
+            }
+        })
+}
+
+impl Reply for JsonError {
+    fn into_response(self) -> warp::reply::Response {
+        warp::reply::with_status(self.to_string(), StatusCode::BAD_REQUEST).into_response()
+    }
+}
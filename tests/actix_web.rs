@@ -0,0 +1,559 @@
+use actix_web::dev::{ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::rt::System;
+use actix_web::test::{call_service, init_service, read_body, TestRequest};
+use actix_web::{web, App, HttpResponse};
+use deserr::actix_web::{
+    AwebForm, AwebFormConfig, AwebHeaders, AwebJson, AwebJsonArray, AwebJsonArrayConfig,
+    AwebJsonConfig, AwebMultipart, AwebMultipartConfig, AwebNdJson, AwebNdJsonConfig, AwebPath,
+};
+use deserr::errors::{FormError, HeaderError, JsonError, MultipartError, PathError};
+use deserr::multipart::File;
+use deserr::query_string::QueryConfig;
+use deserr::Deserr;
+use futures::StreamExt;
+use insta::assert_snapshot;
+
+/// The service factory of the test apps, so their type can be named.
+trait AppFactory:
+    ServiceFactory<
+        ServiceRequest,
+        Config = (),
+        Response = ServiceResponse,
+        Error = actix_web::Error,
+        InitError = (),
+        Future: 'static,
+    > + 'static
+{
+}
+
+impl<T> AppFactory for T where
+    T: ServiceFactory<
+            ServiceRequest,
+            Config = (),
+            Response = ServiceResponse,
+            Error = actix_web::Error,
+            InitError = (),
+            Future: 'static,
+        > + 'static
+{
+}
+
+fn send(app: App<impl AppFactory>, request: TestRequest) -> (StatusCode, String) {
+    System::new().block_on(async {
+        let app = init_service(app).await;
+        let response = call_service(&app, request.to_request()).await;
+        let status = response.status();
+        let body = read_body(response).await;
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    })
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Signup {
+    name: String,
+    age: u8,
+    #[deserr(default)]
+    newsletter: bool,
+    #[deserr(default)]
+    address: Option<Address>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Address {
+    city: String,
+}
+
+async fn signup(item: AwebForm<Signup, FormError>) -> HttpResponse {
+    HttpResponse::Ok().body(format!("{:?}", item.0))
+}
+
+const FORM: &str = "application/x-www-form-urlencoded";
+
+fn form_app(config: AwebFormConfig) -> App<impl AppFactory> {
+    App::new()
+        .app_data(config)
+        .route("/signup", web::post().to(signup))
+}
+
+fn post_form(content_type: &str, body: &'static str) -> TestRequest {
+    TestRequest::post()
+        .uri("/signup")
+        .insert_header(("content-type", content_type.to_string()))
+        .set_payload(body)
+}
+
+#[test]
+fn form() {
+    let config = AwebFormConfig::default();
+    let (status, body) = send(
+        form_app(config),
+        post_form(FORM, "name=doggo&age=3&newsletter=true"),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Signup { name: "doggo", age: 3, newsletter: true, address: None }"###);
+
+    let config = AwebFormConfig::default().query_config(QueryConfig::new().brackets(true));
+    let (status, body) = send(
+        form_app(config),
+        post_form(FORM, "name=doggo&age=3&address[city]=Paris"),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Signup { name: "doggo", age: 3, newsletter: false, address: Some(Address { city: "Paris" }) }"###);
+}
+
+#[test]
+fn form_errors() {
+    let config = AwebFormConfig::default();
+    let (status, body) = send(form_app(config), post_form(FORM, "name=doggo"));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing field `age`");
+
+    let (status, body) = send(form_app(config), post_form(FORM, "name=doggo&age=three"));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for field `age`: expected a positive integer, but found a string: `three`");
+
+    let (status, body) = send(
+        form_app(config),
+        post_form(FORM, "name=doggo&age=3&newsleter=true"),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Unknown field `newsleter`: did you mean `newsletter`? expected one of `name`, `age`, `newsletter`, `address`");
+
+    let (status, body) = send(
+        form_app(config),
+        post_form("application/json", r#"{"name":"doggo","age":3}"#),
+    );
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Content type error.");
+
+    let config = AwebFormConfig::default().limit(8);
+    let (status, body) = send(form_app(config), post_form(FORM, "name=doggo&age=3"));
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_snapshot!(body, @"URL encoded payload is larger (16 bytes) than allowed (limit: 8 bytes).");
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+struct DocumentPath {
+    uid: String,
+    id: u64,
+}
+
+async fn document(item: AwebPath<DocumentPath, PathError>) -> HttpResponse {
+    HttpResponse::Ok().body(format!("{:?}", item.0))
+}
+
+fn path_app() -> App<impl AppFactory> {
+    App::new().route("/indexes/{uid}/documents/{id}", web::get().to(document))
+}
+
+#[test]
+fn path() {
+    let (status, body) = send(
+        path_app(),
+        TestRequest::get().uri("/indexes/movies/documents/12"),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"DocumentPath { uid: "movies", id: 12 }"###);
+
+    let (status, body) = send(
+        path_app(),
+        TestRequest::get().uri("/indexes/my%20movies/documents/12"),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"DocumentPath { uid: "my movies", id: 12 }"###);
+
+    let (status, body) = send(
+        path_app(),
+        TestRequest::get().uri("/indexes/movies/documents/doggo"),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for path parameter `id`: expected a positive integer, but found a string: `doggo`");
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(rename_all = kebab-case)]
+struct Headers {
+    x_api_key: String,
+    #[deserr(default)]
+    x_request_id: Option<u64>,
+    #[deserr(default)]
+    x_forwarded_for: Vec<String>,
+}
+
+async fn headers(item: AwebHeaders<Headers, HeaderError>) -> HttpResponse {
+    HttpResponse::Ok().body(format!("{:?}", item.0))
+}
+
+fn headers_app() -> App<impl AppFactory> {
+    App::new().route("/headers", web::get().to(headers))
+}
+
+fn get_headers(headers_list: &[(&'static str, &'static str)]) -> TestRequest {
+    let mut request = TestRequest::get().uri("/headers");
+    for header in headers_list {
+        request = request.append_header(*header);
+    }
+    request
+}
+
+#[test]
+fn headers_extractor() {
+    let (status, body) = send(
+        headers_app(),
+        get_headers(&[
+            ("X-API-Key", "s3cr3t"),
+            ("x-request-id", "42"),
+            ("X-Forwarded-For", "10.0.0.1"),
+            ("x-forwarded-for", "10.0.0.2"),
+        ]),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Headers { x_api_key: "s3cr3t", x_request_id: Some(42), x_forwarded_for: ["10.0.0.1", "10.0.0.2"] }"###);
+
+    let (status, body) = send(headers_app(), get_headers(&[("x-request-id", "42")]));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing header `x-api-key`");
+
+    let (status, body) = send(
+        headers_app(),
+        get_headers(&[("x-api-key", "s3cr3t"), ("x-request-id", "doggo")]),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for header `x-request-id`: expected a positive integer, but found a string: `doggo`");
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Upload {
+    title: String,
+    #[deserr(default)]
+    public: bool,
+    image: File,
+}
+
+async fn upload(item: AwebMultipart<Upload, MultipartError>) -> HttpResponse {
+    let Upload {
+        title,
+        public,
+        image,
+    } = item.0;
+    HttpResponse::Ok().body(format!(
+        "{title} {public} {:?} {:?} {:?}",
+        image.file_name,
+        image.content_type,
+        String::from_utf8(image.content).unwrap()
+    ))
+}
+
+const BOUNDARY: &str = "X-DESERR-BOUNDARY";
+
+fn multipart_body(parts: &[(&str, Option<&str>, &str)]) -> String {
+    let mut body = String::new();
+    for (name, file_name, content) in parts {
+        body.push_str(&format!("--{BOUNDARY}\r\n"));
+        match file_name {
+            Some(file_name) => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{name}\"; filename=\"{file_name}\"\r\nContent-Type: text/plain\r\n\r\n"
+            )),
+            None => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{name}\"\r\n\r\n"
+            )),
+        }
+        body.push_str(content);
+        body.push_str("\r\n");
+    }
+    body.push_str(&format!("--{BOUNDARY}--\r\n"));
+    body
+}
+
+fn multipart_app(config: AwebMultipartConfig) -> App<impl AppFactory> {
+    App::new()
+        .app_data(config)
+        .route("/upload", web::post().to(upload))
+}
+
+fn post_multipart(content_type: &str, body: String) -> TestRequest {
+    TestRequest::post()
+        .uri("/upload")
+        .insert_header(("content-type", content_type.to_string()))
+        .set_payload(body)
+}
+
+#[test]
+fn multipart() {
+    let config = AwebMultipartConfig::default();
+    let content_type = format!("multipart/form-data; boundary={BOUNDARY}");
+    let body = multipart_body(&[
+        ("title", None, "Doggo"),
+        ("public", None, "true"),
+        ("image", Some("doggo.txt"), "bork bork"),
+    ]);
+    let (status, body) = send(multipart_app(config), post_multipart(&content_type, body));
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo true Some("doggo.txt") Some("text/plain") "bork bork""###);
+}
+
+#[test]
+fn multipart_errors() {
+    let config = AwebMultipartConfig::default();
+    let content_type = format!("multipart/form-data; boundary={BOUNDARY}");
+
+    let body = multipart_body(&[("title", None, "Doggo")]);
+    let (status, body) = send(multipart_app(config), post_multipart(&content_type, body));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing part `image`");
+
+    let body = multipart_body(&[
+        ("title", None, "Doggo"),
+        ("public", None, "yes"),
+        ("image", Some("doggo.txt"), "bork"),
+    ]);
+    let (status, body) = send(multipart_app(config), post_multipart(&content_type, body));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for part `public`: expected a boolean, but found a string: `yes`");
+
+    let body = multipart_body(&[("title", None, "Doggo")]);
+    let (status, _) = send(multipart_app(config), post_multipart("text/plain", body));
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let body = multipart_body(&[
+        ("title", None, "Doggo"),
+        ("image", Some("doggo.txt"), "bork"),
+    ]);
+    let (status, _) = send(
+        multipart_app(config.limit(16)),
+        post_multipart(&content_type, body),
+    );
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Doggo {
+    name: String,
+    age: u8,
+}
+
+async fn doggo(item: AwebJson<Doggo, JsonError>) -> HttpResponse {
+    HttpResponse::Ok().body(format!("{:?}", item.0))
+}
+
+fn json_app(config: AwebJsonConfig) -> App<impl AppFactory> {
+    App::new()
+        .app_data(config)
+        .route("/doggo", web::post().to(doggo))
+}
+
+fn post_json(content_type: Option<&str>, body: &'static str) -> TestRequest {
+    let request = TestRequest::post().uri("/doggo").set_payload(body);
+    match content_type {
+        Some(content_type) => request.insert_header(("content-type", content_type.to_string())),
+        None => request,
+    }
+}
+
+#[test]
+fn json() {
+    let doggo = r#"{"name":"doggo","age":3}"#;
+    let (status, body) = send(
+        json_app(AwebJsonConfig::default()),
+        post_json(Some("application/json"), doggo),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo { name: "doggo", age: 3 }"###);
+
+    let config = AwebJsonConfig::default().content_type(|mime| mime == "text/plain");
+    let (status, body) = send(json_app(config), post_json(Some("text/plain"), doggo));
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo { name: "doggo", age: 3 }"###);
+
+    let config = AwebJsonConfig::default().content_type_required(false);
+    let (status, body) = send(json_app(config), post_json(None, doggo));
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo { name: "doggo", age: 3 }"###);
+}
+
+#[test]
+fn json_errors() {
+    let doggo = r#"{"name":"doggo","age":3}"#;
+    let config = AwebJsonConfig::default();
+    let (status, body) = send(
+        json_app(config.clone()),
+        post_json(Some("application/json"), r#"{"name":"doggo"}"#),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing field `age`");
+
+    let (status, body) = send(json_app(config.clone()), post_json(None, doggo));
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Missing `Content-Type` header: expected `application/json`");
+
+    let (status, body) = send(
+        json_app(config.clone()),
+        post_json(Some("text/plain"), doggo),
+    );
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Invalid `Content-Type` header `text/plain`: expected `application/json`");
+
+    let (status, body) = send(
+        json_app(config.limit(8)),
+        post_json(Some("application/json"), doggo),
+    );
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_snapshot!(body, @"The payload is larger than allowed (limit: 8 bytes)");
+}
+
+async fn doggos(item: AwebNdJson<Doggo, JsonError>) -> HttpResponse {
+    let results = item
+        .into_inner()
+        .stop_on_error(true)
+        .map(|result| match result {
+            Ok(doggo) => format!("{doggo:?}"),
+            Err(error) => error.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .await;
+    HttpResponse::Ok().body(results.join("\n"))
+}
+
+fn ndjson_app(config: AwebNdJsonConfig) -> App<impl AppFactory> {
+    App::new()
+        .app_data(config)
+        .route("/doggos", web::post().to(doggos))
+}
+
+fn post_doggos(content_type: Option<&str>, body: &'static str) -> TestRequest {
+    let request = TestRequest::post().uri("/doggos").set_payload(body);
+    match content_type {
+        Some(content_type) => request.insert_header(("content-type", content_type.to_string())),
+        None => request,
+    }
+}
+
+#[test]
+fn ndjson() {
+    let body = "{\"name\":\"doggo\",\"age\":3}\r\n{\"name\":\"doggo\",\"age\":3\n{\"name\":\"doggo\",\"age\":4}\n";
+    let config = AwebNdJsonConfig::default();
+    let (status, body) = send(
+        ndjson_app(config),
+        post_doggos(Some("application/x-ndjson"), body),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"
+    Doggo { name: "doggo", age: 3 }
+    Invalid JSON at line 2, column 23: EOF while parsing an object
+    "###);
+}
+
+#[test]
+fn ndjson_errors() {
+    let payload = "{\"name\":\"doggo\",\"age\":3}\n{\"name\":\"kefir\",\"age\":12}\n";
+    let config = AwebNdJsonConfig::default().max_line_length(24);
+    let (status, body) = send(
+        ndjson_app(config),
+        post_doggos(Some("application/x-ndjson"), payload),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"
+    Doggo { name: "doggo", age: 3 }
+    Invalid value at `[1]`: The line is longer than allowed (limit: 24 bytes)
+    "###);
+
+    let (status, body) = send(
+        ndjson_app(AwebNdJsonConfig::default()),
+        post_doggos(None, payload),
+    );
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Missing `Content-Type` header: expected `application/x-ndjson`");
+
+    let config = AwebNdJsonConfig::default();
+    let (status, body) = send(
+        ndjson_app(config),
+        post_doggos(Some("application/json"), payload),
+    );
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Invalid `Content-Type` header `application/json`: expected `application/x-ndjson`");
+
+    let config = AwebNdJsonConfig::default().content_type(|mime| mime == "text/plain");
+    let (status, _) = send(ndjson_app(config), post_doggos(Some("text/plain"), payload));
+    assert_eq!(status, StatusCode::OK);
+}
+
+async fn doggo_array(item: AwebJsonArray<Doggo, JsonError>) -> HttpResponse {
+    let results = item
+        .into_inner()
+        .stop_on_error(true)
+        .map(|result| match result {
+            Ok(doggo) => format!("{doggo:?}"),
+            Err(error) => error.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .await;
+    HttpResponse::Ok().body(results.join("\n"))
+}
+
+fn json_array_app(config: AwebJsonArrayConfig) -> App<impl AppFactory> {
+    App::new()
+        .app_data(config)
+        .route("/doggos", web::post().to(doggo_array))
+}
+
+#[test]
+fn json_array() {
+    let body =
+        "[{\"name\":\"doggo\",\"age\":3}, {\"name\":\"doggo\"}, {\"name\":\"doggo\",\"age\":4}]";
+    let config = AwebJsonArrayConfig::default();
+    let (status, body) = send(
+        json_array_app(config),
+        post_doggos(Some("application/json"), body),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"
+    Doggo { name: "doggo", age: 3 }
+    Missing field `age` inside `[1]`
+    "###);
+}
+
+#[test]
+fn json_array_errors() {
+    let payload = "[{\"name\":\"doggo\",\"age\":3}, {\"name\":\"kefir\",\"age\":12}]";
+    let config = AwebJsonArrayConfig::default().max_element_length(24);
+    let (status, body) = send(
+        json_array_app(config),
+        post_doggos(Some("application/json"), payload),
+    );
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"
+    Doggo { name: "doggo", age: 3 }
+    Invalid value at `[1]`: The element is longer than allowed (limit: 24 bytes)
+    "###);
+
+    let config = AwebJsonArrayConfig::default();
+    let (status, body) = send(json_array_app(config), post_doggos(None, payload));
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Missing `Content-Type` header: expected `application/json`");
+
+    let config = AwebJsonArrayConfig::default();
+    let (status, body) = send(
+        json_array_app(config),
+        post_doggos(Some("application/x-ndjson"), payload),
+    );
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Invalid `Content-Type` header `application/x-ndjson`: expected `application/json`");
+
+    let config = AwebJsonArrayConfig::default().content_type(|mime| mime == "text/plain");
+    let (status, _) = send(
+        json_array_app(config),
+        post_doggos(Some("text/plain"), payload),
+    );
+    assert_eq!(status, StatusCode::OK);
+}
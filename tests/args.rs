@@ -0,0 +1,116 @@
+use deserr::{errors::ArgsError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields, rename_all = kebab-case)]
+struct Args {
+    db: Db,
+    #[deserr(default)]
+    verbose: bool,
+    #[deserr(default)]
+    port: Vec<u16>,
+    #[deserr(default)]
+    dry_run: bool,
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Db {
+    host: String,
+    #[deserr(range(min = 1))]
+    port: u16,
+}
+
+fn deserialize(args: &[&str]) -> Result<Args, ArgsError> {
+    deserr::args::deserialize_args::<Args>(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn options() {
+    let data = deserialize(&[
+        "--db.host",
+        "localhost",
+        "--db.port=5432",
+        "--verbose",
+        "--port",
+        "7700",
+        "--port=7701",
+        "--dry-run",
+        "false",
+    ])
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Args {
+        db: Db {
+            host: "localhost",
+            port: 5432,
+        },
+        verbose: true,
+        port: [
+            7700,
+            7701,
+        ],
+        dry_run: false,
+    }
+    "###);
+
+    let data = deserialize(&["--port", "7700", "--db.host=a=b", "--db.port", "1"]).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Args {
+        db: Db {
+            host: "a=b",
+            port: 1,
+        },
+        verbose: false,
+        port: [
+            7700,
+        ],
+        dry_run: false,
+    }
+    "###);
+}
+
+#[test]
+fn error_msg() {
+    let err = deserialize(&["--db.host", "localhost"]).unwrap_err();
+    assert_snapshot!(err, @"Missing option `--db.port`");
+
+    let err = deserialize(&["--db.host", "localhost", "--db.port", "five"]).unwrap_err();
+    assert_snapshot!(err, @"Invalid value type for option `--db.port`: expected a positive integer, but found `five`");
+
+    let err = deserialize(&["--db.host", "localhost", "--db.port"]).unwrap_err();
+    assert_snapshot!(err, @"Invalid value type for option `--db.port`: expected a positive integer, but found `true`");
+
+    let err = deserialize(&["--db.host", "localhost", "--db.port", "0"]).unwrap_err();
+    assert_snapshot!(err, @"Invalid value for option `--db.port`: expected a value greater than or equal to 1, but found 0");
+
+    let err = deserialize(&["--db.host", "a", "--db.port", "1", "--db.port", "2"]).unwrap_err();
+    assert_snapshot!(err, @"Invalid value type for option `--db.port`: expected a positive integer, but found multiple values");
+
+    let err = deserialize(&[
+        "--db.host",
+        "a",
+        "--db.port",
+        "1",
+        "--port",
+        "1",
+        "--port",
+        "x",
+    ])
+    .unwrap_err();
+    assert_snapshot!(err, @"Invalid value type for option `--port[1]`: expected a positive integer, but found `x`");
+
+    let err = deserialize(&["--db.hots", "localhost", "--db.port", "1"]).unwrap_err();
+    assert_snapshot!(err, @"Unknown option `--db.hots`: did you mean `--db.host`? expected one of `--db.host`, `--db.port`");
+
+    let err = deserialize(&["--db.host", "a", "--db.port", "1", "--dryrun"]).unwrap_err();
+    assert_snapshot!(err, @"Unknown option `--dryrun`: did you mean `--dry-run`? expected one of `--db`, `--verbose`, `--port`, `--dry-run`");
+
+    let err = deserialize(&["localhost"]).unwrap_err();
+    assert_snapshot!(err, @"Invalid argument `localhost`: expected an option starting with `--`");
+
+    let err = deserialize(&["--db..host", "localhost"]).unwrap_err();
+    assert_snapshot!(err, @"Invalid argument `--db..host`: expected the name of an option after `--`");
+}
@@ -0,0 +1,65 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn conflicts_with() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(
+        rename_all = camelCase,
+        conflicts_with(page, offset, limit),
+        conflicts_with(hits_per_page, offset, limit)
+    )]
+    struct Search {
+        #[deserr(default)]
+        page: Option<usize>,
+        #[deserr(default)]
+        hits_per_page: Option<usize>,
+        #[deserr(default)]
+        offset: Option<usize>,
+        #[deserr(default)]
+        limit: Option<usize>,
+    }
+
+    let data =
+        deserialize::<Search, _, JsonError>(json!({ "page": 2, "hitsPerPage": 20 })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        page: Some(
+            2,
+        ),
+        hits_per_page: Some(
+            20,
+        ),
+        offset: None,
+        limit: None,
+    }
+    "###);
+
+    let data = deserialize::<Search, _, JsonError>(json!({ "offset": 10, "limit": 20 })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        page: None,
+        hits_per_page: None,
+        offset: Some(
+            10,
+        ),
+        limit: Some(
+            20,
+        ),
+    }
+    "###);
+
+    let data = deserialize::<Search, _, JsonError>(json!({ "page": 2, "offset": 10 })).unwrap_err();
+
+    assert_snapshot!(data, @"Conflicting field `offset`: it cannot be specified together with `page`");
+
+    let data =
+        deserialize::<Search, _, JsonError>(json!({ "hitsPerPage": 2, "offset": 10, "limit": 20 }))
+            .unwrap_err();
+
+    assert_snapshot!(data, @"Conflicting fields `offset` and `limit`: they cannot be specified together with `hitsPerPage`");
+}
@@ -0,0 +1,39 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn exactly_one_of() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(exactly_one_of(query, vector, filter))]
+    struct Search {
+        #[deserr(default)]
+        query: Option<String>,
+        #[deserr(default)]
+        vector: Option<Vec<f32>>,
+        #[deserr(default)]
+        filter: Option<String>,
+    }
+
+    let data = deserialize::<Search, _, JsonError>(json!({ "query": "doggo" })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        query: Some(
+            "doggo",
+        ),
+        vector: None,
+        filter: None,
+    }
+    "###);
+
+    let data = deserialize::<Search, _, JsonError>(json!({})).unwrap_err();
+
+    assert_snapshot!(data, @"Missing field: expected exactly one of `query`, `vector` or `filter`");
+
+    let data = deserialize::<Search, _, JsonError>(json!({ "filter": "id = 1", "query": "doggo" }))
+        .unwrap_err();
+
+    assert_snapshot!(data, @"Conflicting fields `query` and `filter`: expected exactly one of `query`, `vector` or `filter`");
+}
@@ -0,0 +1,111 @@
+use std::collections::{BTreeMap, HashMap};
+
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn extra() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Webhook {
+        event: String,
+        #[deserr(extra)]
+        metadata: BTreeMap<String, serde_json::Value>,
+    }
+
+    let data = deserialize::<Webhook, _, JsonError>(json!({ "event": "created" })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Webhook {
+        event: "created",
+        metadata: {},
+    }
+    "###);
+
+    let data = deserialize::<Webhook, _, JsonError>(
+        json!({ "event": "created", "retries": 2, "source": { "ip": "127.0.0.1" } }),
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Webhook {
+        event: "created",
+        metadata: {
+            "retries": Number(2),
+            "source": Object {
+                "ip": String("127.0.0.1"),
+            },
+        },
+    }
+    "###);
+
+    // the extra field is not a key of its own
+    let data =
+        deserialize::<Webhook, _, JsonError>(json!({ "event": "created", "metadata": "hello" }))
+            .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Webhook {
+        event: "created",
+        metadata: {
+            "metadata": String("hello"),
+        },
+    }
+    "###);
+}
+
+#[test]
+fn extra_with_typed_values() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Counters {
+        total: u32,
+        #[deserr(extra)]
+        others: HashMap<String, u32>,
+    }
+
+    let data = deserialize::<Counters, _, JsonError>(json!({ "total": 3, "doggo": 2 })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Counters {
+        total: 3,
+        others: {
+            "doggo": 2,
+        },
+    }
+    "###);
+
+    let data =
+        deserialize::<Counters, _, JsonError>(json!({ "total": 3, "doggo": "two" })).unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value type at `.doggo`: expected a positive integer, but found a string: `\"two\"`");
+}
+
+#[test]
+fn extra_in_tagged_enum() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(tag = "type")]
+    enum Event {
+        Created {
+            id: u32,
+            #[deserr(extra)]
+            metadata: BTreeMap<String, serde_json::Value>,
+        },
+    }
+
+    let data =
+        deserialize::<Event, _, JsonError>(json!({ "type": "Created", "id": 1, "user": "kero" }))
+            .unwrap();
+
+    // the tag is not captured
+    assert_debug_snapshot!(data, @r###"
+    Created {
+        id: 1,
+        metadata: {
+            "user": String("kero"),
+        },
+    }
+    "###);
+}
@@ -0,0 +1,38 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn length() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(length(max = 5))]
+        name: String,
+        #[deserr(length(min = 1, max = 2))]
+        tags: Vec<String>,
+    }
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "name": "kéfir", "tags": ["dog"] })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        name: "kéfir",
+        tags: [
+            "dog",
+        ],
+    }
+    "###);
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "name": "intel the wise", "tags": ["dog"] }))
+            .unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value length at `.name`: expected a length less than or equal to 5, but found 14");
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "name": "echo", "tags": [] })).unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value length at `.tags`: expected a length between 1 and 2, but found 0");
+}
@@ -1,9 +1,19 @@
+mod conflicts_with;
 mod deny_unknown_fields;
 mod error;
+mod exactly_one_of;
+mod extra;
 mod from;
+mod length;
 mod map;
 mod missing_field_error;
+mod non_empty;
+mod other;
+#[cfg(feature = "regex")]
+mod pattern;
+mod range;
 mod rename_all;
+mod requires;
 mod skip;
 mod tag;
 mod try_from;
@@ -0,0 +1,33 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn non_empty() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(non_empty)]
+        doggo: String,
+        #[deserr(default, non_empty)]
+        friends: Option<Vec<String>>,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "doggo": "bork" })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: "bork",
+        friends: None,
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "doggo": "" })).unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.doggo`: expected a non-empty value");
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "doggo": "bork", "friends": [] })).unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.friends`: expected a non-empty value");
+}
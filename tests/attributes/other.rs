@@ -0,0 +1,96 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn other_unit_variant() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = lowercase)]
+    enum Status {
+        Enqueued,
+        Succeeded,
+        #[deserr(other)]
+        Unknown,
+    }
+
+    let data = deserialize::<Status, _, JsonError>(json!("succeeded")).unwrap();
+
+    assert_debug_snapshot!(data, @"Succeeded");
+
+    let data = deserialize::<Status, _, JsonError>(json!("canceled")).unwrap();
+
+    assert_debug_snapshot!(data, @"Unknown");
+
+    // the other variant doesn't match its own name
+    let data = deserialize::<Status, _, JsonError>(json!("unknown")).unwrap();
+
+    assert_debug_snapshot!(data, @"Unknown");
+
+    // but the value must still be a string
+    let data = deserialize::<Status, _, JsonError>(json!(42)).unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value type: expected a string, but found a positive integer: `42`");
+}
+
+#[test]
+fn other_variant_with_value() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = lowercase)]
+    enum Status {
+        Enqueued,
+        Succeeded,
+        #[deserr(other)]
+        Unknown(String),
+    }
+
+    let data = deserialize::<Status, _, JsonError>(json!("enqueued")).unwrap();
+
+    assert_debug_snapshot!(data, @"Enqueued");
+
+    let data = deserialize::<Status, _, JsonError>(json!("canceled")).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Unknown(
+        "canceled",
+    )
+    "###);
+}
+
+#[test]
+fn other_tagged_variant() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(tag = "type")]
+    enum Event {
+        Created {
+            id: u32,
+        },
+        #[deserr(other)]
+        Unknown(String),
+    }
+
+    let data = deserialize::<Event, _, JsonError>(json!({ "type": "Created", "id": 1 })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Created {
+        id: 1,
+    }
+    "###);
+
+    // the rest of the map is ignored
+    let data =
+        deserialize::<Event, _, JsonError>(json!({ "type": "Deleted", "id": "doggo" })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Unknown(
+        "Deleted",
+    )
+    "###);
+
+    // the tag is still required
+    let data = deserialize::<Event, _, JsonError>(json!({ "id": 1 })).unwrap_err();
+
+    assert_snapshot!(data, @"Missing field `type`");
+}
@@ -0,0 +1,25 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn pattern() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(pattern = "^[a-zA-Z0-9_-]+$")]
+        uid: String,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "uid": "movies_2" })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        uid: "movies_2",
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "uid": "movies 2" })).unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.uid`: expected a value matching the pattern `^[a-zA-Z0-9_-]+$`, but found `movies 2`");
+}
@@ -0,0 +1,64 @@
+use deserr::validators::check_range;
+use deserr::{deserialize, errors::JsonError, Deserr, ValuePointerRef};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn range() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(range(min = 1, max = 1000))]
+        limit: usize,
+        #[deserr(range(min = -1.5))]
+        score: f64,
+        #[deserr(default, range(max = 10))]
+        page: Option<u8>,
+    }
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "limit": 1000, "score": -1.5, "page": 10 }))
+            .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        limit: 1000,
+        score: -1.5,
+        page: Some(
+            10,
+        ),
+    }
+    "###);
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "limit": 5000, "score": 0 })).unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.limit`: expected a value between 1 and 1000, but found 5000");
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "limit": 1, "score": -2 })).unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.score`: expected a value greater than or equal to -1.5, but found -2");
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "limit": 1, "score": 0, "page": 11 }))
+        .unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.page`: expected a value less than or equal to 10, but found 11");
+
+    // NaN is never in a range
+    let location = ValuePointerRef::Origin.push_key("score");
+    let data = check_range::<f64, JsonError>(&f64::NAN, Some(-1.5), None, location).unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.score`: expected a value greater than or equal to -1.5, but found NaN");
+
+    // the range is not checked when there is no value
+    let data = deserialize::<Struct, _, JsonError>(json!({ "limit": 1, "score": 0, "page": null }))
+        .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        limit: 1,
+        score: 0.0,
+        page: None,
+    }
+    "###);
+}
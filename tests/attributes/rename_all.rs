@@ -96,3 +96,50 @@ fn rename_all_lowercase() {
 
     assert_snapshot!(data, @"Missing field `BORK`");
 }
+
+#[test]
+fn rename_all_kebab_case() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = kebab-case)]
+    struct Struct {
+        word: String,
+        multiple_words: String,
+        kind: Kind,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = kebab-case)]
+    enum Kind {
+        Word,
+        MultipleWords,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "word": "doggo", "multiple-words": "good doggo", "kind": "multiple-words" }),
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        word: "doggo",
+        multiple_words: "good doggo",
+        kind: MultipleWords,
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "word": "doggo", "multiple_words": "good doggo", "kind": "word" }),
+    )
+    .unwrap_err();
+
+    assert_snapshot!(data, @"Missing field `multiple-words`");
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "word": "doggo", "multiple-words": "good doggo", "kind": "MultipleWords" }),
+    )
+    .unwrap_err();
+
+    assert_snapshot!(data, @"Unknown value `MultipleWords` at `.kind`: did you mean `multiple-words`? expected one of `word`, `multiple-words`");
+}
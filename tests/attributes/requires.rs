@@ -0,0 +1,91 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn requires() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = camelCase, requires(page, hits_per_page))]
+    struct Search {
+        #[deserr(default)]
+        page: Option<usize>,
+        #[deserr(default)]
+        hits_per_page: Option<usize>,
+    }
+
+    let data =
+        deserialize::<Search, _, JsonError>(json!({ "page": 2, "hitsPerPage": 20 })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        page: Some(
+            2,
+        ),
+        hits_per_page: Some(
+            20,
+        ),
+    }
+    "###);
+
+    // the constraint only goes in one direction
+    let data = deserialize::<Search, _, JsonError>(json!({ "hitsPerPage": 20 })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        page: None,
+        hits_per_page: Some(
+            20,
+        ),
+    }
+    "###);
+
+    let data = deserialize::<Search, _, JsonError>(json!({ "page": 2 })).unwrap_err();
+
+    assert_snapshot!(data, @"Missing field `hitsPerPage`: it is required when `page` is specified");
+
+    // a field explicitly set to `null` is still specified
+    let data =
+        deserialize::<Search, _, JsonError>(json!({ "page": 2, "hitsPerPage": null })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        page: Some(
+            2,
+        ),
+        hits_per_page: None,
+    }
+    "###);
+}
+
+#[test]
+fn requires_multiple_fields() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(requires(facets, limit, offset))]
+    struct Search {
+        #[deserr(default)]
+        facets: Option<Vec<String>>,
+        #[deserr(default)]
+        limit: Option<usize>,
+        #[deserr(default)]
+        offset: Option<usize>,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Request {
+        search: Search,
+    }
+
+    let data =
+        deserialize::<Request, _, JsonError>(json!({ "search": { "facets": [] } })).unwrap_err();
+
+    assert_snapshot!(data, @"Missing fields `limit` and `offset` inside `.search`: they are required when `facets` is specified");
+
+    let data =
+        deserialize::<Request, _, JsonError>(json!({ "search": { "facets": [], "offset": 0 } }))
+            .unwrap_err();
+
+    assert_snapshot!(data, @"Missing field `limit` inside `.search`: it is required when `facets` is specified");
+}
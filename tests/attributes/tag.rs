@@ -113,7 +113,7 @@ fn tagged_enum_plus_rename() {
 
     assert_debug_snapshot!(data, @r###"
     JsonError(
-        "Invalid value at `.either`: Incorrect tag value",
+        "Unknown value `Left` at `.either.type`: did you mean `left`? expected one of `left`, `RIGHT`",
     )
     "###);
 
@@ -124,7 +124,7 @@ fn tagged_enum_plus_rename() {
 
     assert_debug_snapshot!(data, @r###"
     JsonError(
-        "Invalid value at `.either`: Incorrect tag value",
+        "Unknown value `Right` at `.either.type`: expected one of `left`, `RIGHT`",
     )
     "###);
 }
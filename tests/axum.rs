@@ -0,0 +1,735 @@
+use std::convert::Infallible;
+
+use axum::body::{to_bytes, Body};
+use axum::extract::DefaultBodyLimit;
+use axum::http::{Request, StatusCode};
+use axum::routing::{get, post};
+use axum::{Extension, Router};
+use deserr::axum::{
+    AxumForm, AxumHeaders, AxumJson, AxumJsonArray, AxumJsonArrayConfig, AxumJsonConfig,
+    AxumMultipart, AxumNdJson, AxumNdJsonConfig, AxumPath, AxumQuery,
+};
+use deserr::errors::{
+    FormError, HeaderError, JsonError, MultipartError, PathError, QueryParamError,
+};
+use deserr::multipart::File;
+use deserr::query_string::QueryConfig;
+use deserr::{take_cf_content, DeserializeError, Deserr, ErrorKind, ValuePointerRef};
+use futures::StreamExt;
+use insta::assert_snapshot;
+use tower::ServiceExt;
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Query {
+    name: String,
+
+    #[deserr(default)]
+    number: Option<i32>,
+
+    #[deserr(default = Range { min: 2, max: 4 })]
+    range: Range,
+
+    #[deserr(rename = "return")]
+    returns: Return,
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields, validate = validate_range -> __Deserr_E)]
+struct Range {
+    min: u8,
+    max: u8,
+}
+
+fn validate_range<E: DeserializeError>(
+    range: Range,
+    location: ValuePointerRef,
+) -> Result<Range, E> {
+    if range.min > range.max {
+        Err(take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!(
+                    "`max` (`{}`) should be greater than `min` (`{}`)",
+                    range.max, range.min
+                ),
+            },
+            location,
+        )))
+    } else {
+        Ok(range)
+    }
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(rename_all = camelCase)]
+enum Return {
+    Name,
+    Number,
+}
+
+async fn deserr(item: AxumQuery<Query, QueryParamError>) -> String {
+    format!("{:?}", item.0)
+}
+
+async fn send(app: Router, request: Request<Body>) -> (StatusCode, String) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn get_query(query: &str) -> Request<Body> {
+    Request::get(format!("/deserr?{query}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn app() -> Router {
+    Router::new()
+        .route("/deserr", get(deserr))
+        .layer(Extension(QueryConfig::new().brackets(true)))
+}
+
+#[tokio::test]
+async fn query() {
+    let (status, body) = send(app(), get_query("name=doggo&number=-12&return=number")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Query { name: "doggo", number: Some(-12), range: Range { min: 2, max: 4 }, returns: Number }"###);
+
+    let (status, body) = send(
+        app(),
+        get_query("name=doggo&range[min]=1&range[max]=3&return=name"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Query { name: "doggo", number: None, range: Range { min: 1, max: 3 }, returns: Name }"###);
+}
+
+#[tokio::test]
+async fn query_errors() {
+    let (status, body) = send(app(), get_query("name=doggo")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing parameter `return`");
+
+    let (status, body) = send(app(), get_query("name=doggo&number=twelve&return=name")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for parameter `number`: expected an integer, but found a string: `twelve`");
+
+    let (status, body) = send(app(), get_query("name=doggo&return=nume")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Unknown value `nume` for parameter `return`: did you mean `name`? expected one of `name`, `number`");
+
+    let (status, body) = send(
+        app(),
+        get_query("name=doggo&range[min]=4&range[max]=2&return=name"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value in parameter `range`: `max` (`2`) should be greater than `min` (`4`)");
+
+    let (status, body) = send(
+        app(),
+        get_query("name=doggo&range[min]=1&range[max]=300&return=name"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value in parameter `range.max`: value: `300` is too large to be deserialized, maximum value authorized is `255`");
+
+    let (status, body) = send(app(), get_query("name=doggo&return=name&doggo=bork")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Unknown parameter `doggo`: expected one of `name`, `number`, `range`, `return`");
+}
+
+#[tokio::test]
+async fn query_without_config() {
+    let app = Router::new().route("/deserr", get(deserr));
+
+    // without the bracket notation, `range[min]` is an unknown parameter
+    let (status, body) = send(app, get_query("name=doggo&range[min]=1&return=name")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Unknown parameter `range[min]`: expected one of `name`, `number`, `range`, `return`");
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Signup {
+    name: String,
+    age: u8,
+    #[deserr(default)]
+    newsletter: bool,
+}
+
+async fn signup(item: AxumForm<Signup, FormError>) -> String {
+    format!("{:?}", item.0)
+}
+
+fn post_form(content_type: &str, body: &'static str) -> Request<Body> {
+    Request::post("/signup")
+        .header("content-type", content_type)
+        .body(Body::from(body))
+        .unwrap()
+}
+
+fn form_app() -> Router {
+    Router::new().route("/signup", post(signup))
+}
+
+const FORM: &str = "application/x-www-form-urlencoded";
+
+#[tokio::test]
+async fn form() {
+    let (status, body) = send(
+        form_app(),
+        post_form(FORM, "name=doggo&age=3&newsletter=true"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Signup { name: "doggo", age: 3, newsletter: true }"###);
+
+    let (status, body) = send(
+        form_app(),
+        post_form(
+            "application/x-www-form-urlencoded; charset=utf-8",
+            "name=doggo&age=3",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Signup { name: "doggo", age: 3, newsletter: false }"###);
+}
+
+#[tokio::test]
+async fn form_errors() {
+    let (status, body) = send(form_app(), post_form(FORM, "name=doggo")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing field `age`");
+
+    let (status, body) = send(form_app(), post_form(FORM, "name=doggo&age=three")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for field `age`: expected a positive integer, but found a string: `three`");
+
+    let (status, body) = send(
+        form_app(),
+        post_form(FORM, "name=doggo&age=3&newsleter=true"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Unknown field `newsleter`: did you mean `newsletter`? expected one of `name`, `age`, `newsletter`");
+
+    let (status, body) = send(
+        form_app(),
+        post_form("application/json", r#"{"name":"doggo","age":3}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Form requests must have `Content-Type: application/x-www-form-urlencoded`");
+
+    let app = form_app().layer(DefaultBodyLimit::max(8));
+    let (status, body) = send(app, post_form(FORM, "name=doggo&age=3")).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_snapshot!(body, @"Failed to buffer the request body: length limit exceeded");
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+struct DocumentPath {
+    uid: String,
+    id: u64,
+}
+
+async fn document(item: AxumPath<DocumentPath, PathError>) -> String {
+    format!("{:?}", item.0)
+}
+
+fn path_app() -> Router {
+    Router::new().route("/indexes/{uid}/documents/{id}", get(document))
+}
+
+fn get_path(uri: &str) -> Request<Body> {
+    Request::get(uri).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn path() {
+    let (status, body) = send(path_app(), get_path("/indexes/movies/documents/12")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"DocumentPath { uid: "movies", id: 12 }"###);
+
+    let (status, body) = send(path_app(), get_path("/indexes/my%20movies/documents/12")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"DocumentPath { uid: "my movies", id: 12 }"###);
+
+    let (status, body) = send(path_app(), get_path("/indexes/movies/documents/doggo")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for path parameter `id`: expected a positive integer, but found a string: `doggo`");
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(rename_all = kebab-case)]
+struct Headers {
+    x_api_key: String,
+    #[deserr(default)]
+    x_request_id: Option<u64>,
+    #[deserr(default)]
+    x_forwarded_for: Vec<String>,
+}
+
+async fn headers(item: AxumHeaders<Headers, HeaderError>) -> String {
+    format!("{:?}", item.0)
+}
+
+fn headers_app() -> Router {
+    Router::new().route("/headers", get(headers))
+}
+
+fn get_headers(headers_list: &[(&str, &str)]) -> Request<Body> {
+    let mut request = Request::get("/headers");
+    for (name, value) in headers_list {
+        request = request.header(*name, *value);
+    }
+    request.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn headers_extractor() {
+    let (status, body) = send(
+        headers_app(),
+        get_headers(&[
+            ("X-API-Key", "s3cr3t"),
+            ("x-request-id", "42"),
+            ("X-Forwarded-For", "10.0.0.1"),
+            ("x-forwarded-for", "10.0.0.2"),
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Headers { x_api_key: "s3cr3t", x_request_id: Some(42), x_forwarded_for: ["10.0.0.1", "10.0.0.2"] }"###);
+
+    let (status, body) = send(headers_app(), get_headers(&[("x-request-id", "42")])).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing header `x-api-key`");
+
+    let (status, body) = send(
+        headers_app(),
+        get_headers(&[("x-api-key", "s3cr3t"), ("x-request-id", "doggo")]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for header `x-request-id`: expected a positive integer, but found a string: `doggo`");
+
+    let (status, body) = send(
+        headers_app(),
+        get_headers(&[("x-api-key", "a"), ("x-api-key", "b")]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for header `x-api-key`: expected a string, but found multiple values");
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Upload {
+    title: String,
+    #[deserr(default)]
+    public: bool,
+    image: File,
+    #[deserr(default)]
+    tags: Vec<String>,
+}
+
+async fn upload(item: AxumMultipart<Upload, MultipartError>) -> String {
+    let Upload {
+        title,
+        public,
+        image,
+        tags,
+    } = item.0;
+    format!(
+        "{title} {public} {tags:?} {:?} {:?} {:?}",
+        image.file_name,
+        image.content_type,
+        String::from_utf8(image.content).unwrap()
+    )
+}
+
+const BOUNDARY: &str = "X-DESERR-BOUNDARY";
+
+fn multipart_body(parts: &[(&str, Option<&str>, &str)]) -> String {
+    let mut body = String::new();
+    for (name, file_name, content) in parts {
+        body.push_str(&format!("--{BOUNDARY}\r\n"));
+        match file_name {
+            Some(file_name) => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{name}\"; filename=\"{file_name}\"\r\nContent-Type: text/plain\r\n\r\n"
+            )),
+            None => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{name}\"\r\n\r\n"
+            )),
+        }
+        body.push_str(content);
+        body.push_str("\r\n");
+    }
+    body.push_str(&format!("--{BOUNDARY}--\r\n"));
+    body
+}
+
+fn post_multipart(content_type: &str, body: String) -> Request<Body> {
+    Request::post("/upload")
+        .header("content-type", content_type)
+        .body(Body::from(body))
+        .unwrap()
+}
+
+fn multipart_app() -> Router {
+    Router::new().route("/upload", post(upload))
+}
+
+#[tokio::test]
+async fn multipart() {
+    let content_type = format!("multipart/form-data; boundary={BOUNDARY}");
+    let body = multipart_body(&[
+        ("title", None, "Doggo"),
+        ("public", None, "true"),
+        ("image", Some("doggo.txt"), "bork bork"),
+        ("tags", None, "dog"),
+        ("tags", None, "good"),
+    ]);
+    let (status, body) = send(multipart_app(), post_multipart(&content_type, body)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo true ["dog", "good"] Some("doggo.txt") Some("text/plain") "bork bork""###);
+}
+
+#[tokio::test]
+async fn multipart_errors() {
+    let content_type = format!("multipart/form-data; boundary={BOUNDARY}");
+
+    let body = multipart_body(&[("title", None, "Doggo")]);
+    let (status, body) = send(multipart_app(), post_multipart(&content_type, body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing part `image`");
+
+    let body = multipart_body(&[
+        ("title", None, "Doggo"),
+        ("public", None, "yes"),
+        ("image", Some("doggo.txt"), "bork"),
+    ]);
+    let (status, body) = send(multipart_app(), post_multipart(&content_type, body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for part `public`: expected a boolean, but found a string: `yes`");
+
+    let body = multipart_body(&[("title", None, "Doggo"), ("image", None, "bork")]);
+    let (status, body) = send(multipart_app(), post_multipart(&content_type, body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for part `image`: expected multiple parts, but found a string: `bork`");
+
+    let body = multipart_body(&[
+        ("title", None, "Doggo"),
+        ("image", Some("doggo.txt"), "bork"),
+        ("imgae", None, "bork"),
+    ]);
+    let (status, body) = send(multipart_app(), post_multipart(&content_type, body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Unknown part `imgae`: did you mean `image`? expected one of `title`, `public`, `image`, `tags`");
+
+    let body = multipart_body(&[("title", None, "Doggo")]);
+    let (status, body) = send(multipart_app(), post_multipart("text/plain", body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid `multipart/form-data` request: Content-Type is not multipart/form-data");
+
+    let body = multipart_body(&[
+        ("title", None, "Doggo"),
+        ("image", Some("doggo.txt"), "bork"),
+    ]);
+    let app = multipart_app().layer(DefaultBodyLimit::max(16));
+    let (status, body) = send(app, post_multipart(&content_type, body)).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_snapshot!(body, @"Failed to read the multipart request: failed to read stream");
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Doggo {
+    name: String,
+    age: u8,
+}
+
+async fn doggo(item: AxumJson<Doggo, JsonError>) -> String {
+    format!("{:?}", item.0)
+}
+
+fn post_json(content_type: Option<&str>, body: &'static str) -> Request<Body> {
+    let mut request = Request::post("/doggo");
+    if let Some(content_type) = content_type {
+        request = request.header("content-type", content_type);
+    }
+    request.body(Body::from(body)).unwrap()
+}
+
+fn json_app(config: AxumJsonConfig) -> Router {
+    Router::new()
+        .route("/doggo", post(doggo))
+        .layer(Extension(config))
+}
+
+#[tokio::test]
+async fn json() {
+    let app = json_app(AxumJsonConfig::default());
+    let (status, body) = send(
+        app,
+        post_json(Some("application/json"), r#"{"name":"doggo","age":3}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo { name: "doggo", age: 3 }"###);
+
+    let app = json_app(AxumJsonConfig::default());
+    let (status, body) = send(
+        app,
+        post_json(
+            Some("application/merge-patch+json"),
+            r#"{"name":"doggo","age":3}"#,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo { name: "doggo", age: 3 }"###);
+
+    let app = json_app(
+        AxumJsonConfig::default().content_type(|content_type| content_type == "text/plain"),
+    );
+    let (status, body) = send(
+        app,
+        post_json(Some("text/plain"), r#"{"name":"doggo","age":3}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo { name: "doggo", age: 3 }"###);
+
+    let app = json_app(AxumJsonConfig::default().content_type_required(false));
+    let (status, body) = send(app, post_json(None, r#"{"name":"doggo","age":3}"#)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo { name: "doggo", age: 3 }"###);
+}
+
+#[tokio::test]
+async fn json_errors() {
+    let app = json_app(AxumJsonConfig::default());
+    let (status, body) = send(
+        app,
+        post_json(Some("application/json"), r#"{"name":"doggo"}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing field `age`");
+
+    let app = json_app(AxumJsonConfig::default());
+    let (status, body) = send(
+        app,
+        post_json(
+            Some("application/json"),
+            "{\n  \"name\": \"doggo\"\n  \"age\": 3\n}",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid JSON at line 3, column 3: expected `,` or `}`");
+
+    let app = json_app(AxumJsonConfig::default());
+    let (status, body) = send(
+        app,
+        post_json(Some("application/json"), r#"{"name":"doggo","age":"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid JSON at line 1, column 22: EOF while parsing a value");
+
+    let app = json_app(AxumJsonConfig::default());
+    let (status, body) = send(app, post_json(None, r#"{"name":"doggo","age":3}"#)).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Missing `Content-Type` header: expected `application/json`");
+
+    let app = json_app(AxumJsonConfig::default());
+    let (status, body) = send(
+        app,
+        post_json(Some("text/plain"), r#"{"name":"doggo","age":3}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Invalid `Content-Type` header `text/plain`: expected `application/json`");
+
+    let app = json_app(AxumJsonConfig::default().limit(8));
+    let (status, body) = send(
+        app,
+        post_json(Some("application/json"), r#"{"name":"doggo","age":3}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_snapshot!(body, @"The payload is larger than allowed (limit: 8 bytes)");
+
+    let app = json_app(AxumJsonConfig::default()).layer(DefaultBodyLimit::max(8));
+    let (status, body) = send(
+        app,
+        post_json(Some("application/json"), r#"{"name":"doggo","age":3}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_snapshot!(body, @"The payload is larger than allowed");
+}
+
+async fn doggos(item: AxumNdJson<Doggo, JsonError>) -> String {
+    item.into_inner()
+        .map(|result| match result {
+            Ok(doggo) => format!("{doggo:?}"),
+            Err(error) => error.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .await
+        .join("\n")
+}
+
+fn ndjson_app(config: AxumNdJsonConfig) -> Router {
+    Router::new()
+        .route("/doggos", post(doggos))
+        .layer(Extension(config))
+}
+
+/// Streams the chunks as the body of the request.
+fn post_chunks(content_type: Option<&str>, chunks: Vec<&'static str>) -> Request<Body> {
+    let chunks = chunks.into_iter().map(Ok::<_, Infallible>);
+    let mut request = Request::post("/doggos");
+    if let Some(content_type) = content_type {
+        request = request.header("content-type", content_type);
+    }
+    request
+        .body(Body::from_stream(futures::stream::iter(chunks)))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn ndjson() {
+    // the lines are split across the chunks of the body
+    let chunks = [
+        "{\"name\":\"doggo\",\"age\":3}\n{\"name\":\"do",
+        "ggo\",\"age\":-1}\n\n{\"name\":",
+        "\"doggo\",\"age\":",
+        "4}",
+    ];
+    let config = AxumNdJsonConfig::default();
+    let (status, body) = send(
+        ndjson_app(config),
+        post_chunks(Some("application/x-ndjson"), chunks.to_vec()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"
+    Doggo { name: "doggo", age: 3 }
+    Invalid value type at `[1].age`: expected a positive integer, but found a negative integer: `-1`
+    Doggo { name: "doggo", age: 4 }
+    "###);
+
+    let config = AxumNdJsonConfig::default().max_line_length(24);
+    let (status, body) = send(
+        ndjson_app(config),
+        post_chunks(Some("application/x-ndjson"), chunks.to_vec()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"
+    Doggo { name: "doggo", age: 3 }
+    Invalid value at `[1]`: The line is longer than allowed (limit: 24 bytes)
+    "###);
+
+    let config = AxumNdJsonConfig::default();
+    let (status, body) = send(ndjson_app(config), post_chunks(None, chunks.to_vec())).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Missing `Content-Type` header: expected `application/x-ndjson`");
+
+    let config = AxumNdJsonConfig::default();
+    let (status, body) = send(
+        ndjson_app(config),
+        post_chunks(Some("application/json"), chunks.to_vec()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Invalid `Content-Type` header `application/json`: expected `application/x-ndjson`");
+
+    let config = AxumNdJsonConfig::default().content_type_required(false);
+    let (status, _) = send(ndjson_app(config), post_chunks(None, chunks.to_vec())).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+async fn doggo_array(item: AxumJsonArray<Doggo, JsonError>) -> String {
+    item.into_inner()
+        .map(|result| match result {
+            Ok(doggo) => format!("{doggo:?}"),
+            Err(error) => error.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .await
+        .join("\n")
+}
+
+fn json_array_app(config: AxumJsonArrayConfig) -> Router {
+    Router::new()
+        .route("/doggos", post(doggo_array))
+        .layer(Extension(config))
+}
+
+#[tokio::test]
+async fn json_array() {
+    // the elements are split across the chunks of the body
+    let chunks = [
+        "[{\"name\":\"doggo\",\"age\":3},{\"name\":\"do",
+        "ggo\",\"age\":-1},\n{\"name\":",
+        "\"doggo\",\"age\":",
+        "4}]",
+    ];
+    let config = AxumJsonArrayConfig::default();
+    let (status, body) = send(
+        json_array_app(config),
+        post_chunks(Some("application/json"), chunks.to_vec()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"
+    Doggo { name: "doggo", age: 3 }
+    Invalid value type at `[1].age`: expected a positive integer, but found a negative integer: `-1`
+    Doggo { name: "doggo", age: 4 }
+    "###);
+
+    let config = AxumJsonArrayConfig::default().max_element_length(24);
+    let (status, body) = send(
+        json_array_app(config),
+        post_chunks(Some("application/json"), chunks.to_vec()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"
+    Doggo { name: "doggo", age: 3 }
+    Invalid value at `[1]`: The element is longer than allowed (limit: 24 bytes)
+    "###);
+
+    let config = AxumJsonArrayConfig::default();
+    let (status, body) = send(json_array_app(config), post_chunks(None, chunks.to_vec())).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Missing `Content-Type` header: expected `application/json`");
+
+    let config = AxumJsonArrayConfig::default();
+    let (status, body) = send(
+        json_array_app(config),
+        post_chunks(Some("application/x-ndjson"), chunks.to_vec()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Invalid `Content-Type` header `application/x-ndjson`: expected `application/json`");
+
+    let config = AxumJsonArrayConfig::default().content_type_required(false);
+    let (status, _) = send(json_array_app(config), post_chunks(None, chunks.to_vec())).await;
+    assert_eq!(status, StatusCode::OK);
+}
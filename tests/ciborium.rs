@@ -0,0 +1,142 @@
+use ciborium::Value;
+use deserr::{errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Request {
+    id: u64,
+    offset: i64,
+    payload: Vec<u8>,
+    #[deserr(default)]
+    checksum: Option<[u8; 4]>,
+    #[deserr(default)]
+    tags: Vec<String>,
+}
+
+fn text(s: &str) -> Value {
+    Value::Text(s.to_owned())
+}
+
+#[test]
+fn values() {
+    let value = Value::Map(vec![
+        (text("id"), Value::Integer(42.into())),
+        (text("offset"), Value::Integer((-7).into())),
+        // tagged values are deserialized as if they weren't tagged
+        (
+            text("payload"),
+            Value::Tag(24, Box::new(Value::Bytes(vec![1, 2]))),
+        ),
+        (text("checksum"), Value::Bytes(vec![0xde, 0xad, 0xbe, 0xef])),
+        (text("tags"), Value::Array(vec![text("a"), text("b")])),
+    ]);
+    let request = deserr::deserialize::<Request, _, JsonError>(value).unwrap();
+    assert_debug_snapshot!(request, @r###"
+    Request {
+        id: 42,
+        offset: -7,
+        payload: [
+            1,
+            2,
+        ],
+        checksum: Some(
+            [
+                222,
+                173,
+                190,
+                239,
+            ],
+        ),
+        tags: [
+            "a",
+            "b",
+        ],
+    }
+    "###);
+
+    // the byte strings can also be deserialized as arrays of numbers
+    let value = Value::Map(vec![
+        (Value::Integer(1.into()), Value::Bytes(vec![1, 2])),
+        (Value::Bool(true), text("ignored")),
+    ]);
+    let map =
+        deserr::deserialize::<std::collections::BTreeMap<String, Vec<u16>>, _, JsonError>(value)
+            .unwrap();
+    assert_debug_snapshot!(map, @r###"
+    {
+        "1": [
+            1,
+            2,
+        ],
+    }
+    "###);
+}
+
+#[test]
+fn error_msg() {
+    let value = Value::Map(vec![
+        (text("id"), Value::Integer(42.into())),
+        (text("offset"), Value::Integer(0.into())),
+        (text("payload"), text("doggo")),
+    ]);
+    let err = deserr::deserialize::<Request, _, JsonError>(value).unwrap_err();
+    assert_snapshot!(err, @r###"Invalid value type at `.payload`: expected an array, but found a string: `"doggo"`"###);
+
+    let value = Value::Map(vec![
+        (text("id"), Value::Integer(42.into())),
+        (text("offset"), Value::Integer(0.into())),
+        (text("payload"), Value::Bytes(vec![])),
+        (text("tags"), Value::Array(vec![Value::Bytes(vec![1, 2])])),
+    ]);
+    let err = deserr::deserialize::<Request, _, JsonError>(value).unwrap_err();
+    assert_snapshot!(err, @"Invalid value type at `.tags[0]`: expected a string, but found a byte string: `[1, 2]`");
+
+    let value = Value::Map(vec![
+        (text("id"), Value::Integer(42.into())),
+        (text("offset"), Value::Integer(0.into())),
+        (text("payload"), Value::Bytes(vec![])),
+        (text("checksum"), Value::Bytes(vec![1, 2])),
+    ]);
+    let err = deserr::deserialize::<Request, _, JsonError>(value).unwrap_err();
+    assert_snapshot!(err, @"Invalid array len at `.checksum`. Received 2 elements instead of 4: `[1,2]`");
+
+    let value = Value::Map(vec![(text("payload"), Value::Bytes(vec![1, 2, 255]))]);
+    let err =
+        deserr::deserialize::<std::collections::BTreeMap<String, Vec<i8>>, _, JsonError>(value)
+            .unwrap_err();
+    assert_snapshot!(err, @"Invalid value at `.payload[2]`: value: `255` is too large to be deserialized, maximum value authorized is `127`");
+}
+
+#[test]
+fn big_integers() {
+    let bignum = |tag: u64, bytes: &[u8]| Value::Tag(tag, Box::new(Value::Bytes(bytes.to_vec())));
+    let value = Value::Array(vec![
+        Value::Integer((-18446744073709551616i128).try_into().unwrap()),
+        bignum(2, &[1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        bignum(3, &[1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        bignum(2, &[0, 0, 42]),
+        bignum(3, &[42]),
+        bignum(2, &[0xff; 16]),
+    ]);
+    let integers = deserr::deserialize::<Vec<i128>, _, JsonError>(value.clone()).unwrap_err();
+    assert_snapshot!(integers, @"Invalid value at `[5]`: expected a value between -170141183460469231731687303715884105728 and 170141183460469231731687303715884105727, but found 340282366920938463463374607431768211455");
+    let numbers = deserr::deserialize::<Vec<deserr::Decimal>, _, JsonError>(value).unwrap();
+    let numbers = numbers.iter().map(|n| n.as_str()).collect::<Vec<_>>();
+    assert_debug_snapshot!(numbers, @r###"
+    [
+        "-18446744073709551616",
+        "18446744073709551616",
+        "-18446744073709551617",
+        "42",
+        "-43",
+        "340282366920938463463374607431768211455",
+    ]
+    "###);
+
+    // bigger than a `u128`
+    let value = bignum(3, &[0xff; 17]);
+    let err = deserr::deserialize::<u128, _, JsonError>(value).unwrap_err();
+    assert_snapshot!(err, @"Invalid value type: expected a positive integer, but found a number: `-87112285931760246646623899502532662132736`");
+}
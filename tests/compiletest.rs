@@ -4,4 +4,6 @@
 fn ui() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/ui/*.rs");
+    #[cfg(not(feature = "regex"))]
+    t.compile_fail("tests/ui/no-regex/*.rs");
 }
@@ -0,0 +1,141 @@
+use deserr::{env::EnvConfig, errors::EnvError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Config {
+    db: Db,
+    #[deserr(default)]
+    debug: bool,
+    #[deserr(default)]
+    ports: Vec<u16>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Db {
+    host: String,
+    #[deserr(range(min = 1))]
+    port: u16,
+}
+
+fn deserialize(vars: &[(&str, &str)], config: &EnvConfig) -> Result<Config, EnvError> {
+    let vars = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+    deserr::env::deserialize_vars::<Config>(vars, config)
+}
+
+#[test]
+fn nested_variables() {
+    let config = EnvConfig::new("APP");
+    let data = deserialize(
+        &[
+            ("APP__DB__HOST", "localhost"),
+            ("APP__DB__PORT", "5432"),
+            ("APP__DEBUG", "true"),
+            ("APP__PORTS", "7700,7701"),
+            ("PATH", "/usr/bin"),
+            ("APPLICATION", "doggo"),
+        ],
+        &config,
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Config {
+        db: Db {
+            host: "localhost",
+            port: 5432,
+        },
+        debug: true,
+        ports: [
+            7700,
+            7701,
+        ],
+    }
+    "###);
+
+    let config = EnvConfig::new("APP").separator("_");
+    let data = deserialize(
+        &[("APP_DB_HOST", "localhost"), ("APP_DB_PORT", "5432")],
+        &config,
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Config {
+        db: Db {
+            host: "localhost",
+            port: 5432,
+        },
+        debug: false,
+        ports: [],
+    }
+    "###);
+}
+
+#[test]
+fn error_msg() {
+    let config = EnvConfig::new("APP");
+
+    let err = deserialize(&[("APP__DB__HOST", "localhost")], &config).unwrap_err();
+    assert_snapshot!(err, @"Missing environment variable `APP__DB__PORT`");
+
+    let err = deserialize(
+        &[("APP__DB__HOST", "localhost"), ("APP__DB__PORT", "five")],
+        &config,
+    )
+    .unwrap_err();
+    assert_snapshot!(err, @"Invalid value type for environment variable `APP__DB__PORT`: expected a positive integer, but found `five`");
+
+    let err = deserialize(
+        &[("APP__DB__HOST", "localhost"), ("APP__DB__PORT", "0")],
+        &config,
+    )
+    .unwrap_err();
+    assert_snapshot!(err, @"Invalid value for environment variable `APP__DB__PORT`: expected a value greater than or equal to 1, but found 0");
+
+    let err = deserialize(
+        &[
+            ("APP__DB__HOST", "localhost"),
+            ("APP__DB__PORT", "5432"),
+            ("APP__DB__HOTS", "localhost"),
+        ],
+        &config,
+    )
+    .unwrap_err();
+    assert_snapshot!(err, @"Unknown environment variable `APP__DB__HOTS`: did you mean `APP__DB__HOST`?");
+
+    let err = deserialize(
+        &[
+            ("APP__DB__HOST", "localhost"),
+            ("APP__DB__PORT", "5432"),
+            ("APP__PORTS", "7700,doggo"),
+        ],
+        &config,
+    )
+    .unwrap_err();
+    assert_snapshot!(err, @"Invalid value type for environment variable `APP__PORTS[1]`: expected a positive integer, but found `doggo`");
+
+    let config = EnvConfig::new("APP").separator("_");
+    let err = deserialize(&[("APP_DB_HOST", "localhost")], &config).unwrap_err();
+    assert_snapshot!(err, @"Missing environment variable `APP_DB_PORT`");
+}
+
+#[test]
+fn uppercase_field_names() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = camelCase)]
+    struct Server {
+        max_connections: u32,
+    }
+
+    // the names of the variables are lowercased, so `maxConnections` is never found
+    let config = EnvConfig::new("APP");
+    let vars = [("APP__MAXCONNECTIONS", "10")].map(|(k, v)| (k.to_owned(), v.to_owned()));
+    let err = deserr::env::deserialize_vars::<Server>(vars, &config).unwrap_err();
+    assert_snapshot!(err, @"Missing environment variable `APP__MAXCONNECTIONS`: the field `maxConnections` can't be set from an environment variable since its name isn't lowercase");
+}
@@ -0,0 +1,197 @@
+use deserr::errors::{JsonError, LayeredError};
+use deserr::json5::{parse, Json5Value, Position, Span};
+use deserr::Deserr;
+use insta::{assert_debug_snapshot, assert_snapshot};
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Config {
+    db: Db,
+    ratio: f64,
+    offset: i64,
+    #[deserr(default)]
+    features: Vec<String>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Db {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn json5_syntax() {
+    let text = r#"
+    // the configuration of the development server
+    {
+        /* the database
+           is local */
+        db: {
+            host: 'local\
+host',
+            port: 0x1538,
+        },
+        "ratio": .5,
+        offset: -0x10,
+        features: ['search', "vectors", 'don\'t',],
+    }
+    "#;
+    let config = deserr::json5::deserialize::<Config, JsonError>(text).unwrap();
+    assert_debug_snapshot!(config, @r###"
+    Config {
+        db: Db {
+            host: "localhost",
+            port: 5432,
+        },
+        ratio: 0.5,
+        offset: -16,
+        features: [
+            "search",
+            "vectors",
+            "don't",
+        ],
+    }
+    "###);
+
+    // strict JSON is valid JSON5
+    let text = r#"{ "db": { "host": "localhost", "port": 5432 }, "ratio": 1e-1, "offset": 3 }"#;
+    let config = deserr::json5::deserialize::<Config, JsonError>(text).unwrap();
+    assert_debug_snapshot!(config, @r###"
+    Config {
+        db: Db {
+            host: "localhost",
+            port: 5432,
+        },
+        ratio: 0.1,
+        offset: 3,
+        features: [],
+    }
+    "###);
+}
+
+#[test]
+fn numbers() {
+    let values = parse(
+        "[+1, -1, Infinity, -Infinity, 0xff, -0x10, 18446744073709551616, 0x100000000000000000000000000000000]",
+    )
+    .unwrap()
+    .value;
+    let Json5Value::Array(values) = values else {
+        panic!()
+    };
+    let values = values.into_iter().map(|v| v.value).collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        [
+            Json5Value::Integer(1),
+            Json5Value::NegativeInteger(-1),
+            Json5Value::Float(f64::INFINITY),
+            Json5Value::Float(f64::NEG_INFINITY),
+            Json5Value::Integer(255),
+            Json5Value::NegativeInteger(-16),
+            Json5Value::Number(String::from("18446744073709551616")),
+            Json5Value::Number(String::from("340282366920938463463374607431768211456")),
+        ]
+    );
+    assert!(matches!(parse("NaN").unwrap().value, Json5Value::Float(x) if x.is_nan()));
+    assert_eq!(
+        parse(r"'\ud83d\udc36 \x41\u00e9'").unwrap().value,
+        Json5Value::String(String::from("🐶 Aé"))
+    );
+
+    let nested = format!("{}{}", "[".repeat(128), "]".repeat(128));
+    assert!(parse(&nested).is_ok());
+    assert_eq!(
+        deserr::json5::deserialize::<Vec<f64>, JsonError>("[0, 0.5, -0e1, 0x0F]").unwrap(),
+        [0., 0.5, 0., 15.]
+    );
+
+    let floats = deserr::json5::deserialize::<Vec<f64>, JsonError>("[1., .5, +1e3, -2.5E-1]");
+    assert_eq!(floats.unwrap(), [1., 0.5, 1000., -0.25]);
+
+    let integers = deserr::json5::deserialize::<Vec<i128>, JsonError>(
+        "[-170141183460469231731687303715884105728, 0x7fffffffffffffffffffffffffffffff]",
+    );
+    assert_eq!(integers.unwrap(), [i128::MIN, i128::MAX]);
+}
+
+#[cfg(feature = "arbitrary-precision")]
+#[test]
+fn exact_decimals() {
+    let decimals = deserr::json5::deserialize::<Vec<deserr::Decimal>, JsonError>(
+        "[1., .5, +1e3, 0.1000000000000000000001]",
+    )
+    .unwrap();
+    let decimals = decimals.iter().map(|d| d.as_str()).collect::<Vec<_>>();
+    assert_eq!(decimals, ["1", "0.5", "1e3", "0.1000000000000000000001"]);
+}
+
+#[test]
+fn syntax_errors() {
+    let error = |text: &str| {
+        deserr::json5::deserialize::<Config, JsonError>(text)
+            .unwrap_err()
+            .to_string()
+    };
+    assert_snapshot!(error("{ db: { host: 'localhost' port: 5432 } }"), @"Invalid JSON at line 1, column 27: expected `,` or `}`");
+    assert_snapshot!(error("{\n  db: {\n    host: localhost,\n  }\n}"), @"Invalid JSON at line 3, column 11: expected value");
+    assert_snapshot!(error("{ db: "), @"Invalid JSON at line 1, column 6: EOF while parsing a value");
+    assert_snapshot!(error("{ ratio: 1, } }"), @"Invalid JSON at line 1, column 15: trailing characters");
+    assert_snapshot!(error("{ 'db\n': 1 }"), @"Invalid JSON at line 1, column 6: unescaped newline in a string");
+    assert_snapshot!(error("{ /* db: 1 }"), @"Invalid JSON at line 1, column 12: EOF while parsing a comment");
+    assert_snapshot!(error("[1 2]"), @"Invalid JSON at line 1, column 4: expected `,` or `]`");
+    assert_snapshot!(error("{ 1: 2 }"), @"Invalid JSON at line 1, column 3: key must be a string or an identifier");
+    assert_snapshot!(error("'\\1'"), @r###"Invalid JSON at line 1, column 3: invalid escape"###);
+    assert_snapshot!(error("-foo"), @"Invalid JSON at line 1, column 4: invalid number");
+    assert_snapshot!(error("007"), @"Invalid JSON at line 1, column 3: invalid number");
+    assert_snapshot!(error("-01.5"), @"Invalid JSON at line 1, column 5: invalid number");
+    assert_snapshot!(error(&format!("{}{}", "[".repeat(129), "]".repeat(129))), @"Invalid JSON at line 1, column 129: recursion limit exceeded");
+    if cfg!(not(feature = "arbitrary-precision")) {
+        assert_snapshot!(error("1e400"), @"Invalid JSON at line 1, column 5: number out of range");
+    }
+}
+
+#[test]
+fn deserialization_errors() {
+    let text = "{ db: { host: 'localhost', port: 5432 }, ratio: 'half', offset: 0 }";
+    let error = deserr::json5::deserialize::<Config, JsonError>(text).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.ratio`: expected a number, but found a string: `\"half\"`");
+}
+
+#[test]
+fn spans() {
+    let text = "{\n  db: {\n    host: 'localhost',\n    port: 70000,\n  },\n  ratio: 0.5,\n  offset: 0,\n}";
+    let value = parse(text).unwrap();
+    let error = deserr::deserialize::<Config, _, LayeredError>(value.clone()).unwrap_err();
+    assert_eq!(
+        *error.location(),
+        deserr::ValuePointerRef::Origin
+            .push_key("db")
+            .push_key("port")
+            .to_owned()
+    );
+    assert_eq!(
+        value.span_of(error.location()),
+        Some(Span {
+            start: Position {
+                line: 4,
+                column: 11
+            },
+            end: Position {
+                line: 4,
+                column: 15
+            },
+        })
+    );
+    let db = value.span_of(&deserr::ValuePointerRef::Origin.push_key("db").to_owned());
+    assert_eq!(
+        db,
+        Some(Span {
+            start: Position { line: 2, column: 7 },
+            end: Position { line: 5, column: 3 },
+        })
+    );
+}
@@ -0,0 +1,167 @@
+use std::io::BufReader;
+
+use deserr::json_array::JsonArrayReader;
+use deserr::{errors::JsonError, Deserr};
+use insta::assert_snapshot;
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Document {
+    id: u32,
+    #[deserr(default)]
+    title: Option<String>,
+}
+
+/// Return the result of each element, one per line. The payload is read a few bytes at a
+/// time, so that the elements are split across the reads.
+fn deserialize(payload: &str, stop_on_error: bool) -> String {
+    let reader = BufReader::with_capacity(3, payload.as_bytes());
+    JsonArrayReader::<_, Document, JsonError>::new(reader)
+        .stop_on_error(stop_on_error)
+        .map(|result| match result {
+            Ok(document) => format!("{document:?}"),
+            Err(error) => error.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn elements() {
+    let payload = r#" [
+        { "id": 1, "title": "a \"quoted\" ] title, with [brackets]" },
+        {"id":2,"title":null} ,{ "id": 3 }
+    ]
+    "#;
+    assert_snapshot!(deserialize(payload, false), @r###"
+    Document { id: 1, title: Some("a \"quoted\" ] title, with [brackets]") }
+    Document { id: 2, title: None }
+    Document { id: 3, title: None }
+    "###);
+
+    assert_snapshot!(deserialize("[]", false), @"");
+    assert_snapshot!(deserialize(" [ ] ", false), @"");
+}
+
+#[test]
+fn error_msg() {
+    let payload = r#"[
+        { "id": 1 },
+        { "id": -2 },
+        { "id": 3, "tilte": "doggo" },
+        { "id": 4 "title": "doggo" },
+        { "id": 5 }
+    ]"#;
+    assert_snapshot!(deserialize(payload, false), @r###"
+    Document { id: 1, title: None }
+    Invalid value type at `[1].id`: expected a positive integer, but found a negative integer: `-2`
+    Unknown field `tilte` inside `[2]`: did you mean `title`? expected one of `id`, `title`
+    Invalid JSON at line 5, column 19: expected `,` or `}`
+    Document { id: 5, title: None }
+    "###);
+
+    assert_snapshot!(deserialize(payload, true), @r###"
+    Document { id: 1, title: None }
+    Invalid value type at `[1].id`: expected a positive integer, but found a negative integer: `-2`
+    "###);
+
+    assert_snapshot!(deserialize(r#"{ "id": 1 }"#, false), @"Invalid JSON at line 1, column 1: expected an array");
+    assert_snapshot!(deserialize(r#"[{ "id": 1 },]"#, false), @r###"
+    Document { id: 1, title: None }
+    Invalid JSON at line 1, column 14: trailing comma
+    "###);
+    assert_snapshot!(deserialize(r#"[{ "id": 1 }, { "id": 2 }"#, false), @r###"
+    Document { id: 1, title: None }
+    Invalid JSON at line 1, column 25: EOF while parsing a list
+    "###);
+    assert_snapshot!(deserialize(r#"[{ "id": 1 }] []"#, false), @r###"
+    Document { id: 1, title: None }
+    Invalid JSON at line 1, column 15: trailing characters
+    "###);
+    assert_snapshot!(deserialize("", false), @"Invalid JSON at line 1, column 0: EOF while parsing a value");
+}
+
+/// Return the result of each element of the array at the given key path, one per line.
+fn deserialize_at(payload: &str, path: &[&str]) -> String {
+    let reader = BufReader::with_capacity(3, payload.as_bytes());
+    JsonArrayReader::<_, Document, JsonError>::new(reader)
+        .key_path(path.iter().copied())
+        .map(|result| match result {
+            Ok(document) => format!("{document:?}"),
+            Err(error) => error.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn key_path() {
+    let payload = r#"{
+        "skipped": { "documents": [{ "id": "not these ones" }], "text": "}],\"" },
+        "payload": {
+            "count": 3,
+            "documents": [{ "id": 1 }, { "id": -2 }, { "id": 3 }],
+            "after": [{ "id": 4 }]
+        },
+        "documents": []
+    }"#;
+    assert_snapshot!(deserialize_at(payload, &["payload", "documents"]), @r###"
+    Document { id: 1, title: None }
+    Invalid value type at `.payload.documents[1].id`: expected a positive integer, but found a negative integer: `-2`
+    Document { id: 3, title: None }
+    "###);
+    assert_snapshot!(deserialize_at(payload, &["documents"]), @"");
+    // the keys are compared once unescaped
+    let escaped = r#"{ "docu\u006dents": [{ "id": 1 }] }"#;
+    assert_snapshot!(deserialize_at(escaped, &["documents"]), @"Document { id: 1, title: None }");
+
+    assert_snapshot!(deserialize_at(payload, &["payload", "missing"]), @"Missing field `missing` inside `.payload`");
+    assert_snapshot!(deserialize_at(r#"{ "documents": {} }"#, &["documents"]), @"Invalid JSON at line 1, column 16: expected an array");
+    assert_snapshot!(deserialize_at(r#"[{ "id": 1 }]"#, &["documents"]), @"Invalid JSON at line 1, column 1: expected an object");
+    assert_snapshot!(deserialize_at(r#"{ "documents" [] }"#, &["documents"]), @"Invalid JSON at line 1, column 15: expected `:`");
+    assert_snapshot!(deserialize_at(r#"{ "documents": [{ "id": 1 }] } {}"#, &["documents"]), @r###"
+    Document { id: 1, title: None }
+    Invalid JSON at line 1, column 32: trailing characters
+    "###);
+    assert_snapshot!(deserialize_at(r#"{ "documents": [{ "id": 1 }], "#, &["documents"]), @r###"
+    Document { id: 1, title: None }
+    Invalid JSON at line 1, column 30: EOF while parsing an object
+    "###);
+}
+
+#[test]
+fn max_element_length() {
+    let payload = r#"[{ "id": 1 }, { "id": 2, "title": "doggo" }, { "id": 3 }]"#;
+    let deserialize = |limit| {
+        let reader = BufReader::with_capacity(3, payload.as_bytes());
+        JsonArrayReader::<_, Document, JsonError>::new(reader)
+            .max_element_length(limit)
+            .map(|result| match result {
+                Ok(document) => format!("{document:?}"),
+                Err(error) => error.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    // the element at the limit is accepted, and the longer one ends the iteration
+    assert_snapshot!(deserialize(11), @r###"
+    Document { id: 1, title: None }
+    Invalid value at `[1]`: The element is longer than allowed (limit: 11 bytes)
+    "###);
+    assert_snapshot!(deserialize(30), @r###"
+    Document { id: 1, title: None }
+    Document { id: 2, title: Some("doggo") }
+    Document { id: 3, title: None }
+    "###);
+
+    // the keys of the objects of the key path are limited too
+    let payload = r#"{ "a long key": [], "documents": [{ "id": 1 }] }"#;
+    let reader = BufReader::with_capacity(3, payload.as_bytes());
+    let results = JsonArrayReader::<_, Document, JsonError>::new(reader)
+        .key_path(["documents"])
+        .max_element_length(11)
+        .map(|result| result.unwrap_err().to_string())
+        .collect::<Vec<_>>();
+    assert_snapshot!(results.join("\n"), @"Invalid value: The key is longer than allowed (limit: 11 bytes)");
+}
@@ -0,0 +1,102 @@
+use deserr::{env::EnvConfig, layered::Layers, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Config {
+    db: Db,
+    #[deserr(default)]
+    debug: bool,
+    #[deserr(default)]
+    ports: Vec<u16>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Db {
+    host: String,
+    port: u16,
+}
+
+fn load(file: serde_json::Value, vars: &[(&str, &str)], cli: serde_json::Value) -> Layers {
+    let vars = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+    Layers::new()
+        .layer(
+            "defaults",
+            json!({ "db": { "host": "localhost", "port": 5432 }, "ports": [7700] }),
+        )
+        .layer("file `config.json`", file)
+        .env(vars, &EnvConfig::new("APP").separator("_"))
+        .layer("command line", cli)
+}
+
+#[test]
+fn precedence() {
+    let layers = load(
+        json!({ "db": { "host": "db.example.com" }, "ports": [7701, 7702] }),
+        &[("APP_DB_PORT", "6432"), ("APP_DEBUG", "true")],
+        json!({ "debug": false }),
+    );
+    assert_eq!(layers.source(&Default::default()), None);
+    let config = layers.deserialize::<Config>().unwrap();
+    assert_debug_snapshot!(config, @r###"
+    Config {
+        db: Db {
+            host: "db.example.com",
+            port: 6432,
+        },
+        debug: false,
+        ports: [
+            7701,
+            7702,
+        ],
+    }
+    "###);
+
+    let layers = load(json!({}), &[("APP_PORTS", "1,2,3")], json!({}));
+    let config = layers.deserialize::<Config>().unwrap();
+    assert_debug_snapshot!(config.ports, @r###"
+    [
+        1,
+        2,
+        3,
+    ]
+    "###);
+}
+
+#[test]
+fn error_msg() {
+    let err = load(json!({}), &[("APP_DB_PORT", "doggo")], json!({}))
+        .deserialize::<Config>()
+        .unwrap_err();
+    assert_snapshot!(err, @r###"`db.port` from environment variable APP_DB_PORT: expected a positive integer, but found a string: `"doggo"`"###);
+
+    let err = load(json!({ "db": { "port": -1 } }), &[], json!({}))
+        .deserialize::<Config>()
+        .unwrap_err();
+    assert_snapshot!(err, @"`db.port` from file `config.json`: expected a positive integer, but found a negative integer: `-1`");
+
+    let err = load(json!({ "ports": [1, "two"] }), &[], json!({}))
+        .deserialize::<Config>()
+        .unwrap_err();
+    assert_snapshot!(err, @r###"`ports[1]` from file `config.json`: expected a positive integer, but found a string: `"two"`"###);
+
+    let err = load(json!({}), &[], json!({ "db": { "hots": "doggo" } }))
+        .deserialize::<Config>()
+        .unwrap_err();
+    assert_snapshot!(err, @"`db.hots` from command line: unknown field, did you mean `host`? expected one of `host` or `port`");
+
+    let err = Layers::new()
+        .layer(
+            "file `config.json`",
+            json!({ "db": { "host": "localhost" } }),
+        )
+        .deserialize::<Config>()
+        .unwrap_err();
+    assert_snapshot!(err, @"`db.port`: missing value");
+}
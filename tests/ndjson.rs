@@ -0,0 +1,134 @@
+use deserr::{errors::JsonError, Deserr};
+use insta::assert_snapshot;
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Document {
+    id: u32,
+    #[deserr(default)]
+    title: Option<String>,
+}
+
+/// Return the result of each line, one per line.
+fn deserialize(payload: &str, stop_on_error: bool) -> String {
+    deserr::ndjson::from_reader::<Document, JsonError, _>(payload.as_bytes())
+        .stop_on_error(stop_on_error)
+        .map(|result| match result {
+            Ok(document) => format!("{document:?}"),
+            Err(error) => error.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn lines() {
+    let payload = "{ \"id\": 1 }\r\n\n  \n{ \"id\": 2, \"title\": \"doggo\" }\n{ \"id\": 3 }";
+    assert_snapshot!(deserialize(payload, false), @r###"
+    Document { id: 1, title: None }
+    Document { id: 2, title: Some("doggo") }
+    Document { id: 3, title: None }
+    "###);
+
+    assert_snapshot!(deserialize("", false), @"");
+}
+
+#[test]
+fn error_msg() {
+    let payload = "{ \"id\": 1 }\n{ \"id\": -2 }\n\n{ \"id\": 3, \"tilte\": \"doggo\" }\n{ \"id\": 4\n{ \"id\": 5 }\n";
+    assert_snapshot!(deserialize(payload, false), @r###"
+    Document { id: 1, title: None }
+    Invalid value type at `[1].id`: expected a positive integer, but found a negative integer: `-2`
+    Unknown field `tilte` inside `[3]`: did you mean `title`? expected one of `id`, `title`
+    Invalid JSON at line 5, column 9: EOF while parsing an object
+    Document { id: 5, title: None }
+    "###);
+
+    assert_snapshot!(deserialize(payload, true), @r###"
+    Document { id: 1, title: None }
+    Invalid value type at `[1].id`: expected a positive integer, but found a negative integer: `-2`
+    "###);
+}
+
+#[test]
+fn read_error() {
+    struct FailingReader;
+
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("connection reset"))
+        }
+    }
+
+    let results = deserr::ndjson::from_reader::<Document, JsonError, _>(FailingReader)
+        .map(|result| result.unwrap_err().to_string())
+        .collect::<Vec<_>>();
+    assert_snapshot!(results.join("\n"), @"Invalid value: Failed to read the payload: connection reset");
+}
+
+#[test]
+fn max_line_length() {
+    let payload = "{ \"id\": 1 }\r\n{ \"id\": 2, \"title\": \"doggo\" }\n{ \"id\": 3 }\n";
+    let results = deserr::ndjson::from_reader::<Document, JsonError, _>(payload.as_bytes())
+        .max_line_length(12)
+        .map(|result| match result {
+            Ok(document) => format!("{document:?}"),
+            Err(error) => error.to_string(),
+        })
+        .collect::<Vec<_>>();
+    assert_snapshot!(results.join("\n"), @r###"
+    Document { id: 1, title: None }
+    Invalid value at `[1]`: The line is longer than allowed (limit: 12 bytes)
+    "###);
+
+    // the `\r\n` line endings are not counted either
+    let payload = "{ \"id\": 1 }\r\n{ \"id\": 2 }\r\n";
+    let results = deserr::ndjson::from_reader::<Document, JsonError, _>(payload.as_bytes())
+        .max_line_length(11)
+        .map(|result| result.is_ok())
+        .collect::<Vec<_>>();
+    assert_eq!(results, [true, true]);
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn stream_max_line_length() {
+    use futures::StreamExt;
+
+    // the long line is rejected before it is entirely received
+    let chunks = [
+        "{ \"id\": 1 }\n{ \"id\": 2, ",
+        "\"title\": ",
+        "\"doggo\" }\n",
+    ];
+    let stream = futures::stream::iter(chunks.map(Ok::<_, std::convert::Infallible>));
+    let results = deserr::ndjson::from_stream::<Document, JsonError, _>(stream)
+        .max_line_length(12)
+        .map(|result| match result {
+            Ok(document) => format!("{document:?}"),
+            Err(error) => error.to_string(),
+        })
+        .collect::<Vec<_>>();
+    let results = futures::executor::block_on(results);
+    assert_snapshot!(results.join("\n"), @r###"
+    Document { id: 1, title: None }
+    Invalid value at `[1]`: The line is longer than allowed (limit: 12 bytes)
+    "###);
+
+    let stream = futures::stream::iter(chunks.map(Ok::<_, std::convert::Infallible>));
+    let results = deserr::ndjson::from_stream::<Document, JsonError, _>(stream)
+        .max_line_length(32)
+        .map(|result| result.is_ok())
+        .collect::<Vec<_>>();
+    assert_eq!(futures::executor::block_on(results), [true, true]);
+
+    // a chunk may end between the `\r` and the `\n` of a line at the limit
+    let chunks = ["{ \"id\": 1 }\r", "\n{ \"id\": 2 }\r\n"];
+    let stream = futures::stream::iter(chunks.map(Ok::<_, std::convert::Infallible>));
+    let results = deserr::ndjson::from_stream::<Document, JsonError, _>(stream)
+        .max_line_length(11)
+        .map(|result| result.is_ok())
+        .collect::<Vec<_>>();
+    assert_eq!(futures::executor::block_on(results), [true, true]);
+}
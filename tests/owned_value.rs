@@ -0,0 +1,152 @@
+use deserr::errors::JsonError;
+use deserr::{value, Deserr, DuplicateKey, IntoValue, OwnedMap, OwnedValue, ValueKind};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Doggo {
+    name: String,
+    age: u8,
+    extra: OwnedValue,
+}
+
+#[test]
+fn macro_syntax() {
+    let name = "kefir";
+    let key = "computed";
+    let value = value!({
+        "name": name,
+        "age": -(1 + 1),
+        "ratio": 0.5,
+        "toys": ["ball", null, [], {},],
+        key: Some(true),
+        "nothing": None::<u8>,
+    });
+    assert_debug_snapshot!(value, @r###"
+    Map(
+        OwnedMap(
+            [
+                (
+                    "name",
+                    String(
+                        "kefir",
+                    ),
+                ),
+                (
+                    "age",
+                    NegativeInteger(
+                        -2,
+                    ),
+                ),
+                (
+                    "ratio",
+                    Float(
+                        0.5,
+                    ),
+                ),
+                (
+                    "toys",
+                    Sequence(
+                        [
+                            String(
+                                "ball",
+                            ),
+                            Null,
+                            Sequence(
+                                [],
+                            ),
+                            Map(
+                                OwnedMap(
+                                    [],
+                                ),
+                            ),
+                        ],
+                    ),
+                ),
+                (
+                    "computed",
+                    Boolean(
+                        true,
+                    ),
+                ),
+                (
+                    "nothing",
+                    Null,
+                ),
+            ],
+        ),
+    )
+    "###);
+    assert_eq!(value!(null), OwnedValue::Null);
+    assert_eq!(value!(3), OwnedValue::Integer(3));
+    assert_eq!(value!(vec![1, 2]), value!([1, 2]));
+}
+
+#[test]
+fn map() {
+    let mut map = OwnedMap::new();
+    assert_eq!(map.insert("a", value!(1)), Ok(()));
+    assert_eq!(map.insert("b", value!(2)), Ok(()));
+    assert_eq!(
+        map.insert("a", value!(3)),
+        Err(DuplicateKey(String::from("a")))
+    );
+    *map.get_mut("a").unwrap() = value!(3);
+    // the key keeps its position
+    let entries = map.iter().collect::<Vec<_>>();
+    assert_eq!(entries, [("a", &value!(3)), ("b", &value!(2))]);
+    assert!(map.contains_key("b"));
+    assert_eq!(value!({ "a": 3, "b": 2 }), OwnedValue::Map(map));
+}
+
+#[test]
+#[should_panic(expected = "the key `a` is specified multiple times")]
+fn map_duplicated_key() {
+    value!({ "a": 1, "b": 2, "a": 3 });
+}
+
+#[test]
+fn deserialize() {
+    let value = value!({ "name": "kefir", "age": 2, "extra": { "toys": ["ball"], "bytes": (OwnedValue::Bytes(vec![1, 2])) } });
+    assert_eq!(value.kind(), ValueKind::Map);
+    let doggo = deserr::deserialize::<Doggo, _, JsonError>(value).unwrap();
+    assert_eq!(
+        doggo.extra,
+        value!({ "toys": ["ball"], "bytes": (OwnedValue::Bytes(vec![1, 2])) })
+    );
+
+    // the sub-tree is captured from any format
+    let value = json!({ "name": "kefir", "age": 2, "extra": [1, -1, 1.5, "a", null] });
+    let doggo = deserr::deserialize::<Doggo, _, JsonError>(value).unwrap();
+    assert_eq!(doggo.extra, value!([1, -1, 1.5, "a", null]));
+
+    let value = value!({ "name": "kefir", "age": -2, "extra": null });
+    let error = deserr::deserialize::<Doggo, _, JsonError>(value).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.age`: expected a positive integer, but found a negative integer: `-2`");
+}
+
+#[test]
+fn serde_json_conversions() {
+    // without the `preserve_order` feature, the keys of serde_json are sorted
+    let json = json!({ "age": 2, "name": "kefir", "toys": [1.5, -1, null, true] });
+    let value = OwnedValue::from(json.clone());
+    assert_eq!(
+        value,
+        value!({ "age": 2, "name": "kefir", "toys": [1.5, -1, null, true] })
+    );
+    assert_eq!(serde_json::Value::from(value), json);
+
+    // the decimals are only kept as text when a float would change them
+    let json: serde_json::Value = serde_json::from_str("[0.5, 12.50]").unwrap();
+    let expected = if cfg!(feature = "arbitrary-precision") {
+        value!([0.5, (OwnedValue::Number(String::from("12.50")))])
+    } else {
+        value!([0.5, 12.5])
+    };
+    assert_eq!(OwnedValue::from(json), expected);
+
+    let value = value!([(OwnedValue::Bytes(vec![1, 2])), (f64::NAN)]);
+    assert_eq!(serde_json::Value::from(value), json!([[1, 2], null]));
+}
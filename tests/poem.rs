@@ -0,0 +1,116 @@
+use deserr::errors::{JsonError, QueryParamError};
+use deserr::poem::{PoemJson, PoemQuery};
+use deserr::query_string::QueryConfig;
+use deserr::Deserr;
+use insta::assert_snapshot;
+use poem::http::{Method, StatusCode};
+use poem::middleware::AddData;
+use poem::{get, handler, post, Endpoint, EndpointExt, Request, Route};
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Search {
+    q: String,
+    #[deserr(default)]
+    limit: Option<u32>,
+    #[deserr(default)]
+    filter: Option<SearchFilter>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct SearchFilter {
+    genre: String,
+}
+
+#[handler]
+fn search(item: PoemQuery<Search, QueryParamError>) -> String {
+    format!("{:?}", item.0)
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Doggo {
+    name: String,
+    age: u8,
+}
+
+#[handler]
+fn doggo(item: PoemJson<Doggo, JsonError>) -> String {
+    format!("{:?}", item.0)
+}
+
+async fn call(request: Request) -> (StatusCode, String) {
+    let app = Route::new()
+        .at("/search", get(search))
+        .at("/doggo", post(doggo))
+        .with(AddData::new(QueryConfig::new().brackets(true)));
+    let response = app.get_response(request).await;
+    let status = response.status();
+    let body = response.into_body().into_string().await.unwrap();
+    (status, body)
+}
+
+async fn get_query(query: &str) -> (StatusCode, String) {
+    call(
+        Request::builder()
+            .uri_str(format!("/search?{query}"))
+            .finish(),
+    )
+    .await
+}
+
+async fn post_json(content_type: Option<&str>, body: &'static str) -> (StatusCode, String) {
+    let mut request = Request::builder().method(Method::POST).uri_str("/doggo");
+    if let Some(content_type) = content_type {
+        request = request.content_type(content_type);
+    }
+    call(request.body(body)).await
+}
+
+#[tokio::test]
+async fn query() {
+    let (status, body) = get_query("q=doggo&limit=12&filter[genre]=comedy").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Search { q: "doggo", limit: Some(12), filter: Some(SearchFilter { genre: "comedy" }) }"###);
+}
+
+#[tokio::test]
+async fn query_errors() {
+    let (status, body) = get_query("limit=12").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing parameter `q`");
+
+    let (status, body) = get_query("q=doggo&limit=twelve").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for parameter `limit`: expected a positive integer, but found a string: `twelve`");
+}
+
+#[tokio::test]
+async fn json() {
+    let (status, body) = post_json(Some("application/json"), r#"{"name":"doggo","age":3}"#).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo { name: "doggo", age: 3 }"###);
+}
+
+#[tokio::test]
+async fn json_errors() {
+    let (status, body) = post_json(Some("application/json"), r#"{"name":"doggo"}"#).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing field `age`");
+
+    let (status, body) = post_json(Some("application/json"), r#"{"name":"doggo","age":"#).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid JSON at line 1, column 22: EOF while parsing a value");
+
+    let (status, body) = post_json(None, r#"{"name":"doggo","age":3}"#).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Missing `Content-Type` header: expected `application/json`");
+
+    let (status, body) = post_json(Some("text/plain"), r#"{"name":"doggo","age":3}"#).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_snapshot!(body, @"Invalid `Content-Type` header `text/plain`: expected `application/json`");
+}
@@ -0,0 +1,348 @@
+use deserr::{
+    errors::QueryParamError,
+    query_string::{QueryConfig, QueryValue},
+    Deserr,
+};
+use insta::{assert_debug_snapshot, assert_snapshot};
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Search {
+    #[deserr(default)]
+    q: Option<String>,
+    #[deserr(default)]
+    limit: Option<u32>,
+    #[deserr(default)]
+    offset: Option<i64>,
+    #[deserr(default)]
+    ranking_score_threshold: Option<f64>,
+    #[deserr(default)]
+    exhaustive: Option<bool>,
+}
+
+fn deserialize(query: &str) -> Result<Search, QueryParamError> {
+    deserr::deserialize::<Search, _, QueryParamError>(QueryValue::from_query(query).unwrap())
+}
+
+#[test]
+fn coerce_strings() {
+    let data = deserialize(
+        "q=hello%20world&limit=20&offset=-3&ranking_score_threshold=0.5&exhaustive=true",
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        q: Some(
+            "hello world",
+        ),
+        limit: Some(
+            20,
+        ),
+        offset: Some(
+            -3,
+        ),
+        ranking_score_threshold: Some(
+            0.5,
+        ),
+        exhaustive: Some(
+            true,
+        ),
+    }
+    "###);
+
+    // a number stays a string when a string is expected
+    let data = deserialize("q=42").unwrap();
+
+    assert_debug_snapshot!(data.q, @r###"
+    Some(
+        "42",
+    )
+    "###);
+
+    // integers are also floats
+    let data = deserialize("ranking_score_threshold=1").unwrap();
+
+    assert_debug_snapshot!(data.ranking_score_threshold, @r###"
+    Some(
+        1.0,
+    )
+    "###);
+
+    // the last value of a parameter is kept
+    let data = deserialize("limit=1&limit=2").unwrap();
+
+    assert_debug_snapshot!(data.limit, @r###"
+    Some(
+        2,
+    )
+    "###);
+}
+
+#[test]
+fn coercion_errors() {
+    let error = deserialize("limit=abc").unwrap_err();
+    assert_snapshot!(error, @"Invalid value type for parameter `limit`: expected a positive integer, but found a string: `abc`");
+
+    let error = deserialize("limit=-1").unwrap_err();
+    assert_snapshot!(error, @"Invalid value type for parameter `limit`: expected a positive integer, but found an integer: `-1`");
+
+    let error = deserialize("limit=1.5").unwrap_err();
+    assert_snapshot!(error, @"Invalid value type for parameter `limit`: expected a positive integer, but found a number: `1.5`");
+
+    let error = deserialize("limit=99999999999").unwrap_err();
+    assert_snapshot!(error, @"Invalid value in parameter `limit`: value: `99999999999` is too large to be deserialized, maximum value authorized is `4294967295`");
+
+    let error = deserialize("limit=%2B1").unwrap_err();
+    assert_snapshot!(error, @"Invalid value type for parameter `limit`: expected a positive integer, but found a string: `+1`");
+
+    let error = deserialize("ranking_score_threshold=NaN").unwrap_err();
+    assert_snapshot!(error, @"Invalid value type for parameter `ranking_score_threshold`: expected a number, but found a string: `NaN`");
+
+    let error = deserialize("exhaustive=yes").unwrap_err();
+    assert_snapshot!(error, @"Invalid value type for parameter `exhaustive`: expected a boolean, but found a string: `yes`");
+
+    let error = deserialize("limt=1").unwrap_err();
+    assert_snapshot!(error, @"Unknown parameter `limt`: did you mean `limit`? expected one of `q`, `limit`, `offset`, `ranking_score_threshold`, `exhaustive`");
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Filter {
+    #[deserr(default)]
+    tags: Vec<String>,
+    #[deserr(default)]
+    ids: Vec<u32>,
+    #[deserr(default)]
+    range: Option<Range>,
+    #[deserr(default)]
+    sort: Vec<Sort>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Range {
+    min: u32,
+    max: u32,
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Sort {
+    field: String,
+}
+
+fn deserialize_with(query: &str, config: QueryConfig) -> Result<Filter, QueryParamError> {
+    let value = QueryValue::from_query_with_config(query, config).unwrap();
+    deserr::deserialize::<Filter, _, QueryParamError>(value)
+}
+
+#[test]
+fn single_value_sequence() {
+    let data = deserialize_with("tags=dog&ids=1", QueryConfig::default()).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Filter {
+        tags: [
+            "dog",
+        ],
+        ids: [
+            1,
+        ],
+        range: None,
+        sort: [],
+    }
+    "###);
+}
+
+#[test]
+fn repeated_keys() {
+    let config = QueryConfig::new().repeated_keys(true);
+    let data = deserialize_with("tags=dog&tags=cat&ids=1&ids=2", config).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Filter {
+        tags: [
+            "dog",
+            "cat",
+        ],
+        ids: [
+            1,
+            2,
+        ],
+        range: None,
+        sort: [],
+    }
+    "###);
+
+    // without the option only the last value is kept
+    let data = deserialize_with("tags=dog&tags=cat", QueryConfig::default()).unwrap();
+
+    assert_debug_snapshot!(data.tags, @r###"
+    [
+        "cat",
+    ]
+    "###);
+
+    let error = deserialize_with("ids=1&ids=abc", config).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type for parameter `ids[1]`: expected a positive integer, but found a string: `abc`");
+}
+
+#[test]
+fn comma_separated() {
+    let config = QueryConfig::new().comma_separated(true);
+    let data = deserialize_with("tags=dog,cat&ids=1,2,3", config).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Filter {
+        tags: [
+            "dog",
+            "cat",
+        ],
+        ids: [
+            1,
+            2,
+            3,
+        ],
+        range: None,
+        sort: [],
+    }
+    "###);
+
+    // the commas and the repeated keys can be mixed
+    let config = config.repeated_keys(true);
+    let data = deserialize_with("ids=1,2&ids=3", config).unwrap();
+
+    assert_debug_snapshot!(data.ids, @r###"
+    [
+        1,
+        2,
+        3,
+    ]
+    "###);
+
+    let error = deserialize_with("ids=1,,3", config).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type for parameter `ids[1]`: expected a positive integer, but found a string: ``");
+}
+
+#[test]
+fn brackets() {
+    let config = QueryConfig::new().brackets(true);
+    let data = deserialize_with(
+        "range[min]=2&range[max]=4&tags[]=dog&tags[]=cat&sort[][field]=price&sort[][field]=date",
+        config,
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Filter {
+        tags: [
+            "dog",
+            "cat",
+        ],
+        ids: [],
+        range: Some(
+            Range {
+                min: 2,
+                max: 4,
+            },
+        ),
+        sort: [
+            Sort {
+                field: "price",
+            },
+            Sort {
+                field: "date",
+            },
+        ],
+    }
+    "###);
+
+    // the brackets can be percent-encoded
+    let data = deserialize_with("range%5Bmin%5D=2&range%5Bmax%5D=4", config).unwrap();
+
+    assert_debug_snapshot!(data.range, @r###"
+    Some(
+        Range {
+            min: 2,
+            max: 4,
+        },
+    )
+    "###);
+
+    let error = deserialize_with("range[min]=2", config).unwrap_err();
+    assert_snapshot!(error, @"Missing parameter `max` inside `range`");
+
+    let error = deserialize_with("range[min]=2&range[max]=abc", config).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type for parameter `range.max`: expected a positive integer, but found a string: `abc`");
+
+    // malformed brackets are kept in the name of the parameter
+    let error = deserialize_with("range[min=2", config).unwrap_err();
+    assert_snapshot!(error, @"Unknown parameter `range[min`: expected one of `tags`, `ids`, `range`, `sort`");
+
+    // without the option the brackets are part of the name of the parameter
+    let error = deserialize_with("range[min]=2", QueryConfig::default()).unwrap_err();
+    assert_snapshot!(error, @"Unknown parameter `range[min]`: expected one of `tags`, `ids`, `range`, `sort`");
+}
+
+#[test]
+fn big_numbers() {
+    let value =
+        QueryValue::from_query("max=340282366920938463463374607431768211455&min=-1&price=12.50")
+            .unwrap();
+    let numbers = deserr::deserialize::<
+        std::collections::BTreeMap<String, deserr::Decimal>,
+        _,
+        QueryParamError,
+    >(value.clone())
+    .unwrap();
+    assert_debug_snapshot!(numbers, @r###"
+    {
+        "max": Decimal(
+            "340282366920938463463374607431768211455",
+        ),
+        "min": Decimal(
+            "-1",
+        ),
+        "price": Decimal(
+            "12.50",
+        ),
+    }
+    "###);
+
+    let value = QueryValue::from_query("max=340282366920938463463374607431768211456").unwrap();
+    let err =
+        deserr::deserialize::<std::collections::BTreeMap<String, u128>, _, QueryParamError>(value)
+            .unwrap_err();
+    assert_snapshot!(err, @"Invalid value for parameter `max`: expected a value between 0 and 340282366920938463463374607431768211455, but found 340282366920938463463374607431768211456");
+}
+
+#[test]
+fn bracket_errors() {
+    let config = QueryConfig::new().brackets(true).repeated_keys(true);
+    let error = |query: &str| {
+        QueryValue::from_query_with_config(query, config)
+            .unwrap_err()
+            .to_string()
+    };
+
+    // a parameter can't be both a value and a map
+    assert_snapshot!(error("range=2&range[min]=1"), @"parameter `range` is both a value and a map");
+    assert_snapshot!(error("range[min]=1&range=2"), @"parameter `range` is both a value and a map");
+    assert_snapshot!(error("range[]=1&range[min]=1"), @"parameter `range` is both a value and a map");
+    assert_snapshot!(error("range[min]=1&range[]=1"), @"parameter `range` is both a value and a map");
+    assert_snapshot!(error("range[min]=1&range[min][a]=1"), @"parameter `range[min]` is both a value and a map");
+
+    // but a value and a sequence make a longer sequence
+    let data = deserialize_with("tags=dog&tags[]=cat", config).unwrap();
+    assert_eq!(data.tags, ["dog", "cat"]);
+
+    let query = format!("range{}=1", "[a]".repeat(32));
+    assert!(QueryValue::from_query_with_config(&query, config).is_ok());
+    let query = format!("range{}=1", "[a]".repeat(33));
+    assert_snapshot!(error(&query), @"parameter `range` is nested more than 32 times");
+}
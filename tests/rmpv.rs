@@ -0,0 +1,93 @@
+use deserr::{errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use rmpv::Value;
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Request {
+    id: u64,
+    offset: i64,
+    ratio: f32,
+    payload: Vec<u8>,
+    #[deserr(default)]
+    extension: Option<Vec<u8>>,
+    #[deserr(default)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn values() {
+    let value = Value::Map(vec![
+        (Value::from("id"), Value::from(42)),
+        (Value::from("offset"), Value::from(-7)),
+        (Value::from("ratio"), Value::F32(0.5)),
+        (Value::from("payload"), Value::Binary(vec![1, 2])),
+        (Value::from("extension"), Value::Ext(1, vec![3])),
+        (
+            Value::from("tags"),
+            Value::Array(vec![Value::from("a"), Value::from("b")]),
+        ),
+    ]);
+    let request = deserr::deserialize::<Request, _, JsonError>(value).unwrap();
+    assert_debug_snapshot!(request, @r###"
+    Request {
+        id: 42,
+        offset: -7,
+        ratio: 0.5,
+        payload: [
+            1,
+            2,
+        ],
+        extension: Some(
+            [
+                3,
+            ],
+        ),
+        tags: [
+            "a",
+            "b",
+        ],
+    }
+    "###);
+}
+
+#[test]
+fn error_msg() {
+    let value = Value::Map(vec![
+        (Value::from("id"), Value::from(42)),
+        (Value::from("offset"), Value::from(0)),
+        (Value::from("ratio"), Value::F64(1.0)),
+        (Value::from("payload"), Value::Binary(vec![])),
+        (Value::from("tags"), Value::Binary(vec![1, 2])),
+    ]);
+    let err = deserr::deserialize::<Request, _, JsonError>(value).unwrap_err();
+    assert_snapshot!(err, @"Invalid value type at `.tags[0]`: expected a string, but found a positive integer: `1`");
+
+    // the strings which aren't valid UTF-8 are byte strings
+    let invalid = rmpv::decode::read_value(&mut &[0xa2, 0xff, 0xfe][..]).unwrap();
+    let value = Value::Map(vec![
+        (Value::from("id"), Value::from(42)),
+        (Value::from("offset"), Value::from(0)),
+        (Value::from("ratio"), Value::F64(1.0)),
+        (Value::from("payload"), Value::Binary(vec![])),
+        (Value::from("tags"), Value::Array(vec![invalid])),
+    ]);
+    let err = deserr::deserialize::<Request, _, JsonError>(value).unwrap_err();
+    assert_snapshot!(err, @"Invalid value type at `.tags[0]`: expected a string, but found a byte string: `[255, 254]`");
+
+    let value = Value::Map(vec![(Value::from("id"), Value::Binary(vec![1]))]);
+    let err = deserr::deserialize::<Request, _, JsonError>(value).unwrap_err();
+    assert_snapshot!(err, @"Invalid value type at `.id`: expected a positive integer, but found a byte string: `[1]`");
+}
+
+#[test]
+fn duplicated_keys() {
+    let value = Value::Map(vec![
+        (Value::from("a"), Value::from(1)),
+        (Value::from("b"), Value::from(2)),
+        (Value::from("a"), Value::from(3)),
+    ]);
+    let err = deserr::deserialize::<deserr::OwnedValue, _, JsonError>(value).unwrap_err();
+    assert_snapshot!(err, @"Invalid value: the key `a` is specified multiple times");
+}
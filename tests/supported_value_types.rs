@@ -1,5 +1,5 @@
-use deserr::{deserialize, errors::JsonError, Deserr};
-use insta::assert_debug_snapshot;
+use deserr::{deserialize, errors::JsonError, value, Decimal, Deserr, OwnedValue};
+use insta::{assert_debug_snapshot, assert_snapshot};
 use serde_json::json;
 
 #[test]
@@ -59,6 +59,65 @@ fn numbers() {
     "###);
 }
 
+#[test]
+fn big_numbers() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        u128: u128,
+        i128: i128,
+        f64: f64,
+        decimals: Vec<Decimal>,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(value!({
+       "u128": u128::MAX,
+       "i128": i128::MIN,
+       "f64": (OwnedValue::Number(String::from("1.5e300"))),
+       "decimals": [1, -1, 0.5, (OwnedValue::Number(String::from("0.1000000000000000000001")))],
+    }))
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        u128: 340282366920938463463374607431768211455,
+        i128: -170141183460469231731687303715884105728,
+        f64: 1.5e300,
+        decimals: [
+            Decimal(
+                "1",
+            ),
+            Decimal(
+                "-1",
+            ),
+            Decimal(
+                "0.5",
+            ),
+            Decimal(
+                "0.1000000000000000000001",
+            ),
+        ],
+    }
+    "###);
+
+    let error = deserialize::<u64, _, JsonError>(value!(u128::MAX)).unwrap_err();
+    assert_snapshot!(error, @"Invalid value: expected a value between 0 and 18446744073709551615, but found 340282366920938463463374607431768211455");
+    let error = deserialize::<i64, _, JsonError>(value!(i128::MIN)).unwrap_err();
+    assert_snapshot!(error, @"Invalid value: expected a value between -9223372036854775808 and 9223372036854775807, but found -170141183460469231731687303715884105728");
+    let error = deserialize::<u128, _, JsonError>(value!(i128::MIN)).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type: expected a positive integer, but found a number: `-170141183460469231731687303715884105728`");
+    let error =
+        deserialize::<u128, _, JsonError>(OwnedValue::Number(String::from("1.5"))).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type: expected a positive integer, but found a number: `1.5`");
+    let error =
+        deserialize::<f32, _, JsonError>(OwnedValue::Number(String::from("1e300"))).unwrap_err();
+    assert_snapshot!(error, @"Invalid value: value: `1e300` is outside of the range of a `f32`");
+    let error = deserialize::<Decimal, _, JsonError>(value!(f64::NAN)).unwrap_err();
+    assert_snapshot!(error, @"Invalid value: value: `NaN` is not a decimal number");
+    let error = deserialize::<Decimal, _, JsonError>(value!("1.5")).unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type: expected a number, but found a string: `"1.5"`"###);
+}
+
 #[test]
 fn strings() {
     #[allow(dead_code)]
@@ -233,4 +292,10 @@ fn array() {
         ],
     }
     "###);
+
+    // JSON has no byte strings, so only the arrays are expected
+    let error = deserialize::<Struct3, _, JsonError>(json!({ "arr": "doggo" })).unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type at `.arr`: expected an array, but found a string: `"doggo"`"###);
+    let error = deserialize::<Vec<u8>, _, JsonError>(json!("doggo")).unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type: expected an array, but found a string: `"doggo"`"###);
 }
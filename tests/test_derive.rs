@@ -34,6 +34,38 @@ pub enum DefaultErrorContent {
         actual: usize,
         expected: usize,
     },
+    OutOfRange {
+        actual: String,
+        min: Option<String>,
+        max: Option<String>,
+    },
+    BadLength {
+        actual: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    EmptyValue,
+    PatternMismatch {
+        value: String,
+        pattern: String,
+    },
+    MissingRequiredFields {
+        field: String,
+        missing: Vec<String>,
+    },
+    ConflictingFields {
+        field: String,
+        conflicting: Vec<String>,
+    },
+    NotExactlyOneOf {
+        fields: Vec<String>,
+        present: Vec<String>,
+    },
+    Syntax {
+        msg: String,
+        line: usize,
+        column: usize,
+    },
     Validation,
 }
 
@@ -82,6 +114,40 @@ impl DeserializeError for DefaultError {
                 expected,
             },
             ErrorKind::Unexpected { msg } => DefaultErrorContent::Unexpected(msg),
+            ErrorKind::OutOfRange { actual, min, max } => DefaultErrorContent::OutOfRange {
+                actual: actual.to_string(),
+                min: min.map(|min| min.to_string()),
+                max: max.map(|max| max.to_string()),
+            },
+            ErrorKind::BadLength { actual, min, max } => {
+                DefaultErrorContent::BadLength { actual, min, max }
+            }
+            ErrorKind::EmptyValue => DefaultErrorContent::EmptyValue,
+            ErrorKind::PatternMismatch { value, pattern } => DefaultErrorContent::PatternMismatch {
+                value: value.to_string(),
+                pattern: pattern.to_string(),
+            },
+            ErrorKind::MissingRequiredFields { field, missing } => {
+                DefaultErrorContent::MissingRequiredFields {
+                    field: field.to_string(),
+                    missing: missing.iter().map(|x| x.to_string()).collect(),
+                }
+            }
+            ErrorKind::ConflictingFields { field, conflicting } => {
+                DefaultErrorContent::ConflictingFields {
+                    field: field.to_string(),
+                    conflicting: conflicting.iter().map(|x| x.to_string()).collect(),
+                }
+            }
+            ErrorKind::NotExactlyOneOf { fields, present } => {
+                DefaultErrorContent::NotExactlyOneOf {
+                    fields: fields.iter().map(|x| x.to_string()).collect(),
+                    present: present.iter().map(|x| x.to_string()).collect(),
+                }
+            }
+            ErrorKind::Syntax { msg, line, column } => {
+                DefaultErrorContent::Syntax { msg, line, column }
+            }
         };
         ControlFlow::Break(Self {
             location: location.to_owned(),
@@ -347,6 +413,7 @@ struct Generic<A> {
     some_field: A,
 }
 
+#[allow(dead_code)]
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Deserr)]
 #[deserr(where_predicate = __Deserr_E: MergeWithError<DefaultError>, where_predicate = A: Deserr<DefaultError>)]
 struct Generic2<A> {
@@ -367,6 +434,7 @@ struct FieldMap {
     some_field: Option<u8>,
 }
 
+#[allow(dead_code)]
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Deserr)]
 #[deserr(where_predicate = Option<u8> : Deserr<__Deserr_E>)]
 struct FieldConditions {
@@ -382,12 +450,14 @@ fn parse_hello(b: bool) -> Result<Hello, NeverError> {
         false => Ok(Hello::B),
     }
 }
+#[allow(dead_code)]
 fn parse_hello2(b: bool) -> Result<Hello2, NeverError> {
     match b {
         true => Ok(Hello2::A),
         false => Ok(Hello2::B),
     }
 }
+#[allow(dead_code)]
 fn parse_hello3(b: &str) -> Result<Hello3, DefaultError> {
     match b {
         "A" => Ok(Hello3::A),
@@ -406,6 +476,7 @@ enum Hello {
     B,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, PartialEq, Deserr)]
 #[deserr(error = DefaultError, try_from(bool) = parse_hello2 -> NeverError)]
 enum Hello2 {
@@ -413,6 +484,7 @@ enum Hello2 {
     B,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, PartialEq, Deserr)]
 #[deserr(try_from(& String) = parse_hello3 -> DefaultError)]
 enum Hello3 {
@@ -420,18 +492,21 @@ enum Hello3 {
     B,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, PartialEq, Deserr)]
 #[deserr(where_predicate = Hello: Deserr<__Deserr_E>)]
 struct ContainsHello {
     _x: Hello,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, PartialEq, Deserr)]
 #[deserr(error = DefaultError)]
 struct ContainsHello2 {
     _x: Hello,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, PartialEq, Deserr)]
 struct ContainsHello3 {
     #[deserr(needs_predicate)]
@@ -460,6 +535,7 @@ fn validate_it(x: Validated, _location: ValuePointerRef) -> Result<Validated, My
     }
 }
 
+#[allow(dead_code)]
 fn validate_it2(
     x: Validated2,
     _location: ValuePointerRef,
@@ -478,6 +554,7 @@ struct Validated {
     y: u16,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Deserr)]
 #[deserr(error = DefaultError, validate = validate_it2 -> MyValidationError)]
 struct Validated2 {
@@ -564,6 +641,25 @@ pub struct Skipped2 {
     z: bool,
 }
 
+#[derive(PartialEq, Eq, Debug, Deserr)]
+pub struct Validated3 {
+    #[deserr(range(min = 1, max = 10))]
+    x: u8,
+    #[deserr(default, length(max = 2))]
+    y: Vec<u8>,
+}
+
+#[derive(PartialEq, Eq, Debug, Deserr)]
+#[deserr(requires(x, y), exactly_one_of(y, z))]
+pub struct Constrained {
+    #[deserr(default)]
+    x: Option<u8>,
+    #[deserr(default)]
+    y: Option<u8>,
+    #[deserr(default)]
+    z: Option<u8>,
+}
+
 #[track_caller]
 fn compare_with_serde_roundtrip<T>(x: T)
 where
@@ -691,6 +787,18 @@ fn test_de() {
         "#,
     );
 
+    // enum with an unknown tag value
+    assert_error_matches::<EnumWithOptionData, DefaultError>(
+        r#"{ "t": "C" }"#,
+        DefaultError {
+            location: ValuePointerRef::Origin.push_key("t").to_owned(),
+            content: DefaultErrorContent::UnknownValue {
+                value: "C".to_owned(),
+                accepted: vec!["A".to_owned(), "B".to_owned()],
+            },
+        },
+    );
+
     // struct with renamed field, roundtrip
     compare_with_serde_roundtrip(StructWithRenamedField { x: true });
 
@@ -950,4 +1058,62 @@ fn test_de() {
             },
         },
     );
+
+    // Validation attributes
+    assert_ok_matches::<Validated3, DefaultError>(
+        r#"{ "x": 10 }"#,
+        Validated3 { x: 10, y: vec![] },
+    );
+    assert_error_matches::<Validated3, DefaultError>(
+        r#"{ "x": 0 }"#,
+        DefaultError {
+            location: ValuePointerRef::Origin.push_key("x").to_owned(),
+            content: DefaultErrorContent::OutOfRange {
+                actual: "0".to_owned(),
+                min: Some("1".to_owned()),
+                max: Some("10".to_owned()),
+            },
+        },
+    );
+    assert_error_matches::<Validated3, DefaultError>(
+        r#"{ "x": 2, "y": [1, 2, 3] }"#,
+        DefaultError {
+            location: ValuePointerRef::Origin.push_key("y").to_owned(),
+            content: DefaultErrorContent::BadLength {
+                actual: 3,
+                min: None,
+                max: Some(2),
+            },
+        },
+    );
+
+    // Constraints between fields
+    assert_ok_matches::<Constrained, DefaultError>(
+        r#"{ "x": 1, "y": 2 }"#,
+        Constrained {
+            x: Some(1),
+            y: Some(2),
+            z: None,
+        },
+    );
+    assert_error_matches::<Constrained, DefaultError>(
+        r#"{ "x": 1, "z": 3 }"#,
+        DefaultError {
+            location: ValuePointerRef::Origin.to_owned(),
+            content: DefaultErrorContent::MissingRequiredFields {
+                field: "x".to_owned(),
+                missing: vec!["y".to_owned()],
+            },
+        },
+    );
+    assert_error_matches::<Constrained, DefaultError>(
+        r#"{ "y": 2, "z": 3 }"#,
+        DefaultError {
+            location: ValuePointerRef::Origin.to_owned(),
+            content: DefaultErrorContent::NotExactlyOneOf {
+                fields: vec!["y".to_owned(), "z".to_owned()],
+                present: vec!["y".to_owned(), "z".to_owned()],
+            },
+        },
+    );
 }
@@ -0,0 +1,10 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(requires(page, hits_per_page))]
+struct Search {
+    #[deserr(default)]
+    page: Option<usize>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(tag = "type")]
+enum Event {
+    Created { id: u32 },
+    #[deserr(other)]
+    Unknown { id: u32 },
+}
+
+fn main() {}
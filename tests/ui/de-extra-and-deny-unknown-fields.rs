@@ -0,0 +1,13 @@
+use std::collections::BTreeMap;
+
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Webhook {
+    event: String,
+    #[deserr(extra)]
+    metadata: BTreeMap<String, String>,
+}
+
+fn main() {}
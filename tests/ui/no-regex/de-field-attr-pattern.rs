@@ -0,0 +1,9 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+struct Index {
+    #[deserr(pattern = "^[a-z]+$")]
+    uid: String,
+}
+
+fn main() {}
@@ -0,0 +1,133 @@
+use std::convert::Infallible;
+
+use deserr::errors::{JsonError, QueryParamError};
+use deserr::query_string::QueryConfig;
+use deserr::warp::{WarpJson, WarpQuery, WarpRejection};
+use deserr::Deserr;
+use insta::assert_snapshot;
+use warp::http::StatusCode;
+use warp::{Filter, Rejection, Reply};
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Search {
+    q: String,
+    #[deserr(default)]
+    limit: Option<u32>,
+    #[deserr(default)]
+    filter: Option<SearchFilter>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct SearchFilter {
+    genre: String,
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Doggo {
+    name: String,
+    age: u8,
+}
+
+async fn recover(rejection: Rejection) -> Result<warp::reply::Response, Infallible> {
+    if let Some(WarpRejection(error)) = rejection.find::<WarpRejection<JsonError>>() {
+        Ok(error.clone().into_response())
+    } else if let Some(WarpRejection(error)) = rejection.find::<WarpRejection<QueryParamError>>() {
+        Ok(error.clone().into_response())
+    } else {
+        Ok(
+            warp::reply::with_status(format!("{rejection:?}"), StatusCode::NOT_FOUND)
+                .into_response(),
+        )
+    }
+}
+
+fn app() -> impl Filter<Extract = (warp::reply::Response,), Error = Infallible> + Clone {
+    let search = warp::get()
+        .and(warp::path("search"))
+        .and(deserr::warp::query_with_config(
+            QueryConfig::new().brackets(true),
+        ))
+        .map(|item: WarpQuery<Search, QueryParamError>| format!("{:?}", item.0));
+    let doggo = warp::post()
+        .and(warp::path("doggo"))
+        .and(deserr::warp::json())
+        .map(|item: WarpJson<Doggo, JsonError>| format!("{:?}", item.0));
+    search
+        .or(doggo)
+        .unify()
+        .map(|reply: String| reply.into_response())
+        .recover(recover)
+        .unify()
+}
+
+async fn get_query(query: &str) -> (StatusCode, String) {
+    let response = warp::test::request()
+        .path(&format!("/search?{query}"))
+        .reply(&app())
+        .await;
+    let body = String::from_utf8(response.body().to_vec()).unwrap();
+    (response.status(), body)
+}
+
+async fn post_json(content_type: Option<&str>, body: &'static str) -> (StatusCode, String) {
+    let mut request = warp::test::request()
+        .method("POST")
+        .path("/doggo")
+        .body(body);
+    if let Some(content_type) = content_type {
+        request = request.header("content-type", content_type);
+    }
+    let response = request.reply(&app()).await;
+    let body = String::from_utf8(response.body().to_vec()).unwrap();
+    (response.status(), body)
+}
+
+#[tokio::test]
+async fn query() {
+    let (status, body) = get_query("q=doggo&limit=12&filter[genre]=comedy").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Search { q: "doggo", limit: Some(12), filter: Some(SearchFilter { genre: "comedy" }) }"###);
+}
+
+#[tokio::test]
+async fn query_errors() {
+    let (status, body) = get_query("limit=12").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing parameter `q`");
+
+    let (status, body) = get_query("q=doggo&limit=twelve").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid value type for parameter `limit`: expected a positive integer, but found a string: `twelve`");
+}
+
+#[tokio::test]
+async fn json() {
+    let (status, body) = post_json(Some("application/json"), r#"{"name":"doggo","age":3}"#).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_snapshot!(body, @r###"Doggo { name: "doggo", age: 3 }"###);
+}
+
+#[tokio::test]
+async fn json_errors() {
+    let (status, body) = post_json(Some("application/json"), r#"{"name":"doggo"}"#).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing field `age`");
+
+    let (status, body) = post_json(Some("application/json"), r#"{"name":"doggo","age":"#).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid JSON at line 1, column 22: EOF while parsing a value");
+
+    let (status, body) = post_json(None, r#"{"name":"doggo","age":3}"#).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Missing `Content-Type` header: expected `application/json`");
+
+    let (status, body) = post_json(Some("text/plain"), r#"{"name":"doggo","age":3}"#).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_snapshot!(body, @"Invalid `Content-Type` header `text/plain`: expected `application/json`");
+}